@@ -0,0 +1,41 @@
+//! Floating-point functions outside `core`, taken from `libm` under `core-only`
+//!
+//! The standard library provides these as inherent methods of `f64`; the trait
+//! gives the same method syntax without it, so the astronomy reads alike in both
+//! builds.
+
+/// The `f64` methods of the standard library used by the astronomy
+pub(crate) trait Float {
+    fn sin(self) -> f64;
+    fn cos(self) -> f64;
+    fn atan2(self, other: f64) -> f64;
+    fn sqrt(self) -> f64;
+    fn floor(self) -> f64;
+    fn powi(self, n: i32) -> f64;
+}
+
+impl Float for f64 {
+    fn sin(self) -> f64 {
+        libm::sin(self)
+    }
+
+    fn cos(self) -> f64 {
+        libm::cos(self)
+    }
+
+    fn atan2(self, other: f64) -> f64 {
+        libm::atan2(self, other)
+    }
+
+    fn sqrt(self) -> f64 {
+        libm::sqrt(self)
+    }
+
+    fn floor(self) -> f64 {
+        libm::floor(self)
+    }
+
+    fn powi(self, n: i32) -> f64 {
+        libm::pow(self, n as f64)
+    }
+}
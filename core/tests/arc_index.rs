@@ -0,0 +1,76 @@
+//! Sweeps angles at and around the 0°/360° seam through the lookups of the limbs,
+//! which must stay inside their tables where a bare division reaches the count.
+
+use panchang_core::{arc_index, karana_index, nakshatra_pada};
+
+/// Tithis, Nakshatras and Yogas, and Rashis
+const COUNTS: [usize; 3] = [30, 27, 12];
+
+#[test]
+fn full_circle_wraps_to_the_first_arc() {
+    for count in COUNTS {
+        assert_eq!(arc_index(0.0, count), 0, "{} arcs", count);
+        assert_eq!(arc_index(360.0, count), 0, "{} arcs", count);
+        assert_eq!(arc_index(720.0, count), 0, "{} arcs", count);
+    }
+}
+
+#[test]
+fn just_short_of_the_circle_is_the_last_arc() {
+    for count in COUNTS {
+        assert_eq!(arc_index(359.9999, count), count - 1, "{} arcs", count);
+        assert_eq!(arc_index(-0.0001, count), count - 1, "{} arcs", count);
+        // A negative rounding error, which `rev` rounds up to exactly 360°
+        assert_eq!(arc_index(-1e-15, count), count - 1, "{} arcs", count);
+        assert_eq!(
+            arc_index(360.0 - f64::EPSILON * 256.0, count),
+            count - 1,
+            "{} arcs",
+            count
+        );
+    }
+}
+
+#[test]
+fn sweep_stays_inside_the_tables() {
+    for count in COUNTS {
+        let mut angle = -720.0;
+        while angle <= 720.0 {
+            assert!(
+                arc_index(angle, count) < count,
+                "{}° in {} arcs",
+                angle,
+                count
+            );
+            angle += 0.0625;
+        }
+        for angle in [f64::MAX, f64::MIN, f64::INFINITY, f64::NAN] {
+            assert!(
+                arc_index(angle, count) < count,
+                "{}° in {} arcs",
+                angle,
+                count
+            );
+        }
+    }
+}
+
+#[test]
+fn tithi_at_new_and_full_moon() {
+    // Amavasya ends and Shukla Pratipada begins at 0°; Poornima ends at 180°
+    assert_eq!(arc_index(359.9999, 30), 29);
+    assert_eq!(arc_index(360.0, 30), 0);
+    assert_eq!(arc_index(179.9999, 30), 14);
+    assert_eq!(arc_index(180.0, 30), 15);
+}
+
+#[test]
+fn karana_and_pada_at_the_seam() {
+    // Kimstughna opens the month, Naga closes it
+    assert_eq!(karana_index(0.0), 10);
+    assert_eq!(karana_index(360.0), 10);
+    assert_eq!(karana_index(359.9999), 9);
+    // Revati's fourth pada ends the zodiac, Ashwini's first begins it
+    assert_eq!(nakshatra_pada(359.9999), 4);
+    assert_eq!(nakshatra_pada(360.0), 1);
+}
@@ -0,0 +1,51 @@
+//! Checks each Ayanamsa system at J2000.0 against the values the Swiss Ephemeris
+//! publishes for it, and that all of them precess at the same rate.
+
+use panchang_core::{Ayanamsa, calc_ayanamsa};
+
+/// Day number of J2000.0, 2000-01-01 12h, counted from 1999-12-31 0h
+const J2000: f64 = 1.5;
+
+/// Largest accepted difference from the published values, in degrees (about 18″):
+/// the Swiss Ephemeris refers its values to the true equinox, while the crate's
+/// Lahiri formula is a mean value without nutation
+const TOLERANCE: f64 = 0.005;
+
+/// Converts degrees, arcminutes and arcseconds to degrees
+fn dms(degrees: f64, minutes: f64, seconds: f64) -> f64 {
+    degrees + minutes / 60.0 + seconds / 3600.0
+}
+
+#[test]
+fn j2000_values_match_the_swiss_ephemeris() {
+    let published = [
+        (Ayanamsa::Lahiri, dms(23.0, 51.0, 25.53)),
+        (Ayanamsa::Raman, dms(22.0, 24.0, 38.85)),
+        (Ayanamsa::KP, dms(23.0, 45.0, 36.86)),
+        (Ayanamsa::FaganBradley, dms(24.0, 44.0, 25.08)),
+    ];
+    for (system, expected) in published {
+        // The crate returns the Ayanamsa negated, to be added to tropical longitudes
+        let ayanamsa = -calc_ayanamsa(J2000, system);
+        assert!(
+            (ayanamsa - expected).abs() < TOLERANCE,
+            "{:?}: {} against {}",
+            system,
+            ayanamsa,
+            expected
+        );
+    }
+}
+
+#[test]
+fn systems_precess_together() {
+    // A century later every system has moved by the same 1.397° or so
+    let century = 36525.0;
+    let lahiri =
+        calc_ayanamsa(J2000 + century, Ayanamsa::Lahiri) - calc_ayanamsa(J2000, Ayanamsa::Lahiri);
+    assert!((-lahiri - 1.397).abs() < 0.01, "{}", lahiri);
+    for system in [Ayanamsa::Raman, Ayanamsa::KP, Ayanamsa::FaganBradley] {
+        let drift = calc_ayanamsa(J2000 + century, system) - calc_ayanamsa(J2000, system);
+        assert!((drift - lahiri).abs() < 1e-9, "{:?}", system);
+    }
+}
@@ -0,0 +1,46 @@
+//! Checks the Karanas at the month's boundary, where the three fixed Karanas closing
+//! the month and the one opening it replace the movable ones.
+
+use panchang_core::karana_index;
+
+const BAVA: usize = 0;
+const VISTI: usize = 6;
+const SAKUNI: usize = 7;
+const CHATUSPADA: usize = 8;
+const NAGA: usize = 9;
+const KIMSTUGHNA: usize = 10;
+
+#[test]
+fn fixed_karanas_take_the_half_tithis_around_new_moon() {
+    // Half-Tithi 56 is the last Visti, 57 to 59 the closing fixed Karanas
+    assert_eq!(karana_index(341.99), VISTI);
+    assert_eq!(karana_index(342.0), SAKUNI);
+    assert_eq!(karana_index(347.99), SAKUNI);
+    assert_eq!(karana_index(348.0), CHATUSPADA);
+    assert_eq!(karana_index(354.0), NAGA);
+    assert_eq!(karana_index(359.99), NAGA);
+    // New moon opens the month with Kimstughna, and Bava follows at 6°
+    assert_eq!(karana_index(360.0), KIMSTUGHNA);
+    assert_eq!(karana_index(0.0), KIMSTUGHNA);
+    assert_eq!(karana_index(-0.01), NAGA);
+    assert_eq!(karana_index(5.99), KIMSTUGHNA);
+    assert_eq!(karana_index(6.0), BAVA);
+}
+
+#[test]
+fn month_runs_through_every_karana_in_order() {
+    let karanas: Vec<usize> = (0..60)
+        .map(|half_tithi| karana_index(half_tithi as f64 * 6.0 + 3.0))
+        .collect();
+
+    assert_eq!(karanas[0], KIMSTUGHNA);
+    for (round, movable) in karanas[1..57].chunks(7).enumerate() {
+        assert_eq!(movable, [0, 1, 2, 3, 4, 5, 6], "round {}", round + 1);
+    }
+    assert_eq!(karanas[57..], [SAKUNI, CHATUSPADA, NAGA]);
+
+    // Each fixed Karana comes once in the month
+    for fixed in [SAKUNI, CHATUSPADA, NAGA, KIMSTUGHNA] {
+        assert_eq!(karanas.iter().filter(|karana| **karana == fixed).count(), 1);
+    }
+}
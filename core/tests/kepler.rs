@@ -0,0 +1,83 @@
+//! Checks that Kepler's equation converges in a few steps for the Earth's orbit, and
+//! that the iteration cap stops the search on eccentricities near 1, where Newton's
+//! method wanders before it settles.
+
+use panchang_core::{
+    KEPLER_MAX_ITERATIONS, KeplerOptions, KeplerSolution, solve_kepler, sun_long, sun_long_with,
+};
+
+/// Eccentricity of the Earth's orbit at J2000
+const EARTH: f64 = 0.016709;
+
+/// How far a solution misses Kepler's equation, in radians
+fn residual(mean_anomaly: f64, eccentricity: f64, solution: KeplerSolution) -> f64 {
+    let e = solution.eccentric_anomaly;
+    (e - eccentricity * e.sin() - mean_anomaly).abs()
+}
+
+#[test]
+fn earth_orbit_converges_within_three_steps() {
+    for degrees in 0..360 {
+        let mean_anomaly = (degrees as f64).to_radians();
+        let solution = solve_kepler(mean_anomaly, EARTH, KeplerOptions::STANDARD);
+        assert!(solution.converged, "M = {}°", degrees);
+        assert!(solution.iterations <= 3, "M = {}°: {:?}", degrees, solution);
+        assert!(
+            residual(mean_anomaly, EARTH, solution) < 1e-12,
+            "M = {}°",
+            degrees
+        );
+    }
+}
+
+#[test]
+fn precise_options_converge_tighter() {
+    for degrees in (0..360).step_by(15) {
+        let mean_anomaly = (degrees as f64).to_radians();
+        let solution = solve_kepler(mean_anomaly, EARTH, KeplerOptions::PRECISE);
+        assert!(solution.converged, "M = {}°: {:?}", degrees, solution);
+        assert!(solution.iterations <= 5, "M = {}°: {:?}", degrees, solution);
+        assert!(
+            residual(mean_anomaly, EARTH, solution) < 1e-15,
+            "M = {}°",
+            degrees
+        );
+    }
+
+    // The standard tolerance already leaves the Sun well within a milliarcsecond
+    for d in [-36525.0, 0.0, 8627.5, 36525.0] {
+        let difference = sun_long(d).longitude - sun_long_with(d, KeplerOptions::PRECISE).longitude;
+        assert!(difference.abs() < 1e-6, "d = {}: {}", d, difference);
+    }
+}
+
+#[test]
+fn cap_stops_a_wandering_search() {
+    // Near e = 1 and M = 0 the Newton steps overshoot far before settling
+    let (mean_anomaly, eccentricity) = (1e-6, 0.99999999);
+    let capped = solve_kepler(mean_anomaly, eccentricity, KeplerOptions::STANDARD);
+    assert!(!capped.converged);
+    assert_eq!(capped.iterations, KEPLER_MAX_ITERATIONS);
+    assert!(capped.eccentric_anomaly.is_finite());
+
+    let unbounded = KeplerOptions {
+        tolerance: 1e-12,
+        max_iterations: 1000,
+    };
+    let settled = solve_kepler(mean_anomaly, eccentricity, unbounded);
+    assert!(settled.converged);
+    assert!(settled.iterations < 1000);
+    assert!(residual(mean_anomaly, eccentricity, settled) < 1e-12);
+}
+
+#[test]
+fn no_iterations_returns_the_starting_guess() {
+    let options = KeplerOptions {
+        tolerance: KeplerOptions::STANDARD.tolerance,
+        max_iterations: 0,
+    };
+    let solution = solve_kepler(1.0, EARTH, options);
+    assert!(!solution.converged);
+    assert_eq!(solution.iterations, 0);
+    assert!(residual(1.0, EARTH, solution) < 1e-5);
+}
@@ -0,0 +1,82 @@
+//! Checks `moon_long` at new and full moons of 2000-2024 against the Sun.
+//!
+//! The instants are from the phase tables of F. Espenak (NASA GSFC), to the minute
+//! of UT, for the conjunctions and oppositions in ecliptic longitude behind the
+//! eclipses of those dates. At each the Moon's longitude equals the Sun's apparent
+//! longitude, or is 180° from it, and the Sun is itself checked against Meeus'
+//! examples in `twentieth_century.rs`.
+//!
+//! # Tolerance
+//!
+//! 0.01° (36″): the documented 0.5′ bound of `moon_long`, plus the 15″ the Moon
+//! gains on the Sun in the half minute the tables round to.
+
+use panchang_core::{days_since_j2000, delta_t, moon_long, rev, sun_long};
+
+const TOLERANCE: f64 = 0.01;
+
+/// Aberration of the Sun, by which its apparent longitude trails the geometric one
+const ABERRATION: f64 = 20.5 / 3600.0;
+
+/// Signed difference `a - b` between two longitudes, in (-180°, 180°]
+fn difference(a: f64, b: f64) -> f64 {
+    let difference = rev(a - b);
+    if difference > 180.0 {
+        difference - 360.0
+    } else {
+        difference
+    }
+}
+
+/// The Moon's longitude less the Sun's apparent longitude at an instant in UT
+fn elongation(day: i32, month: i32, year: i32, hour: u32, minute: u32) -> f64 {
+    let d = days_since_j2000(day, month, year) as f64 + (hour as f64 + minute as f64 / 60.0) / 24.0;
+    // The Sun's theory runs on dynamical time, the Moon's on universal time
+    let sun = sun_long(d + delta_t(d) / 86400.0).longitude - ABERRATION;
+    difference(moon_long(d), sun)
+}
+
+#[test]
+fn new_moons_are_conjunctions() {
+    let new_moons = [
+        (6, 1, 2000, 18, 14),
+        (21, 8, 2017, 18, 30),
+        (2, 7, 2019, 19, 16),
+        (8, 4, 2024, 18, 21),
+    ];
+    for (day, month, year, hour, minute) in new_moons {
+        let elongation = elongation(day, month, year, hour, minute);
+        assert!(
+            elongation.abs() < TOLERANCE,
+            "{}/{}/{} {:02}:{:02}: {}",
+            day,
+            month,
+            year,
+            hour,
+            minute,
+            elongation
+        );
+    }
+}
+
+#[test]
+fn full_moons_are_oppositions() {
+    let full_moons = [
+        (21, 1, 2000, 4, 40),
+        (27, 7, 2018, 20, 20),
+        (8, 11, 2022, 11, 2),
+    ];
+    for (day, month, year, hour, minute) in full_moons {
+        let offset = difference(elongation(day, month, year, hour, minute), 180.0);
+        assert!(
+            offset.abs() < TOLERANCE,
+            "{}/{}/{} {:02}:{:02}: {}",
+            day,
+            month,
+            year,
+            hour,
+            minute,
+            offset
+        );
+    }
+}
@@ -0,0 +1,126 @@
+//! Runs the astronomy the way an embedded or WASM caller would: from a `#![no_std]`
+//! crate, with no allocator and nothing but `core`.
+//!
+//! Built with `--features core-only` the library takes its trigonometry from `libm`,
+//! and the elements must still agree with `tests/data/reference.json` of the server:
+//!
+//! ```text
+//! cargo test -p panchang-core --features core-only
+//! ```
+
+#![no_std]
+
+use panchang_core::{Ayanamsa, arc_index, calculate_panchanga, elements_at};
+
+/// A reference instant, its zone in hours, and the expected Tithi index with the
+/// Nakshatra and Karana indices where the row states them
+struct Case {
+    date: (i32, i32, i32),
+    hour: f64,
+    zone: f64,
+    tithi: usize,
+    nakshatra: Option<usize>,
+    karana: Option<usize>,
+}
+
+/// Rows of the server's reference dataset: Raksha Bandhan during and after Bhadra,
+/// Janmashtami at midnight in Rohini, Diwali and Holika Dahan
+const CASES: [Case; 6] = [
+    Case {
+        date: (30, 8, 2023),
+        hour: 15.0,
+        zone: 5.5,
+        tithi: 14,
+        nakshatra: None,
+        karana: Some(6),
+    },
+    Case {
+        date: (30, 8, 2023),
+        hour: 23.0,
+        zone: 5.5,
+        tithi: 14,
+        nakshatra: None,
+        karana: Some(0),
+    },
+    Case {
+        date: (6, 9, 2023),
+        hour: 23.75,
+        zone: 5.5,
+        tithi: 22,
+        nakshatra: Some(3),
+        karana: None,
+    },
+    Case {
+        date: (12, 11, 2023),
+        hour: 19.0,
+        zone: 5.5,
+        tithi: 29,
+        nakshatra: None,
+        karana: None,
+    },
+    Case {
+        date: (24, 3, 2024),
+        hour: 18.0,
+        zone: 5.5,
+        tithi: 14,
+        nakshatra: None,
+        karana: Some(6),
+    },
+    Case {
+        date: (26, 8, 2024),
+        hour: 23.5,
+        zone: 5.5,
+        tithi: 22,
+        nakshatra: Some(3),
+        karana: None,
+    },
+];
+
+#[test]
+fn elements_match_reference_instants() {
+    for case in &CASES {
+        let (day, month, year) = case.date;
+        let elements =
+            calculate_panchanga(day, month, year, case.hour, case.zone, Ayanamsa::Lahiri);
+
+        assert_eq!(elements.tithi, case.tithi, "{:?}: Tithi", case.date);
+        assert_eq!(elements.paksha, elements.tithi / 15);
+        if let Some(nakshatra) = case.nakshatra {
+            assert_eq!(elements.nakshatra, nakshatra, "{:?}: Nakshatra", case.date);
+        }
+        if let Some(karana) = case.karana {
+            assert_eq!(elements.karana, karana, "{:?}: Karana", case.date);
+        }
+    }
+}
+
+#[test]
+fn longitudes_stay_in_range() {
+    // Every six hours across a few years, on both sides of J2000.0
+    for step in -8_000..8_000 {
+        let elements = elements_at(step as f64 / 4.0, Ayanamsa::FaganBradley);
+        for longitude in [
+            elements.sun_longitude,
+            elements.moon_longitude,
+            elements.sidereal_sun_longitude,
+            elements.sidereal_moon_longitude,
+        ] {
+            assert!((0.0..360.0).contains(&longitude));
+        }
+        assert!(elements.nakshatra < 27 && elements.yoga < 27 && elements.karana < 11);
+        assert!((1..=4).contains(&elements.nakshatra_pada));
+    }
+}
+
+#[test]
+fn negative_longitudes_fall_in_last_arc() {
+    // A longitude just below 0° is in Meena, the last Rashi, and Revati, the last
+    // Nakshatra, even when it is too small for 360° minus it to be representable
+    for longitude in [-0.5, -1e-9, -1e-13, -f64::MIN_POSITIVE] {
+        assert_eq!(arc_index(longitude, 12), 11, "Rashi of {}", longitude);
+        assert_eq!(arc_index(longitude, 27), 26, "Nakshatra of {}", longitude);
+    }
+    assert_eq!(arc_index(-0.0, 12), 0);
+    assert_eq!(arc_index(360.0, 12), 0);
+    assert_eq!(arc_index(-30.5, 12), 10);
+}
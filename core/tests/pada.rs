@@ -0,0 +1,38 @@
+//! Checks `nakshatra_pada` on either side of the 3°20′ boundaries, which must turn
+//! the pada over together with the Nakshatra every fourth time.
+
+use panchang_core::{arc_index, nakshatra_pada};
+
+/// Width of a pada in degrees, 3°20′
+const PADA: f64 = 10.0 / 3.0;
+
+/// An offset from a boundary well above the rounding of `PADA` multiples
+const EPSILON: f64 = 1e-9;
+
+#[test]
+fn padas_change_at_each_boundary() {
+    for boundary in 1..108 {
+        let longitude = boundary as f64 * PADA;
+        let before = nakshatra_pada(longitude - EPSILON);
+        let after = nakshatra_pada(longitude + EPSILON);
+        assert_eq!(before as usize, (boundary - 1) % 4 + 1, "{longitude}°");
+        assert_eq!(after as usize, boundary % 4 + 1, "{longitude}°");
+    }
+}
+
+#[test]
+fn first_pada_starts_each_nakshatra() {
+    // Bharani begins at 13°20′, Rohini at 40° and Revati at 346°40′
+    for (longitude, nakshatra) in [(40.0 / 3.0, 1), (40.0, 3), (1040.0 / 3.0, 26)] {
+        let inside = longitude + EPSILON;
+        assert_eq!(arc_index(inside, 27), nakshatra);
+        assert_eq!(nakshatra_pada(inside), 1);
+        assert_eq!(arc_index(longitude - EPSILON, 27), nakshatra - 1);
+        assert_eq!(nakshatra_pada(longitude - EPSILON), 4);
+    }
+
+    // Exact whole degrees on a boundary belong to the Nakshatra they begin
+    assert_eq!((arc_index(40.0, 27), nakshatra_pada(40.0)), (3, 1));
+    assert_eq!(nakshatra_pada(0.0), 1);
+    assert_eq!(nakshatra_pada(360.0 - EPSILON), 4);
+}
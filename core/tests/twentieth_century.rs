@@ -0,0 +1,92 @@
+//! Checks the day count and the Sun's and Moon's longitudes at 20th-century instants
+//! against the worked examples of Meeus' Astronomical Algorithms (2nd ed.), computed
+//! with the full VSOP87 and ELP-2000/82 theories.
+
+use panchang_core::{days_since_j2000, delta_t, moon_long, rev, sun_long};
+
+/// Julian Day of the crate's day zero, 1999-12-31 0h
+const DAY_ZERO: f64 = 2451543.5;
+
+/// Day number of a Julian Ephemeris Day, in dynamical time as the examples give it
+fn day_number(jde: f64) -> f64 {
+    jde - DAY_ZERO
+}
+
+/// Day number in universal time of a Julian Ephemeris Day, as `moon_long` takes it
+fn universal_day_number(jde: f64) -> f64 {
+    let d = day_number(jde);
+    d - delta_t(d) / 86400.0
+}
+
+/// Difference between two longitudes in degrees, across the 0°/360° seam
+fn separation(a: f64, b: f64) -> f64 {
+    let difference = rev(a - b);
+    difference.min(360.0 - difference)
+}
+
+#[test]
+fn day_count_matches_julian_days() {
+    // Julian Days at 0h from Meeus' chapter 7 and its table of years
+    let dates = [
+        ((31, 12, 1899), 2415019.5),
+        ((1, 1, 1950), 2433282.5),
+        ((4, 10, 1957), 2436115.5),
+        ((27, 1, 1987), 2446822.5),
+        ((27, 1, 1988), 2447187.5),
+        ((1, 1, 1999), 2451179.5),
+        ((1, 3, 1900), 2415079.5),
+        ((28, 2, 1900), 2415078.5),
+    ];
+    for ((day, month, year), jd) in dates {
+        assert_eq!(
+            days_since_j2000(day, month, year) as f64,
+            jd - DAY_ZERO,
+            "{}/{}/{}",
+            day,
+            month,
+            year
+        );
+    }
+}
+
+#[test]
+fn sun_matches_example_25a() {
+    // 1992 October 13.0 TD: true geometric longitude 199°.90988
+    let longitude = sun_long(day_number(2448908.5)).longitude;
+    assert!(separation(longitude, 199.90988) < 0.003, "{}", longitude);
+}
+
+#[test]
+fn moon_matches_example_47a() {
+    // 1992 April 12.0 TD: geocentric longitude 133°.162655 before nutation
+    let longitude = moon_long(universal_day_number(2448724.5));
+    assert!(separation(longitude, 133.162655) < 0.005, "{}", longitude);
+}
+
+#[test]
+fn sun_at_the_1962_june_solstice() {
+    // Example 27.a: the Sun reached apparent longitude 90° at JDE 2437837.39245, when
+    // the geometric longitude was 20.5″ of aberration ahead, less the nutation of
+    // under 0.005°
+    let longitude = sun_long(day_number(2437837.39245)).longitude;
+    assert!(
+        separation(longitude, 90.0 + 20.5 / 3600.0) < 0.01,
+        "{}",
+        longitude
+    );
+}
+
+#[test]
+fn new_moon_of_1977_february() {
+    // Example 49.a: new moon at JDE 2443192.65118, 1977 February 18 03:37:42 TD
+    let jde = 2443192.65118;
+    let sun = sun_long(day_number(jde)).longitude;
+    let moon = moon_long(universal_day_number(jde));
+    // Within 0.02°, the Moon's motion relative to the Sun in about two minutes
+    assert!(
+        separation(moon, sun) < 0.02,
+        "Sun {} and Moon {}",
+        sun,
+        moon
+    );
+}
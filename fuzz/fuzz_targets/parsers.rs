@@ -0,0 +1,28 @@
+//! Feeds arbitrary strings to the date, time and timezone parsers, and the
+//! calculation to whatever they accept
+//!
+//! ```text
+//! cargo +nightly fuzz run parsers
+//! ```
+
+#![no_main]
+
+use chrono::NaiveDate;
+use libfuzzer_sys::fuzz_target;
+use panchang_rs::{Ayanamsa, calculate_panchanga, parse_clock_time, parse_date, parse_tz_offset};
+
+fuzz_target!(|input: (&str, &str, &str)| {
+    let (date, time, zone) = input;
+    let date = parse_date(date);
+    let time = parse_clock_time(time);
+    let zone = parse_tz_offset(zone);
+
+    // The handlers turn accepted dates into calendar dates without checking again
+    if let Ok((day, month, year)) = date {
+        assert!(NaiveDate::from_ymd_opt(year, month as u32, day as u32).is_some());
+    }
+
+    if let (Ok((day, month, year)), Ok((hour, minutes)), Ok(zone)) = (date, time, zone) {
+        calculate_panchanga(day, month, year, hour + minutes as f64 / 60.0, zone, Ayanamsa::Lahiri);
+    }
+});
@@ -0,0 +1,6 @@
+-- Calculated Panchang responses keyed by a hash of the request inputs
+CREATE TABLE IF NOT EXISTS panchang_cache (
+    cache_key TEXT PRIMARY KEY,
+    response JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,150 @@
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use sqlx::types::Json;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+
+use crate::panchang::{PanchangResponse, ValidatedRequest, calculate_panchang_validated};
+
+/// Version of the cached responses, to bump whenever a change to the calculations or
+/// to `PanchangResponse` makes the responses already in `panchang_cache` stale
+const CACHE_SCHEMA_VERSION: u32 = 1;
+
+/// Derives the key a request's response is stored under in `panchang_cache` and in
+/// the [`MemoryCache`]
+///
+/// The key is the SHA-256 of the request's [normalized](ValidatedRequest::normalized)
+/// inputs, so it covers every input that can influence the response while requests
+/// spelling the same inputs differently share it. They are prefixed with the crate
+/// version and [`CACHE_SCHEMA_VERSION`] so that responses cached by an older engine
+/// are never served.
+pub fn cache_key(req: &ValidatedRequest) -> String {
+    let inputs = serde_json::to_vec(&req.normalized()).expect("normalized inputs always serialize");
+    let digest = Sha256::new()
+        .chain_update(format!("{}/{}:", env!("CARGO_PKG_VERSION"), CACHE_SCHEMA_VERSION))
+        .chain_update(inputs)
+        .finalize();
+    format!("{:x}", digest)
+}
+
+/// Returns the Panchang for a request, calculating it only on a cache miss
+///
+/// The Panchang is deterministic for a given set of inputs, so results are stored
+/// in the `panchang_cache` table and served from there on later requests. The cache
+/// is best-effort: database failures are logged and the result is calculated as if
+/// there were no cache.
+pub async fn get_or_compute(pool: &PgPool, req: &ValidatedRequest) -> PanchangResponse {
+    let key = cache_key(req);
+
+    let cached = sqlx::query_as::<_, (Json<PanchangResponse>,)>(
+        "SELECT response FROM panchang_cache WHERE cache_key = $1",
+    )
+    .bind(&key)
+    .fetch_optional(pool)
+    .await;
+
+    match cached {
+        Ok(Some((Json(response),))) => return response,
+        Ok(None) => {}
+        Err(err) => tracing::warn!(error = %err, "Failed to read the Panchang cache"),
+    }
+
+    let response = calculate_panchang_validated(req);
+
+    let stored = sqlx::query(
+        "INSERT INTO panchang_cache (cache_key, response) VALUES ($1, $2) ON CONFLICT (cache_key) DO NOTHING",
+    )
+    .bind(&key)
+    .bind(Json(&response))
+    .execute(pool)
+    .await;
+
+    if let Err(err) = stored {
+        tracing::warn!(error = %err, "Failed to write the Panchang cache");
+    }
+
+    response
+}
+
+/// Responses of recent requests held in process, in front of the database cache
+///
+/// The cache keeps the `capacity` most recently used responses, evicting the least
+/// recently used one to make room for another, and holds none when `capacity` is 0.
+/// It serves repeated requests without a round trip to Postgres, or without a
+/// database at all.
+pub struct MemoryCache {
+    capacity: usize,
+    entries: Mutex<LruEntries>,
+}
+
+/// Cached responses with the order they were last used in
+#[derive(Default)]
+struct LruEntries {
+    /// Response and last use of each key
+    responses: HashMap<String, (PanchangResponse, u64)>,
+    /// Key of each last use, the least recent first
+    uses: BTreeMap<u64, String>,
+    /// Counter numbering the uses
+    clock: u64,
+}
+
+impl LruEntries {
+    /// Records a use of `key`, last used at `previous`, and returns the new use
+    fn touch(&mut self, key: &str, previous: Option<u64>) -> u64 {
+        if let Some(previous) = previous {
+            self.uses.remove(&previous);
+        }
+        self.clock += 1;
+        self.uses.insert(self.clock, key.to_string());
+        self.clock
+    }
+}
+
+impl MemoryCache {
+    /// Creates an empty cache of up to `capacity` responses
+    pub fn new(capacity: usize) -> Self {
+        MemoryCache { capacity, entries: Mutex::new(LruEntries::default()) }
+    }
+
+    /// Number of responses in the cache
+    pub fn len(&self) -> usize {
+        self.lock().responses.len()
+    }
+
+    /// Whether the cache holds no response
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the response cached under `key`, marking it the most recently used
+    pub fn get(&self, key: &str) -> Option<PanchangResponse> {
+        let mut entries = self.lock();
+        let previous = entries.responses.get(key)?.1;
+        let used = entries.touch(key, Some(previous));
+        let (response, last_used) = entries.responses.get_mut(key)?;
+        *last_used = used;
+        Some(response.clone())
+    }
+
+    /// Caches `response` under `key`, evicting the least recently used response
+    /// when the cache is full
+    pub fn insert(&self, key: String, response: PanchangResponse) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut entries = self.lock();
+        let previous = entries.responses.get(&key).map(|(_, used)| *used);
+        if previous.is_none() && entries.responses.len() >= self.capacity
+            && let Some((_, evicted)) = entries.uses.pop_first()
+        {
+            entries.responses.remove(&evicted);
+        }
+        let used = entries.touch(&key, previous);
+        entries.responses.insert(key, (response, used));
+    }
+
+    /// Locks the entries, taking them over from a thread that panicked holding them
+    fn lock(&self) -> std::sync::MutexGuard<'_, LruEntries> {
+        self.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
@@ -0,0 +1,411 @@
+use chrono_tz::Tz;
+use serde::Deserialize;
+use serde::de::IntoDeserializer;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::panchang::{ACCURATE_YEARS, Ayanamsa, Location};
+
+/// Errors raised while reading the configuration from the environment
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    /// A variable holds a value that cannot be used
+    #[error("{name} must be {expected}, got '{value}'")]
+    Invalid {
+        name: &'static str,
+        expected: &'static str,
+        value: String,
+    },
+    /// `DB_HOST` is set but another required variable is missing
+    #[error("{0} must be set when DB_HOST is set")]
+    MissingDbVar(&'static str),
+    /// The `PANCHANG_CONFIG` file cannot be read or is not valid TOML for the settings
+    #[error("Cannot load the configuration file {path}: {reason}")]
+    File { path: String, reason: String },
+}
+
+/// Server settings read once at startup
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub host: String,
+    pub port: u16,
+    /// Database settings, absent when `DB_HOST` is not set
+    pub db: Option<DbConfig>,
+    /// Whether the service should report itself unavailable when the database is down
+    pub db_required: bool,
+    /// Seconds in-flight requests may take to finish once a shutdown is signalled
+    pub shutdown_timeout: u64,
+    /// Number of worker threads serving requests
+    pub workers: usize,
+    /// Seconds an idle connection is kept open, with keep-alive off when 0
+    pub keep_alive: u64,
+    /// Origins allowed by CORS, which stays disabled when empty
+    pub cors_origins: Vec<String>,
+    /// Location filling in the parts a `/panchang` or `/panchang/now` request leaves out
+    pub default_location: Location,
+    /// Ayanamsa used when a request does not name one
+    pub default_ayanamsa: Ayanamsa,
+    /// Years the Panchang endpoints calculate, rejecting dates outside them
+    pub supported_years: RangeInclusive<i32>,
+    /// Requests per minute allowed to each client, unlimited when absent
+    pub rate_limit_per_min: Option<u32>,
+    /// Largest JSON request body accepted, in bytes
+    pub max_body_bytes: usize,
+    /// Longest date range, in days, served by `/panchang/range`
+    pub max_range_days: u32,
+    /// Seconds an `Idempotency-Key` is remembered for
+    pub idempotency_ttl: u64,
+    /// Milliseconds a request's calculation may take before it is answered with a 504
+    pub calculation_timeout_ms: u64,
+    /// Responses held in the in-memory cache, which is off when 0
+    pub mem_cache_size: usize,
+    /// Cache warming settings, absent when `WARM_LOCATIONS` is not set
+    pub warm: Option<WarmConfig>,
+}
+
+/// Connection settings for PostgreSQL
+#[derive(Debug, Clone)]
+pub struct DbConfig {
+    pub host: String,
+    pub port: u16,
+    pub user: String,
+    pub password: String,
+    pub name: String,
+    pub max_connections: u32,
+    /// Connection attempts made at startup before giving up
+    pub connect_attempts: u32,
+    /// Milliseconds waited after the first failed attempt, doubling after each one
+    pub connect_base_delay_ms: u64,
+}
+
+/// Settings of the job that precomputes the Panchang of popular locations
+#[derive(Debug, Clone)]
+pub struct WarmConfig {
+    /// Locations whose Panchang is kept in the cache
+    pub locations: Vec<Location>,
+    /// Seconds between two passes of the job
+    pub interval: u64,
+    /// Number of days warmed on each pass, starting with the current local date
+    pub days: u32,
+}
+
+/// Settings of a configuration file, each standing for the environment variable
+/// named in its comment
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileConfig {
+    /// `DEFAULT_AYANAMSA`
+    ayanamsa: Option<String>,
+    server: ServerSection,
+    database: DatabaseSection,
+    default_location: LocationSection,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ServerSection {
+    /// `SERVER_HOST`
+    host: Option<String>,
+    /// `SERVER_PORT`
+    port: Option<u16>,
+    /// `WORKERS`
+    workers: Option<usize>,
+    /// `KEEP_ALIVE`
+    keep_alive: Option<u64>,
+    /// `SHUTDOWN_TIMEOUT`
+    shutdown_timeout: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct DatabaseSection {
+    /// `DB_HOST`
+    host: Option<String>,
+    /// `DB_PORT`
+    port: Option<u16>,
+    /// `DB_USER`
+    user: Option<String>,
+    /// `DB_PASSWORD`
+    password: Option<String>,
+    /// `DB_NAME`
+    name: Option<String>,
+    /// `DB_MAX_CONNECTIONS`
+    max_connections: Option<u32>,
+    /// `DB_CONNECT_ATTEMPTS`
+    connect_attempts: Option<u32>,
+    /// `DB_CONNECT_BASE_DELAY_MS`
+    connect_base_delay_ms: Option<u64>,
+    /// `DB_REQUIRED`
+    required: Option<bool>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct LocationSection {
+    /// `DEFAULT_LATITUDE`
+    latitude: Option<f64>,
+    /// `DEFAULT_LONGITUDE`
+    longitude: Option<f64>,
+    /// `DEFAULT_TIMEZONE`
+    timezone: Option<String>,
+}
+
+impl FileConfig {
+    /// Reads and parses a TOML configuration file
+    fn read(path: &Path) -> Result<FileConfig, ConfigError> {
+        let error = |reason: String| ConfigError::File { path: path.display().to_string(), reason };
+        let contents = fs::read_to_string(path).map_err(|err| error(err.to_string()))?;
+        toml::from_str(&contents).map_err(|err| error(err.to_string()))
+    }
+
+    /// The settings given in the file, by the environment variable each stands for
+    fn into_vars(self) -> HashMap<&'static str, String> {
+        fn text<T: ToString>(value: Option<T>) -> Option<String> {
+            value.map(|value| value.to_string())
+        }
+
+        let FileConfig { ayanamsa, server, database, default_location } = self;
+        [
+            ("DEFAULT_AYANAMSA", ayanamsa),
+            ("SERVER_HOST", server.host),
+            ("SERVER_PORT", text(server.port)),
+            ("WORKERS", text(server.workers)),
+            ("KEEP_ALIVE", text(server.keep_alive)),
+            ("SHUTDOWN_TIMEOUT", text(server.shutdown_timeout)),
+            ("DB_HOST", database.host),
+            ("DB_PORT", text(database.port)),
+            ("DB_USER", database.user),
+            ("DB_PASSWORD", database.password),
+            ("DB_NAME", database.name),
+            ("DB_MAX_CONNECTIONS", text(database.max_connections)),
+            ("DB_CONNECT_ATTEMPTS", text(database.connect_attempts)),
+            ("DB_CONNECT_BASE_DELAY_MS", text(database.connect_base_delay_ms)),
+            ("DB_REQUIRED", text(database.required)),
+            ("DEFAULT_LATITUDE", text(default_location.latitude)),
+            ("DEFAULT_LONGITUDE", text(default_location.longitude)),
+            ("DEFAULT_TIMEZONE", default_location.timezone),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| value.map(|value| (name, value)))
+        .collect()
+    }
+}
+
+impl Config {
+    /// Reads the configuration from the file named by `PANCHANG_CONFIG`, if set, and
+    /// the process environment, which overrides it
+    pub fn from_env() -> Result<Config, ConfigError> {
+        let file = env::var("PANCHANG_CONFIG").ok();
+        Config::load(file.as_deref().map(Path::new), |name| env::var(name).ok())
+    }
+
+    /// Reads the configuration from an optional TOML file and through `lookup`, which
+    /// returns a variable's value if set
+    ///
+    /// A variable found through `lookup`, under its name or its short name, takes
+    /// precedence over the file's setting for it, and either over the default.
+    pub fn load(file: Option<&Path>, lookup: impl Fn(&str) -> Option<String>) -> Result<Config, ConfigError> {
+        let file_vars = match file {
+            Some(path) => FileConfig::read(path)?.into_vars(),
+            None => HashMap::new(),
+        };
+        Config::from_lookup(|name| {
+            lookup(name)
+                .or_else(|| short_name(name).and_then(&lookup))
+                .or_else(|| file_vars.get(name).cloned())
+        })
+    }
+
+    /// Reads the configuration through `lookup`, which returns a variable's value if set
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Config, ConfigError> {
+        let host = lookup("SERVER_HOST").unwrap_or_else(|| String::from("127.0.0.1"));
+        let port = parse_var(&lookup, "SERVER_PORT", "a number 1–65535", 8080, |port| *port > 0)?;
+
+        let db = match lookup("DB_HOST") {
+            None => None,
+            Some(db_host) => {
+                let required = |name: &'static str| lookup(name).ok_or(ConfigError::MissingDbVar(name));
+                let db_port = required("DB_PORT")?;
+                Some(DbConfig {
+                    host: db_host,
+                    port: parse_value("DB_PORT", "a number 1–65535", &db_port, |port| *port > 0)?,
+                    user: required("DB_USER")?,
+                    password: required("DB_PASSWORD")?,
+                    name: required("DB_NAME")?,
+                    max_connections: parse_var(&lookup, "DB_MAX_CONNECTIONS", "a positive number", 5, |max| *max > 0)?,
+                    // Five attempts over 7.5 seconds cover a Postgres container starting alongside
+                    connect_attempts: parse_var(&lookup, "DB_CONNECT_ATTEMPTS", "a positive number", 5, |attempts| *attempts > 0)?,
+                    connect_base_delay_ms: parse_var(&lookup, "DB_CONNECT_BASE_DELAY_MS", "a positive number of milliseconds", 500, |ms| *ms > 0)?,
+                })
+            }
+        };
+
+        let db_required = parse_var(&lookup, "DB_REQUIRED", "true or false", false, |_| true)?;
+        let shutdown_timeout = parse_var(&lookup, "SHUTDOWN_TIMEOUT", "a number of seconds", 30, |_| true)?;
+
+        // One worker per CPU, since the calculations are CPU-bound
+        let cpus = std::thread::available_parallelism().map_or(1, |cpus| cpus.get());
+        let workers = parse_var(&lookup, "WORKERS", "a positive number", cpus, |workers| *workers > 0)?;
+        let keep_alive = parse_var(&lookup, "KEEP_ALIVE", "a number of seconds", 5, |_| true)?;
+
+        let cors_origins = lookup("CORS_ALLOWED_ORIGINS")
+            .unwrap_or_default()
+            .split(',')
+            .map(|origin| origin.trim().to_string())
+            .filter(|origin| !origin.is_empty())
+            .collect();
+
+        // New Delhi unless configured otherwise
+        let default_location = Location {
+            latitude: parse_var(&lookup, "DEFAULT_LATITUDE", "a latitude between -90 and 90", 28.6139, |lat| {
+                (-90.0..=90.0).contains(lat)
+            })?,
+            longitude: parse_var(&lookup, "DEFAULT_LONGITUDE", "a longitude between -180 and 180", 77.2090, |lon| {
+                (-180.0..=180.0).contains(lon)
+            })?,
+            timezone: Some(
+                parse_var(&lookup, "DEFAULT_TIMEZONE", "an IANA timezone name", Tz::Asia__Kolkata, |_| true)?
+                    .name()
+                    .to_string(),
+            ),
+        };
+
+        let default_ayanamsa = match lookup("DEFAULT_AYANAMSA") {
+            None => Ayanamsa::default(),
+            Some(value) => Ayanamsa::deserialize(value.trim().into_deserializer()).map_err(|_: serde::de::value::Error| {
+                ConfigError::Invalid { name: "DEFAULT_AYANAMSA", expected: "lahiri, raman, kp or fagan_bradley", value: value.clone() }
+            })?,
+        };
+
+        let supported_years = match lookup("SUPPORTED_YEARS") {
+            None => ACCURATE_YEARS,
+            Some(years) => parse_years(&years)?,
+        };
+
+        // 0 turns the rate limiter off
+        let rate_limit_per_min = Some(parse_var(&lookup, "RATE_LIMIT_PER_MIN", "a number of requests", 120, |_| true)?)
+            .filter(|limit| *limit > 0);
+
+        // The request bodies are a few hundred bytes, so 16 KiB leaves ample room
+        let max_body_bytes = parse_var(&lookup, "MAX_BODY_BYTES", "a positive number of bytes", 16 * 1024, |bytes| *bytes > 0)?;
+        let max_range_days = parse_var(&lookup, "MAX_RANGE_DAYS", "a positive number of days", 366, |days| *days > 0)?;
+        let idempotency_ttl = parse_var(&lookup, "IDEMPOTENCY_TTL", "a positive number of seconds", 86_400, |secs| *secs > 0)?;
+        // A year's range takes well under a second, so 10 seconds only stops runaway calculations
+        let calculation_timeout_ms =
+            parse_var(&lookup, "CALCULATION_TIMEOUT_MS", "a positive number of milliseconds", 10_000, |ms| *ms > 0)?;
+
+        let mem_cache_size = parse_var(&lookup, "MEM_CACHE_SIZE", "a number of responses", 1024, |_| true)?;
+
+        let warm = match lookup("WARM_LOCATIONS") {
+            None => None,
+            Some(locations) => Some(WarmConfig {
+                locations: parse_locations(&locations)?,
+                interval: parse_var(&lookup, "WARM_INTERVAL", "a positive number of seconds", 3600, |secs| *secs > 0)?,
+                days: parse_var(&lookup, "WARM_DAYS", "a positive number of days", 3, |days| *days > 0)?,
+            }),
+        };
+
+        Ok(Config {
+            host,
+            port,
+            db,
+            db_required,
+            shutdown_timeout,
+            workers,
+            keep_alive,
+            cors_origins,
+            default_location,
+            default_ayanamsa,
+            supported_years,
+            rate_limit_per_min,
+            max_body_bytes,
+            max_range_days,
+            idempotency_ttl,
+            calculation_timeout_ms,
+            mem_cache_size,
+            warm,
+        })
+    }
+}
+
+/// Parses the `START-END` years of `SUPPORTED_YEARS`, both within 1-9999
+fn parse_years(value: &str) -> Result<RangeInclusive<i32>, ConfigError> {
+    let invalid = || ConfigError::Invalid {
+        name: "SUPPORTED_YEARS",
+        expected: "a range of years START-END within 1-9999",
+        value: value.to_string(),
+    };
+
+    let (start, end) = value.split_once('-').ok_or_else(invalid)?;
+    let year = |year: &str| year.trim().parse::<i32>().ok().filter(|year| (1..=9999).contains(year));
+    match (year(start), year(end)) {
+        (Some(start), Some(end)) if start <= end => Ok(start..=end),
+        _ => Err(invalid()),
+    }
+}
+
+/// Parses the `;`-separated `latitude,longitude,timezone` entries of `WARM_LOCATIONS`
+fn parse_locations(value: &str) -> Result<Vec<Location>, ConfigError> {
+    let invalid = || ConfigError::Invalid {
+        name: "WARM_LOCATIONS",
+        expected: "a list of latitude,longitude,timezone entries separated by ';'",
+        value: value.to_string(),
+    };
+
+    value
+        .split(';')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let [latitude, longitude, timezone] = entry.split(',').map(str::trim).collect::<Vec<_>>()[..] else {
+                return Err(invalid());
+            };
+            let latitude = latitude.parse().ok().filter(|lat| (-90.0..=90.0).contains(lat)).ok_or_else(invalid)?;
+            let longitude = longitude.parse().ok().filter(|lon| (-180.0..=180.0).contains(lon)).ok_or_else(invalid)?;
+            let timezone = timezone.parse::<Tz>().map_err(|_| invalid())?.name().to_string();
+            Ok(Location { latitude, longitude, timezone: Some(timezone) })
+        })
+        .collect()
+}
+
+/// Shorter name a variable is also read under, as `DEFAULT_LAT` for `DEFAULT_LATITUDE`
+fn short_name(name: &str) -> Option<&'static str> {
+    match name {
+        "DEFAULT_LATITUDE" => Some("DEFAULT_LAT"),
+        "DEFAULT_LONGITUDE" => Some("DEFAULT_LON"),
+        _ => None,
+    }
+}
+
+/// Parses an optional variable, falling back to `default` when it is unset
+fn parse_var<T: FromStr>(
+    lookup: &impl Fn(&str) -> Option<String>,
+    name: &'static str,
+    expected: &'static str,
+    default: T,
+    valid: impl Fn(&T) -> bool,
+) -> Result<T, ConfigError> {
+    match lookup(name) {
+        None => Ok(default),
+        Some(value) => parse_value(name, expected, &value, valid),
+    }
+}
+
+/// Parses a variable's value, rejecting values that do not parse or fail `valid`
+fn parse_value<T: FromStr>(
+    name: &'static str,
+    expected: &'static str,
+    value: &str,
+    valid: impl Fn(&T) -> bool,
+) -> Result<T, ConfigError> {
+    value
+        .trim()
+        .parse::<T>()
+        .ok()
+        .filter(|parsed| valid(parsed))
+        .ok_or_else(|| ConfigError::Invalid { name, expected, value: value.to_string() })
+}
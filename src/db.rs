@@ -1,36 +1,78 @@
-use sqlx::{PgPool, postgres::{PgPoolOptions, PgConnectOptions}};
-use std::env;
+use sqlx::{Connection, PgConnection, PgPool, migrate::MigrateError, postgres::{PgPoolOptions, PgConnectOptions}};
+use std::fmt::Display;
+use std::time::Duration;
 
-pub async fn init_db() -> PgPool {
-    let host = env::var("DB_HOST").expect("DB_HOST must be set");
-    let port = env::var("DB_PORT")
-        .expect("DB_PORT must be set")
-        .parse::<u16>()
-        .expect("DB_PORT must be a valid number");
-    let user = env::var("DB_USER").expect("DB_USER must be set");
-    let password = env::var("DB_PASSWORD").expect("DB_PASSWORD must be set");
-    let dbname = env::var("DB_NAME").expect("DB_NAME must be set");
+use crate::config::DbConfig;
 
-    println!("Attempting to connect to PostgreSQL database at {}:{}", host, port);
+/// Errors raised while setting up the database
+#[derive(Debug, thiserror::Error)]
+pub enum DbError {
+    /// The database could not be reached
+    #[error("Database connection error: {0}")]
+    Connect(#[from] sqlx::Error),
+    /// The schema migrations could not be applied
+    #[error("Database migration error: {0}")]
+    Migrate(#[from] MigrateError),
+}
 
-    match PgPoolOptions::new()
-        .max_connections(5)
-        .connect_with(
-            PgConnectOptions::new()
-                .host(&host)
-                .port(port)
-                .username(&user)
-                .password(&password)
-                .database(&dbname)
-        )
-        .await {
-            Ok(pool) => {
-                println!("✅ Successfully connected to the database");
-                pool
-            }
-            Err(err) => {
-                eprintln!("❌ Failed to connect to the database: {}", err);
-                panic!("Database connection error: {}", err);
-            }
+/// Longest wait between two connection attempts, however many have failed
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// Connects to PostgreSQL and applies the migrations
+///
+/// The database is optional: without database settings no connection is attempted
+/// and `Ok(None)` is returned so the server can run the calculations on their own.
+/// A database that is not up yet, as when it starts alongside the server, is tried
+/// `connect_attempts` times before the last error is returned.
+pub async fn init_db(config: Option<&DbConfig>) -> Result<Option<PgPool>, DbError> {
+    let Some(config) = config else {
+        tracing::warn!("DB_HOST is not set, starting without a database");
+        return Ok(None);
+    };
+
+    let options = PgConnectOptions::new()
+        .host(&config.host)
+        .port(config.port)
+        .username(&config.user)
+        .password(&config.password)
+        .database(&config.name);
+    // A single connection fails at once while the database is down, where the pool
+    // would keep trying for its whole acquire timeout
+    let base_delay = Duration::from_millis(config.connect_base_delay_ms);
+    retry_with_backoff(config.connect_attempts, base_delay, |attempt| {
+        tracing::info!(attempt, attempts = config.connect_attempts, "Attempting to connect to PostgreSQL database at {}:{}", config.host, config.port);
+        async { PgConnection::connect_with(&options).await?.close().await }
+    })
+    .await
+    .inspect_err(|err| tracing::error!(error = %err, "Failed to connect to the database"))?;
+    let pool = PgPoolOptions::new().max_connections(config.max_connections).connect_with(options).await?;
+
+    tracing::info!("Successfully connected to the database");
+    sqlx::migrate!().run(&pool).await?;
+    Ok(Some(pool))
+}
+
+/// Runs `attempt` until it succeeds, at most `attempts` times, with the wait between
+/// two attempts doubling from `base_delay` up to 30 seconds
+///
+/// `attempt` is given the number of the attempt, from 1. Each failure but the last is
+/// logged with the wait before the next attempt; the last one is returned.
+pub async fn retry_with_backoff<T, E, F, Fut>(attempts: u32, base_delay: Duration, mut attempt: F) -> Result<T, E>
+where
+    E: Display,
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let (mut number, mut delay) = (1, base_delay);
+    loop {
+        match attempt(number).await {
+            Ok(value) => return Ok(value),
+            Err(err) if number < attempts => {
+                tracing::warn!(error = %err, attempt = number, attempts, retry_in_ms = delay.as_millis() as u64, "Connection attempt failed, retrying");
+                tokio::time::sleep(delay).await;
+                (number, delay) = (number + 1, (delay * 2).min(MAX_RETRY_DELAY));
+            },
+            Err(err) => return Err(err),
         }
-}
\ No newline at end of file
+    }
+}
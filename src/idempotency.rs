@@ -0,0 +1,175 @@
+//! Replay of POST responses for requests retried with an `Idempotency-Key` header
+//!
+//! The first successful response to a key is stored in the `idempotency_keys` table
+//! along with a hash of the request, and a later request with the same key gets the
+//! stored response back instead of a new calculation. Reusing a key for a different
+//! request is refused with a 409. Keys expire `IDEMPOTENCY_TTL` seconds after their
+//! first use.
+
+use actix_web::body::{self, BoxBody, MessageBody};
+use actix_web::dev::{Payload, ServiceRequest, ServiceResponse};
+use actix_web::http::header::{ContentType, HeaderName, HeaderValue};
+use actix_web::http::{Method, StatusCode};
+use actix_web::middleware::Next;
+use actix_web::{Error, HttpMessage, HttpResponse, web};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+
+use crate::middleware::RequestId;
+use crate::server::AppState;
+
+/// Header carrying the client's idempotency key
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Header set on a response replayed from an earlier request
+pub const REPLAYED_HEADER: &str = "idempotent-replayed";
+
+/// Longest idempotency key accepted
+const MAX_KEY_LEN: usize = 255;
+
+/// Response stored for a key
+struct Stored {
+    request_hash: String,
+    status: i16,
+    response: Vec<u8>,
+}
+
+/// Hashes what identifies a request: its method, path, query string and body
+///
+/// A JSON body is hashed in a canonical form, so the same fields in another order
+/// or with other whitespace make the same request.
+fn request_hash(req: &ServiceRequest, body: &[u8]) -> String {
+    let body = match serde_json::from_slice::<serde_json::Value>(body) {
+        Ok(json) => serde_json::to_vec(&json).expect("JSON values always serialize"),
+        Err(_) => body.to_vec(),
+    };
+
+    let mut hasher = Sha256::new();
+    for part in [req.method().as_str().as_bytes(), req.path().as_bytes(), req.query_string().as_bytes()] {
+        hasher.update(part);
+        hasher.update([0]);
+    }
+    hasher.update(body);
+    format!("{:x}", hasher.finalize())
+}
+
+/// Looks up the unexpired response stored for a key
+async fn find(pool: &PgPool, key: &str, ttl: u64) -> Result<Option<Stored>, sqlx::Error> {
+    let row = sqlx::query_as::<_, (String, i16, Vec<u8>)>(
+        "SELECT request_hash, status, response FROM idempotency_keys \
+         WHERE idempotency_key = $1 AND created_at > NOW() - make_interval(secs => $2)",
+    )
+    .bind(key)
+    .bind(ttl as f64)
+    .fetch_optional(pool)
+    .await?;
+    Ok(row.map(|(request_hash, status, response)| Stored { request_hash, status, response }))
+}
+
+/// Stores the response to a key, replacing an expired one and dropping the others
+/// that have expired
+async fn store(pool: &PgPool, key: &str, ttl: u64, stored: &Stored) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM idempotency_keys WHERE created_at <= NOW() - make_interval(secs => $1)")
+        .bind(ttl as f64)
+        .execute(pool)
+        .await?;
+    sqlx::query(
+        "INSERT INTO idempotency_keys (idempotency_key, request_hash, status, response) VALUES ($1, $2, $3, $4) \
+         ON CONFLICT (idempotency_key) DO NOTHING",
+    )
+    .bind(key)
+    .bind(&stored.request_hash)
+    .bind(stored.status)
+    .bind(&stored.response)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Answers with an error in the API envelope
+fn reject(req: ServiceRequest, status: StatusCode, code: &str, message: String) -> ServiceResponse {
+    let request_id = req.extensions().get::<RequestId>().cloned().unwrap_or_default();
+    let response = crate::routes::error_response(request_id, status, code, message);
+    req.into_response(response)
+}
+
+/// Replays the stored response of a POST request retried with the same
+/// `Idempotency-Key`, and stores the first successful response to a key
+///
+/// Requests without the header, other methods and servers without a database pass
+/// through untouched. The store is best-effort like the Panchang cache: database
+/// failures are logged and the request is served as if it had no key. Must be
+/// installed inside `request_id`.
+pub async fn idempotency(
+    mut req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, Error> {
+    let key = req.headers().get(IDEMPOTENCY_KEY_HEADER).map(|value| value.to_str().map(str::trim));
+    let state = req.app_data::<web::Data<AppState>>().cloned();
+    let (Some(key), Some(state), true) = (key, state, req.method() == Method::POST) else {
+        return Ok(next.call(req).await?.map_into_boxed_body());
+    };
+    let Some(pool) = state.pool.clone() else {
+        return Ok(next.call(req).await?.map_into_boxed_body());
+    };
+
+    let key = match key {
+        Ok(key) if !key.is_empty() && key.len() <= MAX_KEY_LEN => key.to_string(),
+        _ => {
+            let message = format!("Idempotency-Key must be 1 to {} visible ASCII characters", MAX_KEY_LEN);
+            return Ok(reject(req, StatusCode::BAD_REQUEST, "INVALID_IDEMPOTENCY_KEY", message));
+        }
+    };
+
+    // Read the body to hash it, then hand it back for the handler to parse
+    let body = match req.extract::<web::Bytes>().await {
+        Ok(body) => body,
+        Err(err) => {
+            let status = err.as_response_error().status_code();
+            let code = if status == StatusCode::PAYLOAD_TOO_LARGE { "PAYLOAD_TOO_LARGE" } else { "INVALID_BODY" };
+            return Ok(reject(req, status, code, format!("Invalid request body: {}", err)));
+        }
+    };
+    let request_hash = request_hash(&req, &body);
+    req.set_payload(Payload::from(body));
+
+    let ttl = state.idempotency_ttl;
+    match find(&pool, &key, ttl).await {
+        Ok(Some(stored)) if stored.request_hash != request_hash => {
+            let message = "Idempotency-Key was already used for a different request".to_string();
+            return Ok(reject(req, StatusCode::CONFLICT, "IDEMPOTENCY_KEY_REUSED", message));
+        }
+        Ok(Some(stored)) => {
+            let status = u16::try_from(stored.status).ok().and_then(|status| StatusCode::from_u16(status).ok());
+            let response = HttpResponse::build(status.unwrap_or(StatusCode::OK))
+                .content_type(ContentType::json())
+                .insert_header((HeaderName::from_static(REPLAYED_HEADER), HeaderValue::from_static("true")))
+                .body(stored.response);
+            return Ok(req.into_response(response).map_into_boxed_body());
+        }
+        Ok(None) => {}
+        Err(err) => {
+            tracing::warn!(error = %err, "Failed to read the idempotency keys");
+            return Ok(next.call(req).await?.map_into_boxed_body());
+        }
+    }
+
+    let res = next.call(req).await?;
+    if !res.status().is_success() {
+        return Ok(res.map_into_boxed_body());
+    }
+
+    // Buffer the response to store it, then send the same bytes
+    let (req, res) = res.into_parts();
+    let (head, response_body) = res.into_parts();
+    let bytes = body::to_bytes(response_body)
+        .await
+        .map_err(|_| actix_web::error::ErrorInternalServerError("Failed to read the response"))?;
+    let stored = Stored { request_hash, status: head.status().as_u16() as i16, response: bytes.to_vec() };
+    if let Err(err) = store(&pool, &key, ttl, &stored).await {
+        tracing::warn!(error = %err, "Failed to store the idempotency key");
+    }
+
+    let res = head.set_body(bytes);
+    Ok(ServiceResponse::new(req, res).map_into_boxed_body())
+}
@@ -0,0 +1,51 @@
+//! Panchang (Hindu calendar) calculations, usable without running the HTTP server.
+//!
+//! The [`panchang`] module holds the astronomy: Tithi, Nakshatra, Yoga, Karana,
+//! Rashi, Masa and the other elements, along with the date and time parsers used by
+//! the API. The `panchang-rs` binary serves the same calculations over HTTP.
+//!
+//! # Example
+//!
+//! ```
+//! use panchang_rs::{Ayanamsa, calculate_panchanga, parse_clock_time, parse_date, parse_tz_offset};
+//!
+//! let (day, month, year) = parse_date("15/08/2023").unwrap();
+//! let (hour, _) = parse_clock_time("12:00").unwrap();
+//! let zone = parse_tz_offset("+05:30").unwrap();
+//!
+//! // Noon IST in New Delhi
+//! let panchanga = calculate_panchanga(day, month, year, hour, zone, Ayanamsa::Lahiri);
+//! assert_eq!(panchanga.current_paksha, "Krishna");
+//! println!("{} in {}", panchanga.current_tithi, panchanga.current_masa);
+//! ```
+
+#[cfg(feature = "server")]
+pub mod cache;
+#[cfg(feature = "server")]
+pub mod config;
+#[cfg(feature = "server")]
+pub mod db;
+#[cfg(feature = "server")]
+pub mod idempotency;
+#[cfg(feature = "server")]
+pub mod middleware;
+pub mod panchang;
+#[cfg(feature = "server")]
+pub mod server;
+#[cfg(feature = "server")]
+pub mod warm;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(feature = "server")]
+mod metrics;
+#[cfg(feature = "openapi")]
+mod openapi;
+#[cfg(feature = "server")]
+mod routes;
+#[cfg(feature = "ui")]
+mod ui;
+
+// Re-export the calculation API at the crate root
+pub use self::panchang::{Ayanamsa, MonthSystem, Panchanga, PanchangError};
+pub use self::panchang::{calculate_panchanga, calculate_panchanga_at, parse_clock_time, parse_date, parse_tz_offset};
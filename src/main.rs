@@ -1,14 +1,39 @@
 use std::error::Error;
 
-mod db;
-mod panchang;
-mod routes;
-mod server;
+use panchang_rs::config::Config;
+use panchang_rs::server;
+use tracing_subscriber::EnvFilter;
+
+/// Installs the global log subscriber
+///
+/// The level filter comes from `RUST_LOG` (default `info`), and `LOG_FORMAT=json`
+/// switches from human-readable lines to one JSON object per event.
+fn init_tracing() {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let builder = tracing_subscriber::fmt().with_env_filter(filter);
+
+    if std::env::var("LOG_FORMAT").is_ok_and(|format| format == "json") {
+        builder.json().init();
+    } else {
+        builder.init();
+    }
+}
 
 
 #[actix_web::main]
 async fn main() -> Result<(), Box<dyn Error>> {    
-    server::http_server().await?;
+    // Load .env first so RUST_LOG and LOG_FORMAT can be set there
+    dotenv::dotenv().ok();
+    init_tracing();
+
+    let config = match Config::from_env() {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("Invalid configuration: {}", err);
+            std::process::exit(1);
+        }
+    };
+    server::http_server(config).await?;
 
     Ok(())
 }
@@ -0,0 +1,78 @@
+use prometheus::{Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, Opts, Registry, TextEncoder};
+use sqlx::PgPool;
+use std::time::Duration;
+
+/// Upper bounds in seconds of the latency histogram buckets
+const LATENCY_BUCKETS: [f64; 10] = [0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0];
+
+/// Prometheus metrics of the Panchang endpoints, the in-memory cache and the database pool
+pub struct Metrics {
+    registry: Registry,
+    requests: IntCounterVec,
+    latency: HistogramVec,
+    memory_cache: IntCounterVec,
+    pool_connections: IntGauge,
+    pool_idle: IntGauge,
+}
+
+impl Metrics {
+    /// Creates the metrics in a registry of their own
+    pub fn new() -> Self {
+        let requests = IntCounterVec::new(
+            Opts::new("panchang_requests_total", "Panchang requests served, by endpoint and status"),
+            &["endpoint", "status"],
+        )
+        .expect("valid counter");
+        let latency = HistogramVec::new(
+            HistogramOpts::new("panchang_request_duration_seconds", "Panchang request latency, by endpoint and status")
+                .buckets(LATENCY_BUCKETS.to_vec()),
+            &["endpoint", "status"],
+        )
+        .expect("valid histogram");
+        let memory_cache = IntCounterVec::new(
+            Opts::new("panchang_memory_cache_lookups_total", "In-memory Panchang cache lookups, by hit or miss"),
+            &["result"],
+        )
+        .expect("valid counter");
+        let pool_connections = IntGauge::new("db_pool_connections", "Open database connections, idle or in use")
+            .expect("valid gauge");
+        let pool_idle = IntGauge::new("db_pool_idle_connections", "Idle database connections").expect("valid gauge");
+
+        let registry = Registry::new();
+        registry.register(Box::new(requests.clone())).expect("unique metric");
+        registry.register(Box::new(latency.clone())).expect("unique metric");
+        registry.register(Box::new(memory_cache.clone())).expect("unique metric");
+        registry.register(Box::new(pool_connections.clone())).expect("unique metric");
+        registry.register(Box::new(pool_idle.clone())).expect("unique metric");
+
+        Metrics { registry, requests, latency, memory_cache, pool_connections, pool_idle }
+    }
+
+    /// Records one request to `endpoint` answered with `status` after `elapsed`
+    pub fn observe(&self, endpoint: &str, status: u16, elapsed: Duration) {
+        let status = status.to_string();
+        self.requests.with_label_values(&[endpoint, &status]).inc();
+        self.latency.with_label_values(&[endpoint, &status]).observe(elapsed.as_secs_f64());
+    }
+
+    /// Records a lookup in the in-memory cache, which found the response if `hit`
+    pub fn observe_memory_cache(&self, hit: bool) {
+        self.memory_cache.with_label_values(&[if hit { "hit" } else { "miss" }]).inc();
+    }
+
+    /// Renders every metric in the Prometheus text exposition format
+    ///
+    /// The pool gauges are sampled now, and stay at zero without a database.
+    pub fn render(&self, pool: Option<&PgPool>) -> String {
+        if let Some(pool) = pool {
+            self.pool_connections.set(pool.size() as i64);
+            self.pool_idle.set(pool.num_idle() as i64);
+        }
+
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .expect("text encoding cannot fail");
+        String::from_utf8(buffer).expect("text encoding is UTF-8")
+    }
+}
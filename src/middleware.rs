@@ -0,0 +1,219 @@
+use actix_web::body::{EitherBody, MessageBody};
+use actix_web::dev::{Payload, ServiceRequest, ServiceResponse};
+use actix_web::http::StatusCode;
+use actix_web::http::header::{self, HeaderName, HeaderValue};
+use actix_web::middleware::Next;
+use actix_web::{Error, FromRequest, HttpMessage, HttpRequest, web};
+use std::collections::HashMap;
+use std::future::{Ready, ready};
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::Instrument;
+use uuid::Uuid;
+
+use crate::server::AppState;
+
+/// Header used to pass the request id between clients and the server
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Longest client-supplied request id that is reused as-is
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// Number of tracked clients above which clients with a full bucket are forgotten
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+/// Paths that are never rate limited, so probes and monitoring keep working
+const RATE_LIMIT_EXEMPT_PATHS: [&str; 4] = ["/health", "/healthz", "/readyz", "/metrics"];
+
+/// Identifier correlating a request with its response
+#[derive(Debug, Clone)]
+pub struct RequestId(pub String);
+
+impl RequestId {
+    /// Mints a fresh UUID v4 request id
+    pub fn new() -> Self {
+        RequestId(Uuid::new_v4().to_string())
+    }
+}
+
+impl Default for RequestId {
+    fn default() -> Self {
+        RequestId::new()
+    }
+}
+
+impl FromRequest for RequestId {
+    type Error = Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    /// Reads the id assigned by the `request_id` middleware, minting one if the
+    /// middleware is not installed
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let id = req.extensions().get::<RequestId>().cloned();
+        ready(Ok(id.unwrap_or_default()))
+    }
+}
+
+/// Assigns every request an id and echoes it back in the `X-Request-Id` header
+///
+/// An id supplied by the client in the same header is reused so requests can be
+/// traced across services; otherwise a new UUID v4 is generated.
+pub async fn request_id(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let id = req
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|value| !value.is_empty() && value.len() <= MAX_REQUEST_ID_LEN)
+        .map(|value| RequestId(value.to_string()))
+        .unwrap_or_default();
+
+    req.extensions_mut().insert(id.clone());
+
+    let mut res = next.call(req).await?;
+    if let Ok(value) = HeaderValue::from_str(&id.0) {
+        res.headers_mut()
+            .insert(HeaderName::from_static(REQUEST_ID_HEADER), value);
+    }
+
+    Ok(res)
+}
+
+/// Logs every request with its method, path, status, latency and request id
+///
+/// The request is handled inside a span carrying the request id, so events logged
+/// while serving it are correlated as well. Must be installed inside `request_id`.
+pub async fn log_request(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let request_id = req
+        .extensions()
+        .get::<RequestId>()
+        .map(|id| id.0.clone())
+        .unwrap_or_default();
+    let method = req.method().to_string();
+    let path = req.path().to_string();
+    let span = tracing::info_span!("request", %request_id, %method, %path);
+
+    let started = Instant::now();
+    let result = next.call(req).instrument(span.clone()).await;
+    let latency_ms = started.elapsed().as_secs_f64() * 1000.0;
+
+    let _entered = span.enter();
+    match &result {
+        Ok(res) => tracing::info!(status = res.status().as_u16(), latency_ms, "request completed"),
+        Err(err) => tracing::error!(error = %err, latency_ms, "request failed"),
+    }
+
+    result
+}
+
+/// Records the count and latency of every Panchang request in the metrics
+///
+/// Requests are labelled with their route pattern rather than their path, so any two
+/// requests to the same endpoint share a series, and paths no route matched are skipped.
+pub async fn track_metrics(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let state = req.app_data::<web::Data<AppState>>().cloned();
+
+    let started = Instant::now();
+    let res = next.call(req).await?;
+
+    if let Some(state) = state
+        && let Some(endpoint) = res.request().match_pattern()
+        && endpoint.starts_with("/panchang")
+    {
+        state.metrics.observe(&endpoint, res.status().as_u16(), started.elapsed());
+    }
+
+    Ok(res)
+}
+
+/// Token bucket of one client
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Per-client token-bucket rate limiter
+///
+/// Each client IP may burst up to the per-minute limit, and its bucket refills
+/// continuously at that rate.
+pub struct RateLimiter {
+    per_minute: u32,
+    buckets: Mutex<HashMap<IpAddr, Bucket>>,
+}
+
+impl RateLimiter {
+    /// Creates a limiter allowing `per_minute` requests per minute to each client
+    pub fn new(per_minute: u32) -> Self {
+        RateLimiter { per_minute, buckets: Mutex::new(HashMap::new()) }
+    }
+
+    /// Takes a token from a client's bucket
+    ///
+    /// Returns how long the client has to wait for a token when its bucket is empty.
+    fn acquire(&self, client: IpAddr) -> Result<(), Duration> {
+        let capacity = self.per_minute as f64;
+        let per_second = capacity / 60.0;
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        // Forget clients whose bucket has refilled, they would start from full anyway
+        if buckets.len() >= MAX_TRACKED_CLIENTS {
+            buckets.retain(|_, bucket| {
+                bucket.tokens + now.duration_since(bucket.updated).as_secs_f64() * per_second < capacity
+            });
+        }
+
+        let bucket = buckets.entry(client).or_insert(Bucket { tokens: capacity, updated: now });
+        let refilled = now.duration_since(bucket.updated).as_secs_f64() * per_second;
+        bucket.tokens = (bucket.tokens + refilled).min(capacity);
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / per_second))
+        }
+    }
+}
+
+/// Rejects clients that exceed the rate limit with a 429 and a `Retry-After` header
+///
+/// The limiter is read from the app data and clients are told apart by the IP of the
+/// connection. Requests pass through when no limiter is configured, and the health
+/// probes are never limited. Must be installed inside `request_id`.
+pub async fn rate_limit(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<EitherBody<impl MessageBody>>, Error> {
+    let limiter = req.app_data::<web::Data<RateLimiter>>().cloned();
+    let client = req.peer_addr().map(|addr| addr.ip());
+
+    if let (Some(limiter), Some(client)) = (limiter, client)
+        && !RATE_LIMIT_EXEMPT_PATHS.contains(&req.path())
+        && let Err(wait) = limiter.acquire(client)
+    {
+        let request_id = req.extensions().get::<RequestId>().cloned().unwrap_or_default();
+        let retry_after = wait.as_secs_f64().ceil().max(1.0) as u64;
+        let mut response = crate::routes::error_response(
+            request_id,
+            StatusCode::TOO_MANY_REQUESTS,
+            "RATE_LIMITED",
+            format!("Rate limit of {} requests per minute exceeded, retry in {}s", limiter.per_minute, retry_after),
+        );
+        response.headers_mut().insert(header::RETRY_AFTER, HeaderValue::from(retry_after));
+        return Ok(req.into_response(response).map_into_right_body());
+    }
+
+    Ok(next.call(req).await?.map_into_left_body())
+}
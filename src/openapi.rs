@@ -0,0 +1,35 @@
+use actix_web::web;
+use utoipa::OpenApi;
+use utoipa_swagger_ui::SwaggerUi;
+
+use crate::routes;
+
+/// The OpenAPI contract of the HTTP API
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "panchang-rs", description = "Panchang (Hindu calendar) calculations over HTTP"),
+    paths(
+        routes::health_check,
+        routes::liveness,
+        routes::readiness,
+        routes::metrics,
+        routes::panchang_handler,
+        routes::panchang_query_handler,
+        routes::panchang_now_handler,
+        routes::panchang_range_handler,
+        routes::panchang_range_ndjson_handler,
+        routes::panchang_next_tithi_handler,
+        routes::panchang_compare_handler,
+    ),
+    components(schemas(crate::panchang::PanchangResponseV1, crate::panchang::PanchangSample, crate::panchang::TithiOccurrenceV1, crate::panchang::PanchangComparisonV1)),
+    tags(
+        (name = "panchang", description = "Panchang calculations"),
+        (name = "health", description = "Service health"),
+    ),
+)]
+pub struct ApiDoc;
+
+/// Serves the OpenAPI JSON at `/api-docs/openapi.json` and a Swagger UI at `/swagger-ui`
+pub fn init(cfg: &mut web::ServiceConfig) {
+    cfg.service(SwaggerUi::new("/swagger-ui/{_:.*}").url("/api-docs/openapi.json", ApiDoc::openapi()));
+}
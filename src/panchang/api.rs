@@ -1,64 +1,2052 @@
-use actix_web::{web};
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveTime, Offset, SecondsFormat, TimeZone, Timelike, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
-use crate::panchang::{parse_date,parse_time, calculate_panchanga};
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+use crate::panchang::{Language, ResponseFields, Samples, V1Shape, field_labels, Ayanamsa, Calendar, MonthSystem, NakshatraSystem, NameKind, Panchanga, PanchangError, Script, name_in_script, parse_date, parse_clock_time, parse_tz_offset, calculate_panchanga_detailed, sunrise_sunset, moonrise_moonset, sankranti_on, rashi_end, tithi_bounds, tithi_sunrises, TithiStatus, yoga_bounds, nakshatra_bounds, nakshatra_portions, critical_yogas_on, bhadra_on, bhadra, vaara, weekday, inauspicious_periods, choghadiya, current_hora, abhijit_muhurta, kali_ahargana, nakshatra_index, rashi_index, tarabala, chandrabala, special_days, panchaka, disha_shool, lagna, tithi_numbers, calculate_panchanga, calculate_panchanga_at, ayanamsa_degrees, ACCURATE_YEARS, GREGORIAN_CUTOVER};
 
-#[derive(Debug, Deserialize)]
+/// Longest date range, in days, accepted by `calculate_panchang_range` unless the
+/// server sets another
+const MAX_RANGE_DAYS: u32 = 366;
+
+/// Most days `calculate_next_tithi` searches, covering two lunar months
+const MAX_TITHI_SEARCH_DAYS: usize = 60;
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema, utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PanchangRequest {
-    /// Date in DD/MM/YYYY format
-    date: String,
-    /// Time in HH:MM 24-hour format
+    /// Date in DD/MM/YYYY format, given together with `time` unless `epoch` is
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(example = "15/08/2023"), param(example = "15/08/2023"))]
+    date: Option<String>,
+    /// Time in HH:MM 24-hour format, given together with `date` unless `epoch` is
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(example = "12:30"), param(example = "12:30"))]
+    time: Option<String>,
+    /// Unix timestamp in seconds, an alternative to `date` and `time`; the zone
+    /// still gives the local date and times
+    #[serde(default)]
+    epoch: Option<i64>,
+    /// Calendar `date` is given in, with the first date of the Gregorian calendar
+    #[serde(flatten)]
+    #[cfg_attr(feature = "openapi", param(ignore))]
+    calendar: RequestCalendar,
+    /// Timezone offset from GMT in [+/-]HH:MM, [+/-]HH or Z format, used when `timezone` is absent
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(example = "+05:30"), param(example = "+05:30"))]
+    zone: Option<String>,
+    /// Observer location, with the IANA timezone name that takes precedence over `zone`
+    #[serde(flatten)]
+    #[cfg_attr(feature = "openapi", param(ignore))]
+    location: RequestLocation,
+    /// Ayanamsa, naming, output and response field options
+    #[serde(flatten)]
+    #[cfg_attr(feature = "openapi", param(ignore))]
+    options: RequestOptions,
+    /// Also calculate the limbs at other times of the date, as a count of times evenly
+    /// spaced from midnight (e.g. `4`) or a comma-separated list of `sunrise`, `noon`,
+    /// `sunset` and HH:MM times, at most 48
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "openapi", schema(value_type = Option<String>, example = "sunrise,noon,sunset"), param(value_type = Option<String>, example = "sunrise,noon,sunset"))]
+    samples: Option<Samples>,
+    /// Years the deployment calculates, set by the server rather than the request
+    #[serde(skip)]
+    supported_years: Option<RangeInclusive<i32>>,
+    /// Parts of the location filled in from the server's default, set by the server
+    /// rather than the request
+    #[serde(skip_deserializing, skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "openapi", schema(ignore), param(ignore))]
+    defaulted: Vec<String>,
+}
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema, utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+#[derive(Debug, Deserialize)]
+pub struct PanchangRangeRequest {
+    /// First date of the range in DD/MM/YYYY format
+    #[cfg_attr(feature = "openapi", schema(example = "01/08/2023"), param(example = "01/08/2023"))]
+    start_date: String,
+    /// Last date of the range (inclusive) in DD/MM/YYYY format
+    #[cfg_attr(feature = "openapi", schema(example = "31/08/2023"), param(example = "31/08/2023"))]
+    end_date: String,
+    /// Time in HH:MM 24-hour format, used for every day of the range
+    #[cfg_attr(feature = "openapi", schema(example = "06:00"), param(example = "06:00"))]
     time: String,
-    /// Timezone offset from GMT in [+/-]HH:MM format
-    zone: String,
+    /// Calendar the dates are given in, which also writes the days of the range
+    #[serde(flatten)]
+    #[cfg_attr(feature = "openapi", param(ignore))]
+    calendar: RequestCalendar,
+    /// Timezone offset from GMT in [+/-]HH:MM, [+/-]HH or Z format, used when `timezone` is absent
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(example = "+05:30"), param(example = "+05:30"))]
+    zone: Option<String>,
+    /// Observer location, with the IANA timezone name resolved separately for each
+    /// day of the range
+    #[serde(flatten)]
+    #[cfg_attr(feature = "openapi", param(ignore))]
+    location: RequestLocation,
+    /// Options shared by every day of the range
+    #[serde(flatten)]
+    #[cfg_attr(feature = "openapi", param(ignore))]
+    options: RequestOptions,
+    /// Years the deployment calculates, set by the server rather than the request
+    #[serde(skip)]
+    supported_years: Option<RangeInclusive<i32>>,
+    /// Longest range the deployment calculates, set by the server rather than the request
+    #[serde(skip)]
+    max_days: Option<u32>,
+    /// Parts of the location filled in from the server's default, set by the server
+    /// rather than the request
+    #[serde(skip)]
+    defaulted: Vec<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+#[derive(Debug, Deserialize)]
+pub struct PanchangNowRequest {
+    /// Observer latitude in degrees (north positive), the default location's when absent
+    #[serde(default)]
+    lat: Option<f64>,
+    /// Observer longitude in degrees (east positive), the default location's when absent
+    #[serde(default)]
+    lon: Option<f64>,
+    /// IANA timezone name (e.g. Asia/Kolkata), the default location's when absent
+    #[serde(default)]
+    timezone: Option<String>,
+    /// Options as `/panchang` takes them
+    #[serde(flatten)]
+    #[cfg_attr(feature = "openapi", param(ignore))]
+    options: RequestOptions,
+}
+
+/// Options every Panchang request takes alike, for the Ayanamsa, the naming of the
+/// limbs and what the response holds
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema, utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RequestOptions {
+    /// Ayanamsa system for the sidereal elements, the server's default (Lahiri unless
+    /// configured otherwise) when absent
+    #[serde(default)]
+    pub ayanamsa: Option<Ayanamsa>,
+    /// Reckoning used to name the Masa, Amanta when absent
+    #[serde(default)]
+    pub month_system: MonthSystem,
+    /// Number of Nakshatras, 27 or 28 (with Abhijit), 27 when absent
+    #[serde(default, deserialize_with = "nakshatra_count")]
+    pub nakshatra_system: NakshatraSystem,
+    /// Janma (birth) Nakshatra for Tarabala, e.g. Rohini
+    #[serde(default)]
+    pub janma_nakshatra: Option<String>,
+    /// Janma (birth) Rashi for Chandrabala, e.g. Vrishabha
+    #[serde(default)]
+    pub janma_rashi: Option<String>,
+    /// Evaluate the Panchang at local sunrise instead of `time`, as traditionally
+    /// reported; `time` is kept where the Sun does not rise
+    #[serde(default, deserialize_with = "flag")]
+    pub at_sunrise: bool,
+    /// Script of the element names in the response, roman when absent
+    #[serde(default)]
+    pub script: Script,
+    /// Include the parsed inputs and intermediate values in the response as `meta`
+    #[serde(default, deserialize_with = "flag")]
+    pub verbose: bool,
+    /// Include the sidereal longitudes of the Sun and the Moon in the response
+    #[serde(default, deserialize_with = "flag")]
+    pub longitudes: bool,
+    /// Response fields to send, comma-separated (e.g. `tithi,nakshatra`), all when absent
+    #[serde(default, skip_serializing)]
+    #[cfg_attr(feature = "openapi", schema(value_type = Option<String>, example = "tithi,nakshatra"), param(value_type = Option<String>, example = "tithi,nakshatra"))]
+    pub fields: Option<ResponseFields>,
+}
+
+impl RequestOptions {
+    /// Turns on `verbose` when it is set outside the request, e.g. by a query flag
+    pub fn with_verbose(self, verbose: bool) -> Self {
+        RequestOptions { verbose: self.verbose || verbose, ..self }
+    }
+
+    /// Turns on `longitudes` when it is set outside the request, e.g. by a query flag
+    pub fn with_longitudes(self, longitudes: bool) -> Self {
+        RequestOptions { longitudes: self.longitudes || longitudes, ..self }
+    }
+
+    /// Uses `ayanamsa` when the request does not name one
+    pub fn with_default_ayanamsa(self, ayanamsa: Ayanamsa) -> Self {
+        RequestOptions { ayanamsa: self.ayanamsa.or(Some(ayanamsa)), ..self }
+    }
+
+    /// Response fields the request selected, all of them when `None`
+    pub fn fields(&self) -> Option<&ResponseFields> {
+        self.fields.as_ref()
+    }
+}
+
+/// Calendar the dates of a request are given in, and its responses write them in
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema, utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RequestCalendar {
+    /// Calendar of the dates, Gregorian when absent; `julian` reads dates before
+    /// `gregorian_cutover` as Julian, as historical almanacs give them, and writes
+    /// the dates of the response alike
+    #[serde(default)]
+    pub calendar: Calendar,
+    /// First date of the Gregorian calendar in DD/MM/YYYY format, for the `julian`
+    /// calendar, 15/10/1582 when absent
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(example = "14/09/1752"), param(example = "14/09/1752"))]
+    pub gregorian_cutover: Option<String>,
+}
+
+impl RequestCalendar {
+    /// Reads the first date of the Gregorian calendar, `GREGORIAN_CUTOVER` when absent
+    ///
+    /// # Returns
+    /// The calendar, with the cutover its dates are parsed and formatted against
+    fn resolve(&self) -> Result<(Calendar, (i32, i32, i32)), PanchangError> {
+        let cutover = self.gregorian_cutover.as_deref().map(parse_date).transpose()?;
+        Ok((self.calendar, cutover.unwrap_or(GREGORIAN_CUTOVER)))
+    }
+}
+
+impl PanchangRequest {
+    /// Builds a request for a local date, time and timezone offset at a location,
+    /// leaving every option at its default
+    pub fn new(date: &str, time: &str, zone: &str, latitude: f64, longitude: f64) -> Self {
+        PanchangRequest {
+            date: Some(date.to_string()),
+            time: Some(time.to_string()),
+            zone: Some(zone.to_string()),
+            location: Location { latitude, longitude, timezone: None }.into(),
+            ..PanchangRequest::default()
+        }
+    }
+
+    /// Builds the request for the Panchang at sunrise on a local date at a location,
+    /// named by its timezone, leaving every other option at its default
+    ///
+    /// `time` is 06:00, used only where the Sun does not rise.
+    pub fn at_sunrise(date: &str, location: &Location) -> Self {
+        PanchangRequest {
+            date: Some(date.to_string()),
+            time: Some(String::from("06:00")),
+            location: location.clone().into(),
+            options: RequestOptions { at_sunrise: true, ..RequestOptions::default() },
+            ..PanchangRequest::default()
+        }
+    }
+
+    /// Options of the request
+    pub fn options(&self) -> &RequestOptions {
+        &self.options
+    }
+
+    /// Changes the options of the request, e.g. to apply a query flag or a server default
+    pub fn map_options(self, change: impl FnOnce(RequestOptions) -> RequestOptions) -> Self {
+        PanchangRequest { options: change(self.options), ..self }
+    }
+
+    /// Rejects dates whose year is outside `years`, which are otherwise calculated
+    /// with an accuracy warning when outside `ACCURATE_YEARS`
+    pub fn with_supported_years(self, years: RangeInclusive<i32>) -> Self {
+        PanchangRequest { supported_years: Some(years), ..self }
+    }
+
+    /// Fills in the latitude and longitude the request leaves out from `default`, and
+    /// its timezone when the request gives neither a timezone nor a `zone`
+    pub fn with_default_location(mut self, default: &Location) -> Self {
+        self.defaulted = self.location.fill_in(self.zone.as_deref(), default);
+        self
+    }
+}
+
+impl PanchangNowRequest {
+    /// Options of the request
+    pub fn options(&self) -> &RequestOptions {
+        &self.options
+    }
+
+    /// Changes the options of the request, e.g. to apply a server default
+    pub fn map_options(self, change: impl FnOnce(RequestOptions) -> RequestOptions) -> Self {
+        PanchangNowRequest { options: change(self.options), ..self }
+    }
+}
+
+impl PanchangRangeRequest {
+    /// Options of the request, shared by every day of the range
+    pub fn options(&self) -> &RequestOptions {
+        &self.options
+    }
+
+    /// Changes the options of the request, e.g. to apply a query flag or a server default
+    pub fn map_options(self, change: impl FnOnce(RequestOptions) -> RequestOptions) -> Self {
+        PanchangRangeRequest { options: change(self.options), ..self }
+    }
+
+    /// Rejects ranges reaching a year outside `years`
+    pub fn with_supported_years(self, years: RangeInclusive<i32>) -> Self {
+        PanchangRangeRequest { supported_years: Some(years), ..self }
+    }
+
+    /// Rejects ranges spanning more than `days` days
+    pub fn with_max_days(self, days: u32) -> Self {
+        PanchangRangeRequest { max_days: Some(days), ..self }
+    }
+
+    /// Fills in the latitude and longitude the request leaves out from `default`, and
+    /// its timezone when the request gives neither a timezone nor a `zone`
+    pub fn with_default_location(mut self, default: &Location) -> Self {
+        self.defaulted = self.location.fill_in(self.zone.as_deref(), default);
+        self
+    }
+
+    /// Checks the range and builds the request of each of its days, in order, with
+    /// the same time, zone and location
+    pub fn days(&self) -> Result<Vec<PanchangRequest>, PanchangError> {
+        self.location.resolve()?;
+
+        // Parse and order the range
+        let (calendar, cutover) = self.calendar.resolve()?;
+        let start = to_naive_date(calendar.parse_date(&self.start_date, cutover)?);
+        let end = to_naive_date(calendar.parse_date(&self.end_date, cutover)?);
+        check_supported_year(start.year(), self.supported_years.as_ref())?;
+        check_supported_year(end.year(), self.supported_years.as_ref())?;
+
+        let days = (end - start).num_days() + 1;
+        if days < 1 {
+            return Err(PanchangError::InvalidDateRange("end_date must not be before start_date".to_string()));
+        }
+        let max_days = self.max_days.unwrap_or(MAX_RANGE_DAYS);
+        if days > i64::from(max_days) {
+            return Err(PanchangError::InvalidDateRange(format!(
+                "Date range spans {} days, at most {} are allowed", days, max_days
+            )));
+        }
+
+        // Every day shares the options of the range
+        let base = PanchangRequest {
+            time: Some(self.time.clone()),
+            calendar: self.calendar.clone(),
+            zone: self.zone.clone(),
+            location: self.location.clone(),
+            options: self.options.clone(),
+            supported_years: self.supported_years.clone(),
+            defaulted: self.defaulted.clone(),
+            ..PanchangRequest::default()
+        };
+        Ok(start
+            .iter_days()
+            .take(days as usize)
+            .map(|date| PanchangRequest {
+                date: Some(calendar.format_date((date.day() as i32, date.month() as i32, date.year()), cutover)),
+                ..base.clone()
+            })
+            .collect())
+    }
+}
+
+/// Observer location, given by the fields of the same names in every request and
+/// used by the server as the default location
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema, utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Location {
+    /// Latitude in degrees (north positive), -90 to 90
+    #[serde(deserialize_with = "coordinate")]
+    #[cfg_attr(feature = "openapi", schema(example = 28.6139), param(example = 28.6139))]
+    pub latitude: f64,
+    /// Longitude in degrees (east positive), -180 to 180
+    #[serde(deserialize_with = "coordinate")]
+    #[cfg_attr(feature = "openapi", schema(example = 77.2090), param(example = 77.2090))]
+    pub longitude: f64,
+    /// IANA timezone name (e.g. Asia/Kolkata), resolved with the DST rules of the date
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(example = "Asia/Kolkata"), param(example = "Asia/Kolkata"))]
+    pub timezone: Option<String>,
+}
+
+impl Location {
+    /// Rejects a latitude outside -90 to 90 or a longitude outside -180 to 180
+    pub fn validate(&self) -> Result<(), PanchangError> {
+        if !(-90.0..=90.0).contains(&self.latitude) {
+            return Err(PanchangError::InvalidLocation(format!(
+                "latitude must be between -90 and 90, got {}",
+                self.latitude
+            )));
+        }
+        if !(-180.0..=180.0).contains(&self.longitude) {
+            return Err(PanchangError::InvalidLocation(format!(
+                "longitude must be between -180 and 180, got {}",
+                self.longitude
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Observer location as a Panchang request gives it, each part of which the server
+/// may fill in from its default location
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema, utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RequestLocation {
+    /// Latitude in degrees (north positive), -90 to 90, the server's default when absent
+    #[serde(default, deserialize_with = "optional_coordinate", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "openapi", schema(example = 28.6139), param(example = 28.6139))]
+    pub latitude: Option<f64>,
+    /// Longitude in degrees (east positive), -180 to 180, the server's default when absent
+    #[serde(default, deserialize_with = "optional_coordinate", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "openapi", schema(example = 77.2090), param(example = 77.2090))]
+    pub longitude: Option<f64>,
+    /// IANA timezone name (e.g. Asia/Kolkata), resolved with the DST rules of the date;
+    /// the server's default when both this and `zone` are absent
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(example = "Asia/Kolkata"), param(example = "Asia/Kolkata"))]
+    pub timezone: Option<String>,
+}
+
+impl From<Location> for RequestLocation {
+    fn from(location: Location) -> Self {
+        RequestLocation {
+            latitude: Some(location.latitude),
+            longitude: Some(location.longitude),
+            timezone: location.timezone,
+        }
+    }
+}
+
+impl RequestLocation {
+    /// Fills in the latitude and longitude left out from `default`, and the timezone
+    /// when neither it nor `zone` is given
+    ///
+    /// # Returns
+    /// The parts filled in, in order
+    fn fill_in(&mut self, zone: Option<&str>, default: &Location) -> Vec<String> {
+        let mut defaulted = Vec::new();
+        if self.latitude.is_none() {
+            self.latitude = Some(default.latitude);
+            defaulted.push(String::from("latitude"));
+        }
+        if self.longitude.is_none() {
+            self.longitude = Some(default.longitude);
+            defaulted.push(String::from("longitude"));
+        }
+        if self.timezone.is_none() && zone.is_none() && default.timezone.is_some() {
+            self.timezone = default.timezone.clone();
+            defaulted.push(String::from("timezone"));
+        }
+        defaulted
+    }
+
+    /// Completes the location, rejecting it when a coordinate is missing or out of range
+    fn resolve(&self) -> Result<Location, PanchangError> {
+        let missing = |name: &str| PanchangError::InvalidLocation(format!("{} must be given", name));
+        let location = Location {
+            latitude: self.latitude.ok_or_else(|| missing("latitude"))?,
+            longitude: self.longitude.ok_or_else(|| missing("longitude"))?,
+            timezone: self.timezone.clone(),
+        };
+        location.validate()?;
+        Ok(location)
+    }
+}
+
+/// Reads an optional coordinate, as `coordinate` reads a given one
+fn optional_coordinate<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Option<f64>, D::Error> {
+    #[derive(Deserialize)]
+    struct Degrees(#[serde(deserialize_with = "coordinate")] f64);
+
+    Ok(Option::<Degrees>::deserialize(deserializer)?.map(|Degrees(degrees)| degrees))
+}
+
+/// Reads a coordinate given as a number, or as a string in a query string, whose
+/// values a flattened field only sees as strings
+fn coordinate<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Coordinate {
+        Number(f64),
+        Text(String),
+    }
+
+    match Coordinate::deserialize(deserializer)? {
+        Coordinate::Number(degrees) => Ok(degrees),
+        Coordinate::Text(text) => text
+            .trim()
+            .parse()
+            .map_err(|_| serde::de::Error::custom(format!("invalid coordinate '{}', expected degrees", text))),
+    }
+}
+
+/// Reads a flag given as a boolean, or as `true` or `false` in a query string, as
+/// `coordinate` reads a coordinate
+fn flag<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Flag {
+        Bool(bool),
+        Text(String),
+    }
+
+    match Flag::deserialize(deserializer)? {
+        Flag::Bool(flag) => Ok(flag),
+        Flag::Text(text) => text
+            .trim()
+            .parse()
+            .map_err(|_| serde::de::Error::custom(format!("invalid flag '{}', expected true or false", text))),
+    }
+}
+
+/// Reads the number of Nakshatras given as a number, or as a string in a query
+/// string, as `coordinate` reads a coordinate
+fn nakshatra_count<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<NakshatraSystem, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Count {
+        Number(u64),
+        Text(String),
+    }
+
+    let count = match Count::deserialize(deserializer)? {
+        Count::Number(count) => count,
+        Count::Text(text) => text
+            .trim()
+            .parse()
+            .map_err(|_| serde::de::Error::custom(format!("invalid nakshatra_system '{}', expected 27 or 28", text)))?,
+    };
+    u8::try_from(count)
+        .map_err(|_| format!("nakshatra_system must be 27 or 28, got {}", count))
+        .and_then(NakshatraSystem::try_from)
+        .map_err(serde::de::Error::custom)
+}
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Vaara {
+    /// Sanskrit name of the weekday (e.g. Ravivara)
+    sanskrit: String,
+    /// English name of the weekday (e.g. Sunday)
+    english: String,
+}
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SunriseTithis {
+    /// Tithi in effect at the date's sunrise, which names the day
+    at_sunrise: String,
+    /// Tithi in effect at the next date's sunrise
+    at_next_sunrise: String,
+    /// Tithi that begins and ends between the two sunrises, absent unless it is kshaya
+    skipped: Option<String>,
+}
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Paksha {
+    /// Name of the Paksha (Shukla or Krishna)
+    name: String,
+    /// Position (1-15) of the Tithi within the Paksha, 15 being Poornima in Shukla
+    /// and Amavasya in Krishna
+    index: u8,
+}
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeWindow {
+    /// Local start time in HH:MM format
+    start: String,
+    /// Local end time in HH:MM format
+    end: String,
+}
+
+impl TimeWindow {
+    fn from_hours((start, end): (f64, f64)) -> Self {
+        TimeWindow {
+            start: format_hours(start),
+            end: format_hours(end),
+        }
+    }
+}
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstantWindow {
+    /// Start as an ISO-8601 datetime in the requested zone
+    start: String,
+    /// End as an ISO-8601 datetime in the requested zone
+    end: String,
+}
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Choghadiya {
+    /// Name of the Choghadiya (Amrit, Shubh, Labh, Char, Rog, Kaal, Udveg)
+    name: String,
+    /// Whether the Choghadiya is auspicious
+    auspicious: bool,
+    /// Local start and end times
+    #[serde(flatten)]
+    window: TimeWindow,
+}
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CriticalYoga {
+    /// Vyatipata or Vaidhruthi
+    yoga: String,
+    /// Start of the Yoga as an ISO-8601 datetime in the requested zone
+    start: String,
+    /// End of the Yoga as an ISO-8601 datetime in the requested zone
+    end: String,
+}
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BhadraWindow {
+    /// Start of the Visti Karana as an ISO-8601 datetime in the requested zone
+    start: String,
+    /// End of the Visti Karana as an ISO-8601 datetime in the requested zone
+    end: String,
+    /// Paksha the Bhadra falls in
+    paksha: String,
+    /// Vrishchiki (the scorpion) in the Shukla Paksha, Sarpini (the serpent) in the Krishna
+    form: String,
+    /// Mukha (face), 5 ghatis (sixtieths of the Tithi) from the start of the Prahara
+    /// (eighth of the Tithi) holding it
+    face: InstantWindow,
+    /// Puchha (tail), 3 ghatis up to the end of the Prahara holding it
+    tail: InstantWindow,
+    /// The part to avoid above all, `tail` for Vrishchiki and `face` for Sarpini
+    avoid: String,
+    /// `day` for a day Bhadra, of the second half of its Tithi, or `night` for a
+    /// night Bhadra, of the first half
+    kind: String,
+    /// Whether the middle of the Bhadra falls between sunrise and sunset, absent
+    /// where the Sun does not rise or set that day
+    by_day: Option<bool>,
+    /// Whether the Bhadra does no harm, being a day Bhadra at night or a night Bhadra
+    /// by day
+    harmless: Option<bool>,
+}
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DishaShoolStatus {
+    /// Direction in which travel is inauspicious (e.g. East)
+    direction: String,
+    /// Parihar (remedy) taken before a journey that cannot be put off
+    remedy: String,
+}
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Hora {
+    /// Ruling planet of the Hora (Surya, Chandra, ...)
+    lord: String,
+    /// Local start and end times
+    #[serde(flatten)]
+    window: TimeWindow,
+}
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Sankranti {
+    /// Rashi the Sun enters
+    rashi: String,
+    /// Instant of the ingress as an ISO-8601 datetime in the requested zone
+    time: String,
+}
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Lagna {
+    /// Rashi rising in the east
+    rashi: String,
+    /// Sidereal longitude (0-360) of the ascendant in degrees
+    longitude: f64,
+}
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TarabalaStatus {
+    /// Position (1-27) of the day's Nakshatra counted from the janma Nakshatra
+    count: usize,
+    /// Name of the Tara (Janma, Sampat, Vipat, ...)
+    tara: String,
+    /// Whether the Tara is favourable
+    good: bool,
+}
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChandrabalaStatus {
+    /// Position (1-12) of the Moon's Rashi counted from the janma Rashi
+    position: usize,
+    /// Whether the position is favourable
+    good: bool,
+}
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalculationMeta {
+    /// Parsed day of the month, in the Gregorian calendar
+    day: i32,
+    /// Parsed month (1-12), in the Gregorian calendar
+    month: i32,
+    /// Parsed year, in the Gregorian calendar
+    year: i32,
+    /// Local time the elements were calculated for, in decimal hours
+    hour: f64,
+    /// Resolved offset from GMT in decimal hours
+    zone_offset: f64,
+    /// Fractional day number of the instant, counted from 1999-12-31 0h UT
+    days_since_j2000: f64,
+    /// Ayanamsa in degrees, subtracted from tropical longitudes to make them sidereal
+    ayanamsa: f64,
+    /// Tropical longitude of the Sun in degrees
+    sun_longitude: f64,
+    /// Tropical longitude of the Moon in degrees
+    moon_longitude: f64,
+    /// Parts of the location the request left out, filled in from the server's default
+    #[serde(default)]
+    defaulted: Vec<String>,
+}
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PanchangResponse {
+    /// Current Vaara (Weekday), which changes at sunrise
+    vaara: Vaara,
     /// Current Tithi (Lunar Day)
     tithi: String,
-    /// Current Paksha (Lunar Phase)
-    paksha: String,
-    /// Current Nakshatra (Lunar Mansion)
+    /// Start of the current Tithi as an ISO-8601 datetime in the requested zone
+    tithi_start: Option<String>,
+    /// End of the current Tithi as an ISO-8601 datetime in the requested zone
+    tithi_end: Option<String>,
+    /// Whether a Tithi is skipped (kshaya) or repeated (vriddhi) between the date's
+    /// sunrise and the next, absent when either sunrise is missing
+    tithi_status: Option<TithiStatus>,
+    /// Tithis in effect at the date's sunrise and the next, and the one skipped
+    sunrise_tithis: Option<SunriseTithis>,
+    /// Current Paksha (Lunar Phase), which in the Purnimanta reckoning belongs to the
+    /// Masa named in `masa` as well: a Krishna Paksha opens the next month there
+    paksha: Paksha,
+    /// Current Masa (Lunar Month) in the requested reckoning
+    masa: String,
+    /// Whether the current Masa is an Adhika (intercalary) month
+    adhika_masa: bool,
+    /// Current Samvatsara (year of the 60-year cycle)
+    samvatsara: String,
+    /// Current Kali year, the years elapsed since the start of the Kali Yuga
+    kali_year: i32,
+    /// Days elapsed since the start of the Kali Yuga to the start of the Vaara
+    kali_ahargana: i32,
+    /// Current Ayana (Uttarayana/Dakshinayana)
+    ayana: String,
+    /// Current Ritu (Season)
+    ritu: String,
+    /// Current Nakshatra (Lunar Mansion) in the requested system
     nakshatra: String,
+    /// Current pada (quarter, 1-4) of the Nakshatra
+    nakshatra_pada: u8,
+    /// Ruling planet (Vimshottari dasha lord) of the current Nakshatra
+    nakshatra_lord: String,
     /// Current Yoga (Luni-Solar Day)
     yoga: String,
+    /// Start of the current Yoga as an ISO-8601 datetime in the requested zone
+    yoga_start: Option<String>,
+    /// End of the current Yoga as an ISO-8601 datetime in the requested zone
+    yoga_end: Option<String>,
+    /// Windows of Vyatipata and Vaidhruthi, the inauspicious Yogas, that overlap the
+    /// requested date
+    critical_yogas: Vec<CriticalYoga>,
     /// Current Karana (Half Lunar Day)
     karana: String,
-    /// Current Rashi (Zodiac Sign)
+    /// Window of Visti (Bhadra), the inauspicious Karana, that overlaps the requested
+    /// date, absent on other days
+    bhadra: Option<BhadraWindow>,
+    /// Current Rashi (Zodiac Sign) of the Moon
     rashi: String,
+    /// Ruling planet of the Moon's current Rashi
+    rashi_lord: String,
+    /// Rashi of the Moon in the Navamsa (D9) chart, from its Nakshatra pada
+    navamsa_rashi: String,
+    /// When the Moon leaves its current Rashi, as an ISO-8601 datetime in the
+    /// requested zone
+    rashi_end: Option<String>,
+    /// Rashi the Moon enters at `rashi_end`
+    next_rashi: Option<String>,
+    /// Current Rashi (Zodiac Sign) of the Sun
+    surya_rashi: String,
+    /// Nakshatra the Sun is in
+    surya_nakshatra: String,
+    /// Pada (quarter, 1-4) of the Sun's Nakshatra
+    surya_nakshatra_pada: u8,
+    /// The Sun's entry into a new Rashi during the requested date, absent on other days
+    sankranti: Option<Sankranti>,
+    /// Lagna (ascendant) at the requested time and location
+    lagna: Option<Lagna>,
+    /// Illuminated fraction of the Moon's disc (0.0-1.0)
+    moon_illumination: f64,
+    /// Moon's phase angle in degrees, the Sun-Moon-Earth angle (180 at new moon, 0 at
+    /// full moon)
+    phase_angle: f64,
+    /// Name of the Moon's phase (New Moon, Waxing Crescent, ...)
+    phase_name: String,
+    /// Local sunrise in HH:MM format, absent when the Sun does not rise
+    sunrise: Option<String>,
+    /// Local sunset in HH:MM format, absent when the Sun does not set
+    sunset: Option<String>,
+    /// Local moonrise in HH:MM format, absent on the day each month the Moon does
+    /// not rise
+    moonrise: Option<String>,
+    /// Local moonset in HH:MM format, absent on the day each month the Moon does not
+    /// set
+    moonset: Option<String>,
+    /// Rahu Kaal, absent when there is no sunrise and sunset
+    rahu_kaal: Option<TimeWindow>,
+    /// Yamaganda, absent when there is no sunrise and sunset
+    yamaganda: Option<TimeWindow>,
+    /// Gulika Kaal, absent when there is no sunrise and sunset
+    gulika: Option<TimeWindow>,
+    /// Abhijit Muhurta, absent on Wednesdays and when there is no sunrise and sunset
+    abhijit_muhurta: Option<TimeWindow>,
+    /// Varjyam, the inauspicious portion of the current Nakshatra
+    varjyam: Option<InstantWindow>,
+    /// Amrita Kaal, the auspicious portion of the current Nakshatra
+    amrita_kaal: Option<InstantWindow>,
+    /// Hora (planetary hour) in effect at the requested time, absent when there is
+    /// no sunrise and sunset
+    hora: Option<Hora>,
+    /// Observances falling on the current Tithi (Ekadashi, Pradosh, ...), empty when none
+    festivals: Vec<String>,
+    /// Panchaka in effect (e.g. Mrityu Panchaka), null when the Moon is outside it
+    panchaka: Option<String>,
+    /// Direction inauspicious for travel on the Vaara, with its remedy
+    disha_shool: DishaShoolStatus,
+    /// Tarabala for the requested janma Nakshatra, absent when none is given
+    tarabala: Option<TarabalaStatus>,
+    /// Chandrabala for the requested janma Rashi, absent when none is given
+    chandrabala: Option<ChandrabalaStatus>,
+    /// The eight daytime then eight nighttime Choghadiyas, absent when there is no
+    /// sunrise and sunset on the day or the next day
+    choghadiya: Option<Vec<Choghadiya>>,
+    /// Why the elements may be less accurate than usual, absent for dates within
+    /// 1900-2100
+    accuracy_warning: Option<String>,
+    /// Sidereal longitude (0-360) of the Sun in degrees, present only when requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sun_longitude: Option<f64>,
+    /// Sidereal longitude (0-360) of the Moon in degrees, present only when requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    moon_longitude: Option<f64>,
+    /// The limbs at each of the requested sample times, present only when requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "openapi", schema(no_recursion))]
+    samples: Option<Vec<PanchangSample>>,
+    /// Display label of each field in the requested language, present only when a
+    /// language is requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    labels: Option<BTreeMap<String, String>>,
+    /// Parsed inputs and intermediate values, present only for verbose requests
+    #[serde(skip_serializing_if = "Option::is_none")]
+    meta: Option<CalculationMeta>,
 }
 
-pub async fn calculate_panchang(data: web::Json<PanchangRequest>) -> Result<PanchangResponse, String> {
-    // Parse date
-    let (day, month, year) = parse_date(&data.date)
-        .map_err(|e| format!("Error parsing date: {}", e))?;
+impl From<Panchanga> for PanchangResponse {
+    /// Carries over the calculated elements, naming the Masa in the Amanta reckoning.
+    /// The Vaara, Kali Ahargana, Tithi times, sunrise, sunset and time windows depend on the request
+    /// and are left empty.
+    fn from(panchanga: Panchanga) -> Self {
+        PanchangResponse {
+            vaara: Vaara::default(),
+            tithi: panchanga.current_tithi,
+            tithi_start: None,
+            tithi_end: None,
+            tithi_status: None,
+            sunrise_tithis: None,
+            paksha: Paksha {
+                name: panchanga.current_paksha,
+                index: (panchanga.current_tithi_number - 1) % 15 + 1,
+            },
+            masa: panchanga.current_masa,
+            adhika_masa: panchanga.is_adhika_masa,
+            samvatsara: panchanga.current_samvatsara,
+            kali_year: panchanga.current_kali_year,
+            kali_ahargana: 0,
+            ayana: panchanga.current_ayana,
+            ritu: panchanga.current_ritu,
+            nakshatra: panchanga.current_nakshatra,
+            nakshatra_pada: panchanga.current_nakshatra_pada,
+            nakshatra_lord: panchanga.current_nakshatra_lord,
+            yoga: panchanga.current_yoga,
+            yoga_start: None,
+            yoga_end: None,
+            critical_yogas: Vec::new(),
+            karana: panchanga.current_karana,
+            bhadra: None,
+            rashi: panchanga.current_chandra_rashi,
+            rashi_lord: panchanga.current_rashi_lord,
+            navamsa_rashi: panchanga.current_navamsa_rashi,
+            rashi_end: None,
+            next_rashi: None,
+            surya_rashi: panchanga.current_surya_rashi,
+            surya_nakshatra: panchanga.current_surya_nakshatra,
+            surya_nakshatra_pada: panchanga.current_surya_nakshatra_pada,
+            sankranti: None,
+            lagna: None,
+            moon_illumination: panchanga.moon_illumination,
+            phase_angle: panchanga.moon_phase_angle,
+            phase_name: panchanga.moon_phase,
+            sunrise: None,
+            sunset: None,
+            moonrise: None,
+            moonset: None,
+            rahu_kaal: None,
+            yamaganda: None,
+            gulika: None,
+            festivals: Vec::new(),
+            panchaka: None,
+            disha_shool: DishaShoolStatus::default(),
+            tarabala: None,
+            chandrabala: None,
+            abhijit_muhurta: None,
+            varjyam: None,
+            amrita_kaal: None,
+            hora: None,
+            choghadiya: None,
+            accuracy_warning: None,
+            sun_longitude: None,
+            moon_longitude: None,
+            samples: None,
+            labels: None,
+            meta: None,
+        }
+    }
+}
 
-    // Parse time
-    let (hours, minutes) = parse_time(&data.time)
-        .map_err(|e| format!("Error parsing time: {}", e))?;
+/// The limbs at one sample time of a request, each named as the request names them
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PanchangSample {
+    /// Instant the limbs were calculated for, as an ISO-8601 datetime in the requested zone
+    time: String,
+    /// The Panchanga at the instant, without the fields that depend on the whole day
+    #[serde(flatten)]
+    panchang: PanchangResponse,
+}
 
-    // Parse timezone
-    let (zone_hours, zone_minutes) = parse_time(&data.zone.trim_start_matches('+'))
-        .map_err(|e| format!("Error parsing timezone: {}", e))?;
+impl PanchangResponse {
+    /// Adds the field labels in a language, which are not part of the calculation
+    /// and so are never cached
+    pub fn with_labels(self, language: Language) -> Self {
+        PanchangResponse { labels: Some(field_labels(language)), ..self }
+    }
 
-    // Convert to decimal hours
-    let hour = hours + minutes as f64 / 60.0;
-    let zone_hour = if data.zone.starts_with('-') {
-        -(zone_hours + zone_minutes as f64 / 60.0)
-    } else {
-        zone_hours + zone_minutes as f64 / 60.0
+    /// Rewrites the element names in a script, leaving them as they are for roman
+    fn in_script(mut self, script: Script) -> Self {
+        let write = |kind, name: &mut String| *name = name_in_script(kind, name, script);
+
+        write(NameKind::Vaara, &mut self.vaara.sanskrit);
+        write(NameKind::Tithi, &mut self.tithi);
+        if let Some(sunrise_tithis) = &mut self.sunrise_tithis {
+            write(NameKind::Tithi, &mut sunrise_tithis.at_sunrise);
+            write(NameKind::Tithi, &mut sunrise_tithis.at_next_sunrise);
+            if let Some(skipped) = &mut sunrise_tithis.skipped {
+                write(NameKind::Tithi, skipped);
+            }
+        }
+        write(NameKind::Paksha, &mut self.paksha.name);
+        if let Some(bhadra) = &mut self.bhadra {
+            write(NameKind::Paksha, &mut bhadra.paksha);
+        }
+        write(NameKind::Masa, &mut self.masa);
+        write(NameKind::Samvatsara, &mut self.samvatsara);
+        write(NameKind::Ayana, &mut self.ayana);
+        write(NameKind::Ritu, &mut self.ritu);
+        write(NameKind::Nakshatra, &mut self.nakshatra);
+        write(NameKind::Graha, &mut self.nakshatra_lord);
+        write(NameKind::Yoga, &mut self.yoga);
+        write(NameKind::Karana, &mut self.karana);
+        write(NameKind::Rashi, &mut self.rashi);
+        write(NameKind::Graha, &mut self.rashi_lord);
+        write(NameKind::Rashi, &mut self.navamsa_rashi);
+        if let Some(next_rashi) = &mut self.next_rashi {
+            write(NameKind::Rashi, next_rashi);
+        }
+        write(NameKind::Rashi, &mut self.surya_rashi);
+        write(NameKind::Nakshatra, &mut self.surya_nakshatra);
+        if let Some(sankranti) = &mut self.sankranti {
+            write(NameKind::Rashi, &mut sankranti.rashi);
+        }
+        for critical in &mut self.critical_yogas {
+            write(NameKind::Yoga, &mut critical.yoga);
+        }
+        if let Some(lagna) = &mut self.lagna {
+            write(NameKind::Rashi, &mut lagna.rashi);
+        }
+        if let Some(hora) = &mut self.hora {
+            write(NameKind::Graha, &mut hora.lord);
+        }
+        self
+    }
+}
+
+/// A Panchang request whose inputs are parsed and range-checked
+///
+/// Converting a [`PanchangRequest`] resolves its instant, from the epoch or from the
+/// date and time in its calendar, to a proleptic Gregorian date, a decimal hour and the
+/// UTC offset in effect then, checks the location and the supported years, and looks
+/// up the birth details. Any failure is the request's [`PanchangError`], so
+/// [`calculate_panchang_validated`] cannot fail.
+#[derive(Debug, Clone)]
+pub struct ValidatedRequest {
+    date: NaiveDate,
+    hour: f64,
+    offset: FixedOffset,
+    location: Location,
+    defaulted: Vec<String>,
+    janma_nakshatra: Option<usize>,
+    janma_rashi: Option<usize>,
+    ayanamsa: Ayanamsa,
+    month_system: MonthSystem,
+    nakshatra_system: NakshatraSystem,
+    at_sunrise: bool,
+    script: Script,
+    verbose: bool,
+    longitudes: bool,
+    samples: Option<Samples>,
+}
+
+impl ValidatedRequest {
+    /// Local date in the proleptic Gregorian calendar
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+
+    /// Local time in decimal hours
+    pub fn hour(&self) -> f64 {
+        self.hour
+    }
+
+    /// UTC offset in effect at the local date and time
+    pub fn offset(&self) -> FixedOffset {
+        self.offset
+    }
+
+    /// Observer location, within the latitude and longitude ranges
+    pub fn location(&self) -> &Location {
+        &self.location
+    }
+
+    /// Inputs the response depends on, equal for any two requests answered alike
+    ///
+    /// The instant is the parsed date, hour and UTC offset, so `15/8/2023` and
+    /// `15/08/2023`, `+5:30` and `+05:30`, or a zone and the timezone it stands for,
+    /// give the same inputs. The hour is left out of a sunrise Panchang wherever the
+    /// Sun rises, and the defaulted parts of the location unless `verbose` reports them.
+    pub fn normalized(&self) -> serde_json::Value {
+        let (day, month, year) = (self.date.day() as i32, self.date.month() as i32, self.date.year());
+        let zone_hour = self.offset.local_minus_utc() as f64 / 3600.0;
+        let sun_rises = sunrise_sunset(day, month, year, self.location.latitude, self.location.longitude, zone_hour).is_some();
+        serde_json::json!({
+            "date": self.date,
+            "hour": (!(self.at_sunrise && sun_rises)).then_some(self.hour),
+            "offset": self.offset.local_minus_utc(),
+            "latitude": self.location.latitude,
+            "longitude": self.location.longitude,
+            "janma_nakshatra": self.janma_nakshatra,
+            "janma_rashi": self.janma_rashi,
+            "ayanamsa": self.ayanamsa,
+            "month_system": self.month_system,
+            "nakshatra_system": self.nakshatra_system,
+            "at_sunrise": self.at_sunrise,
+            "script": self.script,
+            "verbose": self.verbose,
+            "longitudes": self.longitudes,
+            "samples": self.samples,
+            "defaulted": if self.verbose { self.defaulted.as_slice() } else { &[] },
+        })
+    }
+}
+
+impl TryFrom<&PanchangRequest> for ValidatedRequest {
+    type Error = PanchangError;
+
+    fn try_from(data: &PanchangRequest) -> Result<Self, Self::Error> {
+        let location = data.location.resolve()?;
+
+        // Resolve the local date, time and zone from either the epoch or the date and time
+        let (day, month, year, hour, zone_hour) = match (data.epoch, &data.date, &data.time) {
+            (Some(epoch), None, None) => local_from_epoch(epoch, location.timezone.as_deref(), data.zone.as_deref())?,
+            (None, Some(date), Some(time)) => {
+                let (calendar, cutover) = data.calendar.resolve()?;
+                let (day, month, year) = calendar.parse_date(date, cutover)?;
+                let (hours, minutes) = parse_clock_time(time)?;
+
+                // Convert to decimal hours
+                let hour = hours + minutes as f64 / 60.0;
+
+                // Resolve the timezone, preferring a named zone over a raw offset
+                let zone_hour = match (&location.timezone, &data.zone) {
+                    (Some(timezone), _) => resolve_timezone(timezone, (day, month, year), (hours, minutes))?,
+                    (None, Some(zone)) => parse_tz_offset(zone)?,
+                    (None, None) => return Err(missing_zone()),
+                };
+                (day, month, year, hour, zone_hour)
+            }
+            _ => {
+                return Err(PanchangError::InvalidInstant(
+                    "give either epoch, or both date and time".to_string(),
+                ));
+            }
+        };
+        check_supported_year(year, data.supported_years.as_ref())?;
+
+        // Look up the birth details
+        let options = &data.options;
+        let janma_nakshatra = options
+            .janma_nakshatra
+            .as_deref()
+            .map(|name| {
+                nakshatra_index(name)
+                    .ok_or_else(|| PanchangError::InvalidBirthDetails(format!("unknown Nakshatra '{}'", name)))
+            })
+            .transpose()?;
+        let janma_rashi = options
+            .janma_rashi
+            .as_deref()
+            .map(|name| {
+                rashi_index(name)
+                    .ok_or_else(|| PanchangError::InvalidBirthDetails(format!("unknown Rashi '{}'", name)))
+            })
+            .transpose()?;
+
+        // Pin the offset every instant of the response is formatted in
+        let offset = FixedOffset::east_opt((zone_hour * 3600.0).round() as i32)
+            .ok_or_else(|| PanchangError::InvalidTimezone(format!("offset {} hours is out of range", zone_hour)))?;
+
+        Ok(ValidatedRequest {
+            date: to_naive_date((day, month, year)),
+            hour,
+            offset,
+            location,
+            defaulted: data.defaulted.clone(),
+            janma_nakshatra,
+            janma_rashi,
+            ayanamsa: options.ayanamsa.unwrap_or_default(),
+            month_system: options.month_system,
+            nakshatra_system: options.nakshatra_system,
+            at_sunrise: options.at_sunrise,
+            script: options.script,
+            verbose: options.verbose,
+            longitudes: options.longitudes,
+            samples: data.samples.clone(),
+        })
+    }
+}
+
+/// Validates a request and calculates its Panchang
+pub async fn calculate_panchang(data: &PanchangRequest) -> Result<PanchangResponse, PanchangError> {
+    Ok(calculate_panchang_validated(&ValidatedRequest::try_from(data)?))
+}
+
+/// Calculates the Panchang of a validated request
+pub fn calculate_panchang_validated(request: &ValidatedRequest) -> PanchangResponse {
+    let date = request.date;
+    let (day, month, year) = (date.day() as i32, date.month() as i32, date.year());
+    let (hour, offset) = (request.hour, request.offset);
+    let zone_hour = offset.local_minus_utc() as f64 / 3600.0;
+    let (janma_nakshatra, janma_rashi) = (request.janma_nakshatra, request.janma_rashi);
+
+    // Calculate sunrise and sunset, and moonrise and moonset, at the observer's location
+    let sun_times = sunrise_sunset(day, month, year, request.location.latitude, request.location.longitude, zone_hour);
+    let (moonrise, moonset) = moonrise_moonset(day, month, year, request.location.latitude, request.location.longitude, zone_hour);
+
+    // Evaluate everything at sunrise instead of the requested time when asked to,
+    // keeping the requested time where the Sun does not rise
+    let hour = match sun_times {
+        Some((sunrise, _)) if request.at_sunrise => sunrise,
+        _ => hour,
     };
 
     // Calculate panchanga
-    let panchang_data = calculate_panchanga(day as i32, month, year, hour, zone_hour);
+    let ayanamsa = request.ayanamsa;
+    let (panchang_data, details) = calculate_panchanga_detailed(day, month, year, hour, zone_hour, ayanamsa);
+    let meta = request.verbose.then_some(CalculationMeta {
+        day,
+        month,
+        year,
+        hour,
+        zone_offset: zone_hour,
+        days_since_j2000: details.day_number,
+        ayanamsa: details.ayanamsa,
+        sun_longitude: details.sun_longitude,
+        moon_longitude: details.moon_longitude,
+        defaulted: request.defaulted.clone(),
+    });
+
+    // Report the sidereal longitudes the elements were found from, to the microdegree
+    let round = |degrees: f64| (degrees * 1e6).round() / 1e6;
+    let sun_longitude = request.longitudes.then_some(round(details.sidereal_sun_longitude));
+    let moon_longitude = request.longitudes.then_some(round(details.sidereal_moon_longitude));
+
+    // Find the Rashi rising at the location at the same instant
+    let (lagna_rashi, lagna_longitude) = lagna(details.instant, request.location.latitude, request.location.longitude, ayanamsa);
+    let lagna = Lagna { rashi: lagna_rashi, longitude: round(lagna_longitude) };
+
+    // Calculate when the current Tithi and Yoga began and end
+    let tithi_times = tithi_bounds(day, month, year, hour, zone_hour);
+    let yoga_times = yoga_bounds(day, month, year, hour, zone_hour, ayanamsa);
+
+    // Find when the Moon moves on to the next Rashi
+    let rashi_transit = rashi_end(day, month, year, hour, zone_hour, ayanamsa);
+
+    // Find the windows of the inauspicious Yogas during the local date
+    let critical_yogas = critical_yogas_on(day, month, year, zone_hour, ayanamsa)
+        .into_iter()
+        .map(|(yoga, start, end)| CriticalYoga { yoga, start: format_instant(start, offset), end: format_instant(end, offset) })
+        .collect();
+
+    // Find the Sun's ingress into a new Rashi during the local date
+    let sankranti = sankranti_on(day, month, year, zone_hour, ayanamsa)
+        .map(|(rashi, instant)| Sankranti { rashi, time: format_instant(instant, offset) });
+
+    // The Vedic day runs from sunrise to sunrise, so before sunrise it is still
+    // the previous day's Vaara
+    let before_sunrise = sun_times.is_some_and(|(sunrise, _)| hour < sunrise);
+    let vaara_day = if before_sunrise { day - 1 } else { day };
+    let (vaara_sanskrit, vaara_english) = vaara(vaara_day, month, year);
+
+    // Calculate the inauspicious bands of the daytime
+    let periods = sun_times
+        .map(|(sunrise, sunset)| inauspicious_periods(sunrise, sunset, weekday(day, month, year)));
+
+    // Calculate the auspicious midday muhurta
+    let abhijit = sun_times
+        .and_then(|(sunrise, sunset)| abhijit_muhurta(sunrise, sunset, weekday(day, month, year)));
+
+    // Calculate the Choghadiyas, whose night runs until the next day's sunrise
+    let next_sunrise = sunrise_sunset(day + 1, month, year, request.location.latitude, request.location.longitude, zone_hour)
+        .map(|(sunrise, _)| sunrise);
+    let choghadiyas = sun_times.zip(next_sunrise).map(|((sunrise, sunset), next_sunrise)| {
+        choghadiya(sunrise, sunset, next_sunrise, weekday(day, month, year))
+            .into_iter()
+            .map(|slot| Choghadiya {
+                name: slot.name.to_string(),
+                auspicious: slot.auspicious,
+                window: TimeWindow::from_hours((slot.start, slot.end)),
+            })
+            .collect()
+    });
+
+    // Compare the Tithis of this sunrise and the next for one skipped or repeated
+    let sunrise_tithis = sun_times
+        .zip(next_sunrise)
+        .map(|((sunrise, _), next_sunrise)| tithi_sunrises(day, month, year, sunrise, next_sunrise, zone_hour));
+
+    // Find the Hora, which before sunrise still belongs to the previous day's night
+    let hora = if before_sunrise {
+        sunrise_sunset(day - 1, month, year, request.location.latitude, request.location.longitude, zone_hour)
+            .zip(sun_times)
+            .and_then(|((sunrise, sunset), (next_sunrise, _))| {
+                current_hora(sunrise, sunset, next_sunrise, weekday(day - 1, month, year), hour + 24.0)
+            })
+    } else {
+        sun_times.zip(next_sunrise).and_then(|((sunrise, sunset), next_sunrise)| {
+            current_hora(sunrise, sunset, next_sunrise, weekday(day, month, year), hour)
+        })
+    }
+    .map(|slot| Hora {
+        lord: slot.lord.to_string(),
+        window: TimeWindow::from_hours((slot.start, slot.end)),
+    });
+
+    // Flag the observances of the Tithi, by the Vaara in effect
+    let tithi_in_paksha = (panchang_data.current_tithi_number as usize - 1) % 15 + 1;
+    let festivals = special_days(tithi_in_paksha, &panchang_data.current_paksha, weekday(vaara_day, month, year));
+
+    // Detect Panchaka from the Moon's Nakshatra, named by the Vaara in effect
+    let moon_nakshatra = panchang_data.current_nakshatra_number as usize - 1;
+    let panchaka = panchaka(moon_nakshatra, panchang_data.current_nakshatra_pada, weekday(vaara_day, month, year))
+        .map(str::to_string);
+
+    // Place Varjyam and Amrita Kaal within the current Nakshatra, scaled to its length
+    let portions = nakshatra_bounds(day, month, year, hour, zone_hour, ayanamsa).map(|(start, end)| {
+        nakshatra_portions(start.timestamp_millis() as f64, end.timestamp_millis() as f64, moon_nakshatra)
+    });
+    let instant_window = |(start, end): (f64, f64)| {
+        let at = |millis: f64| DateTime::from_timestamp_millis(millis.round() as i64).expect("within a day of the Nakshatra");
+        InstantWindow { start: format_instant(at(start), offset), end: format_instant(at(end), offset) }
+    };
+
+    // Find the Bhadra overlapping the local date, and whether its middle is by day
+    let bhadra = bhadra_on(day, month, year, zone_hour).map(|(half_tithi, (start, end), (tithi_start, tithi_end))| {
+        let classified = bhadra(tithi_start.timestamp_millis() as f64, tithi_end.timestamp_millis() as f64, half_tithi);
+        let middle = (start + (end - start) / 2).with_timezone(&offset);
+        let middle_hour = middle.hour() as f64 + middle.minute() as f64 / 60.0 + middle.second() as f64 / 3600.0;
+        let by_day = sunrise_sunset(middle.day() as i32, middle.month() as i32, middle.year(), request.location.latitude, request.location.longitude, zone_hour)
+            .map(|(sunrise, sunset)| (sunrise..sunset).contains(&middle_hour));
+        BhadraWindow {
+            start: format_instant(start, offset),
+            end: format_instant(end, offset),
+            paksha: classified.paksha.to_string(),
+            form: classified.form.to_string(),
+            face: instant_window(classified.face),
+            tail: instant_window(classified.tail),
+            avoid: classified.avoid.to_string(),
+            kind: String::from(if classified.day_bhadra { "day" } else { "night" }),
+            by_day,
+            harmless: by_day.map(|by_day| classified.harmless(by_day)),
+        }
+    });
+
+    // Look up the direction to avoid, which also follows the Vaara in effect
+    let shool = disha_shool(weekday(vaara_day, month, year));
+    let disha_shool = DishaShoolStatus { direction: shool.direction.to_string(), remedy: shool.remedy.to_string() };
+
+    // Calculate the day's strength relative to the birth details
+    let tarabala = janma_nakshatra.map(|janma| {
+        let bala = tarabala(moon_nakshatra, janma);
+        TarabalaStatus { count: bala.count, tara: bala.tara.to_string(), good: bala.favourable }
+    });
+    let chandrabala = janma_rashi.map(|janma| {
+        let bala = chandrabala(panchang_data.current_chandra_rashi_number as usize - 1, janma);
+        ChandrabalaStatus { position: bala.position, good: bala.favourable }
+    });
+
+    // Sample the limbs at the requested times of the local date
+    let midnight = date.and_time(NaiveTime::MIN).and_utc() - Duration::seconds(offset.local_minus_utc() as i64);
+    let samples = request.samples.as_ref().map(|samples| {
+        samples
+            .hours(sun_times)
+            .into_iter()
+            .map(|hours| {
+                let instant = midnight + Duration::milliseconds((hours * 3_600_000.0).round() as i64);
+                PanchangSample { time: format_instant(instant, offset), panchang: limbs_at(instant, request) }
+            })
+            .collect()
+    });
+
+    // Name the Masa in the requested reckoning
+    let masa = match request.month_system {
+        MonthSystem::Amanta => panchang_data.current_masa.clone(),
+        MonthSystem::Purnimanta => panchang_data.current_purnimanta_masa.clone(),
+    };
+
+    // Name the Nakshatra in the requested system, Tarabala above counts in 27
+    let nakshatra = match request.nakshatra_system {
+        NakshatraSystem::TwentySeven => panchang_data.current_nakshatra.clone(),
+        NakshatraSystem::TwentyEight => panchang_data.current_nakshatra_28.clone(),
+    };
 
     // Prepare response
-    Ok(PanchangResponse {
-        tithi: panchang_data.current_tithi,
-        paksha: panchang_data.current_paksha,
-        nakshatra: panchang_data.current_nakshatra,
-        yoga: panchang_data.current_yoga,
-        karana: panchang_data.current_karana,
-        rashi: panchang_data.current_rashi,
+    let response = PanchangResponse {
+        vaara: Vaara {
+            sanskrit: vaara_sanskrit,
+            english: vaara_english,
+        },
+        kali_ahargana: kali_ahargana(vaara_day, month, year),
+        tithi_start: tithi_times.map(|(start, _)| format_instant(start, offset)),
+        tithi_end: tithi_times.map(|(_, end)| format_instant(end, offset)),
+        tithi_status: sunrise_tithis.map(|tithis| tithis.status),
+        sunrise_tithis: sunrise_tithis.map(|tithis| SunriseTithis {
+            at_sunrise: tithis.at_sunrise.to_string(),
+            at_next_sunrise: tithis.at_next_sunrise.to_string(),
+            skipped: tithis.skipped.map(str::to_string),
+        }),
+        yoga_start: yoga_times.map(|(start, _)| format_instant(start, offset)),
+        yoga_end: yoga_times.map(|(_, end)| format_instant(end, offset)),
+        critical_yogas,
+        bhadra,
+        masa,
+        rashi_end: rashi_transit.as_ref().map(|(_, end)| format_instant(*end, offset)),
+        next_rashi: rashi_transit.map(|(rashi, _)| rashi),
+        sankranti,
+        lagna: Some(lagna),
+        nakshatra,
+        sunrise: sun_times.map(|(sunrise, _)| format_hours(sunrise)),
+        sunset: sun_times.map(|(_, sunset)| format_hours(sunset)),
+        moonrise: moonrise.map(format_hours),
+        moonset: moonset.map(format_hours),
+        rahu_kaal: periods.map(|periods| TimeWindow::from_hours(periods.rahu_kaal)),
+        yamaganda: periods.map(|periods| TimeWindow::from_hours(periods.yamaganda)),
+        gulika: periods.map(|periods| TimeWindow::from_hours(periods.gulika)),
+        festivals,
+        panchaka,
+        disha_shool,
+        tarabala,
+        chandrabala,
+        abhijit_muhurta: abhijit.map(TimeWindow::from_hours),
+        varjyam: portions.map(|portions| instant_window(portions.varjyam)),
+        amrita_kaal: portions.map(|portions| instant_window(portions.amrita_kaal)),
+        hora,
+        choghadiya: choghadiyas,
+        accuracy_warning: accuracy_warning(year),
+        sun_longitude,
+        moon_longitude,
+        samples,
+        meta,
+        ..PanchangResponse::from(panchang_data)
+    };
+
+    response.in_script(request.script)
+}
+
+/// Calculates the limbs alone at an instant, in the request's Ayanamsa, reckonings
+/// and script
+fn limbs_at(instant: DateTime<Utc>, request: &ValidatedRequest) -> PanchangResponse {
+    let panchanga = calculate_panchanga_at(instant, request.ayanamsa);
+    let masa = match request.month_system {
+        MonthSystem::Amanta => panchanga.current_masa.clone(),
+        MonthSystem::Purnimanta => panchanga.current_purnimanta_masa.clone(),
+    };
+    let nakshatra = match request.nakshatra_system {
+        NakshatraSystem::TwentySeven => panchanga.current_nakshatra.clone(),
+        NakshatraSystem::TwentyEight => panchanga.current_nakshatra_28.clone(),
+    };
+    PanchangResponse { masa, nakshatra, ..PanchangResponse::from(panchanga) }.in_script(request.script)
+}
+
+/// Calculates the Panchang for the instant `now` at a location, filling in the
+/// parts of the location the request leaves out from `default`
+pub async fn calculate_panchang_now(
+    data: &PanchangNowRequest,
+    default: &Location,
+    now: DateTime<Utc>,
+) -> Result<PanchangResponse, PanchangError> {
+    let location = Location {
+        latitude: data.lat.unwrap_or(default.latitude),
+        longitude: data.lon.unwrap_or(default.longitude),
+        timezone: None,
+    };
+    location.validate()?;
+    let timezone = data.timezone.as_deref().or(default.timezone.as_deref()).ok_or_else(missing_zone)?;
+    let tz = parse_timezone_name(timezone)?;
+
+    // Pin the zone to the offset in effect now, so a DST change cannot shift it
+    let local = now.with_timezone(&tz);
+    let offset_minutes = local.offset().fix().local_minus_utc() / 60;
+    let sign = if offset_minutes < 0 { '-' } else { '+' };
+
+    let given = [("latitude", data.lat.is_some()), ("longitude", data.lon.is_some()), ("timezone", data.timezone.is_some())];
+    let defaulted = given.into_iter().filter(|(_, given)| !given).map(|(part, _)| part.to_string()).collect();
+
+    let request = PanchangRequest {
+        date: Some(local.format("%d/%m/%Y").to_string()),
+        time: Some(local.format("%H:%M").to_string()),
+        zone: Some(format!("{}{:02}:{:02}", sign, offset_minutes.abs() / 60, offset_minutes.abs() % 60)),
+        location: location.into(),
+        options: data.options.clone(),
+        defaulted,
+        ..PanchangRequest::default()
+    };
+    calculate_panchang(&request).await
+}
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Serialize)]
+pub struct PanchangDay {
+    /// Date the Panchang was calculated for, in DD/MM/YYYY format
+    date: String,
+    #[serde(flatten)]
+    panchang: PanchangResponse,
+}
+
+impl PanchangDay {
+    /// Adds the field labels in a language to the day's Panchang
+    pub fn with_labels(self, language: Language) -> Self {
+        PanchangDay { panchang: self.panchang.with_labels(language), ..self }
+    }
+}
+
+/// Panchang in the version 1 response schema, the six limbs the API first served
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Serialize)]
+pub struct PanchangResponseV1 {
+    /// Current Tithi (Lunar Day)
+    tithi: String,
+    /// Name of the current Paksha (Lunar Phase)
+    paksha: String,
+    /// Current Nakshatra (Lunar Mansion) in the requested system
+    nakshatra: String,
+    /// Current Yoga (Luni-Solar Day)
+    yoga: String,
+    /// Current Karana (Half Lunar Day)
+    karana: String,
+    /// Current Rashi (Zodiac Sign) of the Moon
+    rashi: String,
+}
+
+impl V1Shape for PanchangResponse {
+    type V1 = PanchangResponseV1;
+
+    fn into_v1(self) -> PanchangResponseV1 {
+        PanchangResponseV1 {
+            tithi: self.tithi,
+            paksha: self.paksha.name,
+            nakshatra: self.nakshatra,
+            yoga: self.yoga,
+            karana: self.karana,
+            rashi: self.rashi,
+        }
+    }
+}
+
+/// Day of a range in the version 1 response schema
+#[derive(Debug, Serialize)]
+pub struct PanchangDayV1 {
+    /// Date the Panchang was calculated for, in DD/MM/YYYY format
+    date: String,
+    #[serde(flatten)]
+    panchang: PanchangResponseV1,
+}
+
+impl V1Shape for PanchangDay {
+    type V1 = PanchangDayV1;
+
+    fn into_v1(self) -> PanchangDayV1 {
+        PanchangDayV1 { date: self.date, panchang: self.panchang.into_v1() }
+    }
+}
+
+pub async fn calculate_panchang_range(data: &PanchangRangeRequest) -> Result<Vec<PanchangDay>, PanchangError> {
+    let mut panchang_days = Vec::new();
+    for request in data.days()? {
+        panchang_days.push(calculate_panchang_day(&request).await?);
+    }
+    Ok(panchang_days)
+}
+
+/// Calculates the Panchang of a day of a range, from its request among
+/// [`PanchangRangeRequest::days`]
+pub async fn calculate_panchang_day(request: &PanchangRequest) -> Result<PanchangDay, PanchangError> {
+    let panchang = calculate_panchang(request).await?;
+    Ok(PanchangDay { date: request.date.clone().unwrap_or_default(), panchang })
+}
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Deserialize)]
+pub struct NextTithiRequest {
+    /// First date searched, in DD/MM/YYYY format
+    #[cfg_attr(feature = "openapi", schema(example = "20/08/2023"))]
+    from_date: String,
+    /// Calendar `from_date` is given in, which also writes the date found
+    #[serde(flatten)]
+    calendar: RequestCalendar,
+    /// Name of the Tithi searched for (e.g. Ekadashi, Poornima)
+    #[cfg_attr(feature = "openapi", schema(example = "Ekadashi"))]
+    tithi: String,
+    /// Paksha of the Tithi, Shukla or Krishna; either Paksha matches when absent
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(example = "Shukla"))]
+    paksha: Option<String>,
+    /// Time in HH:MM 24-hour format, used on days when the Sun does not rise
+    #[cfg_attr(feature = "openapi", schema(example = "06:00"))]
+    time: String,
+    /// Timezone offset from GMT in [+/-]HH:MM, [+/-]HH or Z format, used when `timezone` is absent
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(example = "+05:30"))]
+    zone: Option<String>,
+    /// Observer location, with the IANA timezone name resolved separately for each
+    /// day searched
+    #[serde(flatten)]
+    location: RequestLocation,
+    /// Years the deployment calculates, set by the server rather than the request
+    #[serde(skip)]
+    supported_years: Option<RangeInclusive<i32>>,
+    /// Parts of the location filled in from the server's default, set by the server
+    /// rather than the request
+    #[serde(skip)]
+    defaulted: Vec<String>,
+}
+
+impl NextTithiRequest {
+    /// Rejects searches starting in a year outside `years`
+    pub fn with_supported_years(self, years: RangeInclusive<i32>) -> Self {
+        NextTithiRequest { supported_years: Some(years), ..self }
+    }
+
+    /// Fills in the latitude and longitude the request leaves out from `default`, and
+    /// its timezone when the request gives neither a timezone nor a `zone`
+    pub fn with_default_location(mut self, default: &Location) -> Self {
+        self.defaulted = self.location.fill_in(self.zone.as_deref(), default);
+        self
+    }
+}
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Serialize)]
+pub struct TithiOccurrence {
+    /// Date on whose sunrise the Tithi prevails, in DD/MM/YYYY format
+    date: String,
+    /// Name of the Tithi
+    tithi: String,
+    /// Paksha of the Tithi
+    paksha: String,
+    /// Local sunrise in HH:MM format, absent when the Sun does not rise and `time` was used
+    sunrise: Option<String>,
+    /// Start of the Tithi as an ISO-8601 datetime in the requested zone
+    tithi_start: Option<String>,
+    /// End of the Tithi as an ISO-8601 datetime in the requested zone
+    tithi_end: Option<String>,
+    /// Parts of the location the request left out, filled in from the server's default
+    defaulted: Vec<String>,
+    /// Display label of each field in the requested language, present only when a
+    /// language is requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    labels: Option<BTreeMap<String, String>>,
+}
+
+impl TithiOccurrence {
+    /// Fields of the occurrence that have a label
+    const LABELLED: [&str; 5] = ["tithi", "paksha", "sunrise", "tithi_start", "tithi_end"];
+
+    /// Adds the labels of the occurrence's fields in a language
+    pub fn with_labels(self, language: Language) -> Self {
+        let mut labels = field_labels(language);
+        labels.retain(|field, _| Self::LABELLED.contains(&field.as_str()));
+        TithiOccurrence { labels: Some(labels), ..self }
+    }
+}
+
+/// Tithi occurrence in the version 1 response schema, without the defaulted parts
+/// of the location and the labels added since
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Serialize)]
+pub struct TithiOccurrenceV1 {
+    /// Date on whose sunrise the Tithi prevails, in DD/MM/YYYY format
+    date: String,
+    /// Name of the Tithi
+    tithi: String,
+    /// Paksha of the Tithi
+    paksha: String,
+    /// Local sunrise in HH:MM format, absent when the Sun does not rise and `time` was used
+    sunrise: Option<String>,
+    /// Start of the Tithi as an ISO-8601 datetime in the requested zone
+    tithi_start: Option<String>,
+    /// End of the Tithi as an ISO-8601 datetime in the requested zone
+    tithi_end: Option<String>,
+}
+
+impl V1Shape for TithiOccurrence {
+    type V1 = TithiOccurrenceV1;
+
+    fn into_v1(self) -> TithiOccurrenceV1 {
+        TithiOccurrenceV1 {
+            date: self.date,
+            tithi: self.tithi,
+            paksha: self.paksha,
+            sunrise: self.sunrise,
+            tithi_start: self.tithi_start,
+            tithi_end: self.tithi_end,
+        }
+    }
+}
+
+/// Finds the first date, from `from_date` on, whose sunrise falls in the requested Tithi
+///
+/// A day belongs to the Tithi prevailing at its sunrise, so a Tithi that begins and
+/// ends between two sunrises (a kshaya Tithi) is not observed that month, and the
+/// search carries on to the next occurrence. At most `MAX_TITHI_SEARCH_DAYS` days
+/// are searched, enough to pass over one skipped occurrence.
+pub async fn calculate_next_tithi(data: &NextTithiRequest) -> Result<TithiOccurrence, PanchangError> {
+    let location = data.location.resolve()?;
+
+    // Work out which of the 30 Tithis are acceptable
+    let mut numbers = tithi_numbers(&data.tithi);
+    if numbers.is_empty() {
+        return Err(PanchangError::InvalidTithi(format!("unknown Tithi '{}'", data.tithi)));
+    }
+    if let Some(paksha) = &data.paksha {
+        let shukla = match paksha.trim().to_ascii_lowercase().as_str() {
+            "shukla" => true,
+            "krishna" => false,
+            _ => return Err(PanchangError::InvalidTithi(format!("unknown Paksha '{}'", paksha))),
+        };
+        numbers.retain(|number| (*number <= 15) == shukla);
+        if numbers.is_empty() {
+            return Err(PanchangError::InvalidTithi(format!(
+                "{} does not fall in {} Paksha", data.tithi, paksha
+            )));
+        }
+    }
+
+    let (calendar, cutover) = data.calendar.resolve()?;
+    let start = to_naive_date(calendar.parse_date(&data.from_date, cutover)?);
+    check_supported_year(start.year(), data.supported_years.as_ref())?;
+    let (hours, minutes) = parse_clock_time(&data.time)?;
+    let time = hours + minutes as f64 / 60.0;
+
+    for date in start.iter_days().take(MAX_TITHI_SEARCH_DAYS) {
+        let (day, month, year) = (date.day() as i32, date.month() as i32, date.year());
+        let zone_hour = match (&location.timezone, &data.zone) {
+            (Some(timezone), _) => resolve_timezone(timezone, (day, month, year), (hours, minutes))?,
+            (None, Some(zone)) => parse_tz_offset(zone)?,
+            (None, None) => return Err(missing_zone()),
+        };
+
+        // Evaluate the Tithi at sunrise, or at `time` where there is none
+        let sunrise = sunrise_sunset(day, month, year, location.latitude, location.longitude, zone_hour)
+            .map(|(sunrise, _)| sunrise);
+        let hour = sunrise.unwrap_or(time);
+        let panchanga = calculate_panchanga(day, month, year, hour, zone_hour, Ayanamsa::default());
+        if !numbers.contains(&panchanga.current_tithi_number) {
+            continue;
+        }
+
+        let offset = FixedOffset::east_opt((zone_hour * 3600.0).round() as i32)
+            .ok_or_else(|| PanchangError::InvalidTimezone(format!("offset {} hours is out of range", zone_hour)))?;
+        let tithi_times = tithi_bounds(day, month, year, hour, zone_hour);
+        return Ok(TithiOccurrence {
+            date: calendar.format_date((day, month, year), cutover),
+            tithi: panchanga.current_tithi,
+            paksha: panchanga.current_paksha,
+            sunrise: sunrise.map(format_hours),
+            tithi_start: tithi_times.map(|(start, _)| format_instant(start, offset)),
+            tithi_end: tithi_times.map(|(_, end)| format_instant(end, offset)),
+            defaulted: data.defaulted.clone(),
+            labels: None,
+        });
+    }
+
+    Err(PanchangError::TithiNotFound(format!(
+        "{} does not prevail at sunrise within {} days of {}", data.tithi, MAX_TITHI_SEARCH_DAYS, data.from_date
+    )))
+}
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Deserialize)]
+pub struct PanchangCompareRequest {
+    /// Date in DD/MM/YYYY format
+    #[cfg_attr(feature = "openapi", schema(example = "15/08/2023"))]
+    date: String,
+    /// Time in HH:MM 24-hour format
+    #[cfg_attr(feature = "openapi", schema(example = "12:45"))]
+    time: String,
+    /// Calendar `date` is given in, with the first date of the Gregorian calendar
+    #[serde(flatten)]
+    calendar: RequestCalendar,
+    /// Timezone offset from GMT in [+/-]HH:MM, [+/-]HH or Z format, used when `timezone` is absent
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(example = "+05:30"))]
+    zone: Option<String>,
+    /// Observer location, with the IANA timezone name that takes precedence over `zone`
+    #[serde(flatten)]
+    location: RequestLocation,
+    /// Ayanamsa systems to compare, at least two and each at most once
+    #[cfg_attr(feature = "openapi", schema(example = json!(["lahiri", "raman"])))]
+    ayanamsas: Vec<Ayanamsa>,
+    /// Years the deployment calculates, set by the server rather than the request
+    #[serde(skip)]
+    supported_years: Option<RangeInclusive<i32>>,
+    /// Parts of the location filled in from the server's default, set by the server
+    /// rather than the request
+    #[serde(skip)]
+    defaulted: Vec<String>,
+}
+
+impl PanchangCompareRequest {
+    /// Rejects dates whose year is outside `years`
+    pub fn with_supported_years(self, years: RangeInclusive<i32>) -> Self {
+        PanchangCompareRequest { supported_years: Some(years), ..self }
+    }
+
+    /// Fills in the latitude and longitude the request leaves out from `default`, and
+    /// its timezone when the request gives neither a timezone nor a `zone`
+    pub fn with_default_location(mut self, default: &Location) -> Self {
+        self.defaulted = self.location.fill_in(self.zone.as_deref(), default);
+        self
+    }
+}
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Serialize)]
+pub struct AyanamsaPanchang {
+    /// Ayanamsa system the Panchang was calculated in
+    ayanamsa: Ayanamsa,
+    /// Value of the Ayanamsa at the instant in degrees, subtracted from tropical
+    /// longitudes to make them sidereal
+    ayanamsa_degrees: f64,
+    #[serde(flatten)]
+    panchang: PanchangResponse,
+}
+
+/// Limbs that depend on the Ayanamsa, and so may differ between systems
+#[derive(Debug, Clone, Copy)]
+enum SiderealLimb {
+    Nakshatra,
+    Yoga,
+    Rashi,
+    Lagna,
+}
+
+impl SiderealLimb {
+    /// Every limb, in the order the differences are listed
+    const ALL: [SiderealLimb; 4] = [SiderealLimb::Nakshatra, SiderealLimb::Yoga, SiderealLimb::Rashi, SiderealLimb::Lagna];
+
+    /// Response field of the limb
+    fn field(self) -> &'static str {
+        match self {
+            SiderealLimb::Nakshatra => "nakshatra",
+            SiderealLimb::Yoga => "yoga",
+            SiderealLimb::Rashi => "rashi",
+            SiderealLimb::Lagna => "lagna",
+        }
+    }
+
+    /// Name of the limb in a response, the Lagna by its Rashi
+    fn of(self, panchang: &PanchangResponse) -> String {
+        match self {
+            SiderealLimb::Nakshatra => panchang.nakshatra.clone(),
+            SiderealLimb::Yoga => panchang.yoga.clone(),
+            SiderealLimb::Rashi => panchang.rashi.clone(),
+            SiderealLimb::Lagna => panchang.lagna.as_ref().map(|lagna| lagna.rashi.clone()).unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Serialize)]
+pub struct LimbDifference {
+    /// Response field of the limb that differs (`nakshatra`, `yoga`, `rashi` or `lagna`)
+    limb: String,
+    /// Value of the limb in each system, in the order of `systems`
+    values: Vec<String>,
+}
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Serialize)]
+pub struct PanchangComparison {
+    /// Panchang in each requested system, in the order requested
+    systems: Vec<AyanamsaPanchang>,
+    /// Limbs whose value is not the same in every system, empty when they all agree
+    differences: Vec<LimbDifference>,
+    /// Parts of the location the request left out, filled in from the server's default
+    defaulted: Vec<String>,
+}
+
+impl PanchangComparison {
+    /// Adds the field labels in a language to the Panchang of each system
+    pub fn with_labels(self, language: Language) -> Self {
+        let systems = self
+            .systems
+            .into_iter()
+            .map(|system| AyanamsaPanchang { panchang: system.panchang.with_labels(language), ..system })
+            .collect();
+        PanchangComparison { systems, ..self }
+    }
+}
+
+/// Panchang of one Ayanamsa system in the version 1 response schema
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Serialize)]
+pub struct AyanamsaPanchangV1 {
+    /// Ayanamsa system the Panchang was calculated in
+    ayanamsa: Ayanamsa,
+    /// Value of the Ayanamsa at the instant in degrees
+    ayanamsa_degrees: f64,
+    #[serde(flatten)]
+    panchang: PanchangResponseV1,
+}
+
+/// Comparison in the version 1 response schema: each system's six original limbs,
+/// and the differences among them, which leave out the Lagna
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Serialize)]
+pub struct PanchangComparisonV1 {
+    /// Panchang in each requested system, in the order requested
+    systems: Vec<AyanamsaPanchangV1>,
+    /// Limbs whose value is not the same in every system, empty when they all agree
+    differences: Vec<LimbDifference>,
+}
+
+impl V1Shape for PanchangComparison {
+    type V1 = PanchangComparisonV1;
+
+    fn into_v1(self) -> PanchangComparisonV1 {
+        let lagna = SiderealLimb::Lagna.field();
+        PanchangComparisonV1 {
+            systems: self
+                .systems
+                .into_iter()
+                .map(|system| AyanamsaPanchangV1 {
+                    ayanamsa: system.ayanamsa,
+                    ayanamsa_degrees: system.ayanamsa_degrees,
+                    panchang: system.panchang.into_v1(),
+                })
+                .collect(),
+            differences: self.differences.into_iter().filter(|difference| difference.limb != lagna).collect(),
+        }
+    }
+}
+
+/// Calculates the Panchang of an instant in several Ayanamsa systems and lists the
+/// limbs that differ between them
+///
+/// The Tithi and Karana depend only on the distance between the Moon and the Sun,
+/// which every Ayanamsa shifts alike, so only the Nakshatra, Yoga, Rashi and Lagna
+/// can differ.
+pub async fn calculate_panchang_comparison(data: &PanchangCompareRequest) -> Result<PanchangComparison, PanchangError> {
+    data.location.resolve()?;
+
+    if data.ayanamsas.len() < 2 {
+        return Err(PanchangError::InvalidComparison("give at least two ayanamsas".to_string()));
+    }
+    let repeated = (1..data.ayanamsas.len()).any(|index| data.ayanamsas[..index].contains(&data.ayanamsas[index]));
+    if repeated {
+        return Err(PanchangError::InvalidComparison("give each ayanamsa only once".to_string()));
+    }
+
+    let base = PanchangRequest {
+        date: Some(data.date.clone()),
+        time: Some(data.time.clone()),
+        calendar: data.calendar.clone(),
+        zone: data.zone.clone(),
+        location: data.location.clone(),
+        supported_years: data.supported_years.clone(),
+        ..PanchangRequest::default()
+    };
+    let mut systems = Vec::with_capacity(data.ayanamsas.len());
+    for &ayanamsa in &data.ayanamsas {
+        let request = base.clone().map_options(|options| RequestOptions { ayanamsa: Some(ayanamsa), ..options });
+        let validated = ValidatedRequest::try_from(&request)?;
+        let date = validated.date;
+        let zone_hour = validated.offset.local_minus_utc() as f64 / 3600.0;
+        let ayanamsa_degrees =
+            ayanamsa_degrees(date.day() as i32, date.month() as i32, date.year(), validated.hour, zone_hour, ayanamsa);
+        systems.push(AyanamsaPanchang { ayanamsa, ayanamsa_degrees, panchang: calculate_panchang_validated(&validated) });
+    }
+
+    let differences = SiderealLimb::ALL
+        .into_iter()
+        .filter_map(|limb| {
+            let values: Vec<String> = systems.iter().map(|system| limb.of(&system.panchang)).collect();
+            values
+                .iter()
+                .any(|other| *other != values[0])
+                .then(|| LimbDifference { limb: limb.field().to_string(), values })
+        })
+        .collect();
+
+    Ok(PanchangComparison { systems, differences, defaulted: data.defaulted.clone() })
+}
+
+/// Resolves an IANA timezone name to its UTC offset, in decimal hours, at a local date and time
+///
+/// The offset follows the zone's rules for that date, so historical DST changes are
+/// honored. An ambiguous time at the end of DST uses the earlier (DST) offset, and a
+/// time skipped at the start of DST uses the offset in effect just before the gap.
+fn resolve_timezone(
+    timezone: &str,
+    (day, month, year): (i32, i32, i32),
+    (hours, minutes): (f64, i32),
+) -> Result<f64, PanchangError> {
+    let tz = parse_timezone_name(timezone)?;
+
+    let time = NaiveTime::from_hms_opt(hours as u32, minutes as u32, 0)
+        .ok_or_else(|| PanchangError::InvalidTimeFormat("Time is out of range".to_string()))?;
+    let local = to_naive_date((day, month, year)).and_time(time);
+
+    // DST gaps are at most a few hours long, so stepping back three hours always
+    // lands before the gap
+    let resolved = tz
+        .from_local_datetime(&local)
+        .earliest()
+        .or_else(|| tz.from_local_datetime(&(local - Duration::hours(3))).earliest())
+        .ok_or_else(|| PanchangError::InvalidTimezone(format!("cannot resolve local time in '{}'", timezone)))?;
+
+    Ok(resolved.offset().fix().local_minus_utc() as f64 / 3600.0)
+}
+
+/// Resolves a Unix timestamp to its local date, decimal hour and UTC offset
+///
+/// A named timezone takes precedence over a raw offset, as for dates and times, and
+/// is resolved at the instant itself, so there is no DST ambiguity. A local date
+/// outside the years 1 to 9999 is rejected, as it is when given as a date.
+fn local_from_epoch(
+    epoch: i64,
+    timezone: Option<&str>,
+    zone: Option<&str>,
+) -> Result<(i32, i32, i32, f64, f64), PanchangError> {
+    let utc = DateTime::from_timestamp(epoch, 0)
+        .ok_or_else(|| PanchangError::InvalidInstant(format!("epoch {} is out of range", epoch)))?;
+
+    let offset_seconds = match (timezone, zone) {
+        (Some(timezone), _) => utc.with_timezone(&parse_timezone_name(timezone)?).offset().fix().local_minus_utc(),
+        (None, Some(zone)) => (parse_tz_offset(zone)? * 3600.0).round() as i32,
+        (None, None) => return Err(missing_zone()),
+    };
+
+    let local = utc
+        .naive_utc()
+        .checked_add_signed(Duration::seconds(offset_seconds as i64))
+        .ok_or_else(|| PanchangError::InvalidInstant(format!("epoch {} is out of range", epoch)))?;
+    // The same years a date may be given in
+    if !(1..=9999).contains(&local.year()) {
+        return Err(PanchangError::DateOutOfRange(format!(
+            "Year must be between 1 and 9999, got {}",
+            local.year()
+        )));
+    }
+    let hour = local.hour() as f64 + local.minute() as f64 / 60.0 + local.second() as f64 / 3600.0;
+    Ok((local.day() as i32, local.month() as i32, local.year(), hour, offset_seconds as f64 / 3600.0))
+}
+
+/// Parses an IANA timezone name such as Asia/Kolkata
+fn parse_timezone_name(timezone: &str) -> Result<Tz, PanchangError> {
+    timezone
+        .trim()
+        .parse()
+        .map_err(|_| PanchangError::InvalidTimezone(format!("unknown timezone '{}'", timezone)))
+}
+
+/// Rejects a year outside the range the deployment supports, when it sets one
+fn check_supported_year(year: i32, supported: Option<&RangeInclusive<i32>>) -> Result<(), PanchangError> {
+    match supported {
+        Some(years) if !years.contains(&year) => Err(PanchangError::DateOutOfRange(format!(
+            "Year {} is outside the supported range {}-{}",
+            year,
+            years.start(),
+            years.end()
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Warns that the elements of a year outside `ACCURATE_YEARS` may be a step off
+fn accuracy_warning(year: i32) -> Option<String> {
+    (!ACCURATE_YEARS.contains(&year)).then(|| {
+        format!(
+            "Year {} is outside {}-{}, where new moons agree with published times to about a minute; \
+             further out the elements of an instant close to a change may be a step off",
+            year,
+            ACCURATE_YEARS.start(),
+            ACCURATE_YEARS.end()
+        )
     })
-}
\ No newline at end of file
+}
+
+/// The error for a request that gives neither a named timezone nor an offset
+fn missing_zone() -> PanchangError {
+    PanchangError::InvalidTimezone("either timezone or zone must be given".to_string())
+}
+
+/// Converts a `parse_date` result, which is already calendar-validated, to a `NaiveDate`
+fn to_naive_date((day, month, year): (i32, i32, i32)) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32).expect("parse_date only returns valid dates")
+}
+
+/// Formats a UTC instant as an ISO-8601 datetime in the given timezone
+fn format_instant(instant: DateTime<Utc>, offset: FixedOffset) -> String {
+    instant.with_timezone(&offset).to_rfc3339_opts(SecondsFormat::Secs, false)
+}
+
+/// Formats decimal hours as a wall-clock HH:MM string, rounded to the minute
+fn format_hours(hours: f64) -> String {
+    let minutes = ((hours * 60.0).round() as i64).rem_euclid(24 * 60);
+    format!("{:02}:{:02}", minutes / 60, minutes % 60)
+}
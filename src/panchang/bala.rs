@@ -0,0 +1,69 @@
+//! Bala calculations - the day's strength for a person, relative to their birth chart
+//!
+//! Tarabala counts the day's Nakshatra from the janma (birth) Nakshatra and
+//! Chandrabala counts the Moon's Rashi from the janma Rashi. Both depend only on
+//! the positions in the fixed Nakshatra and Rashi sequences.
+
+/// The 9 Taras in counting order from the janma Nakshatra, each with whether it is favourable
+static TARA: [(&str, bool); 9] = [
+    ("Janma", false),
+    ("Sampat", true),
+    ("Vipat", false),
+    ("Kshema", true),
+    ("Pratyari", false),
+    ("Sadhaka", true),
+    ("Vadha", false),
+    ("Mitra", true),
+    ("Parama Mitra", true),
+];
+
+/// Positions (1-12) of the Moon's Rashi from the janma Rashi that give Chandrabala
+static FAVOURABLE_CHANDRA_POSITIONS: [usize; 6] = [1, 3, 6, 7, 10, 11];
+
+/// Tarabala of the day for a janma Nakshatra
+#[derive(Debug, Clone, Copy)]
+pub struct Tarabala {
+    /// Position (1-27) of the day's Nakshatra counted from the janma Nakshatra
+    pub count: usize,
+    /// Name of the Tara, which repeats every 9 Nakshatras
+    pub tara: &'static str,
+    /// Whether the Tara is favourable
+    pub favourable: bool,
+}
+
+/// Chandrabala of the day for a janma Rashi
+#[derive(Debug, Clone, Copy)]
+pub struct Chandrabala {
+    /// Position (1-12) of the Moon's Rashi counted from the janma Rashi
+    pub position: usize,
+    /// Whether the position is favourable
+    pub favourable: bool,
+}
+
+/// Calculates Tarabala from the day's and the janma Nakshatra
+///
+/// # Arguments
+/// * `nakshatra_index` - Index (0-26) of the day's Nakshatra
+/// * `janma_nakshatra_index` - Index (0-26) of the janma Nakshatra
+pub fn tarabala(nakshatra_index: usize, janma_nakshatra_index: usize) -> Tarabala {
+    let count = (nakshatra_index + 27 - janma_nakshatra_index % 27) % 27 + 1;
+    let (tara, favourable) = TARA[(count - 1) % 9];
+    Tarabala {
+        count,
+        tara,
+        favourable,
+    }
+}
+
+/// Calculates Chandrabala from the Moon's and the janma Rashi
+///
+/// # Arguments
+/// * `rashi_index` - Index (0-11) of the Moon's Rashi
+/// * `janma_rashi_index` - Index (0-11) of the janma Rashi
+pub fn chandrabala(rashi_index: usize, janma_rashi_index: usize) -> Chandrabala {
+    let position = (rashi_index + 12 - janma_rashi_index % 12) % 12 + 1;
+    Chandrabala {
+        position,
+        favourable: FAVOURABLE_CHANDRA_POSITIONS.contains(&position),
+    }
+}
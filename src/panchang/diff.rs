@@ -0,0 +1,64 @@
+//! Field-by-field comparison of two Panchangas
+//!
+//! Fields are named as in [`Panchanga`] without their `current_` prefix, so a
+//! difference reads as `nakshatra` or `tithi_number`.
+
+use crate::panchang::Panchanga;
+
+/// Reads a Panchanga field as text
+type FieldValue = fn(&Panchanga) -> String;
+
+/// Name of each Panchanga field with its value
+static FIELDS: [(&str, FieldValue); 27] = [
+    ("tithi", |p| p.current_tithi.clone()),
+    ("tithi_number", |p| p.current_tithi_number.to_string()),
+    ("paksha", |p| p.current_paksha.clone()),
+    ("nakshatra", |p| p.current_nakshatra.clone()),
+    ("nakshatra_number", |p| p.current_nakshatra_number.to_string()),
+    ("nakshatra_28", |p| p.current_nakshatra_28.clone()),
+    ("nakshatra_pada", |p| p.current_nakshatra_pada.to_string()),
+    ("nakshatra_lord", |p| p.current_nakshatra_lord.clone()),
+    ("yoga", |p| p.current_yoga.clone()),
+    ("karana", |p| p.current_karana.clone()),
+    ("chandra_rashi", |p| p.current_chandra_rashi.clone()),
+    ("chandra_rashi_number", |p| p.current_chandra_rashi_number.to_string()),
+    ("rashi_lord", |p| p.current_rashi_lord.clone()),
+    ("navamsa_rashi", |p| p.current_navamsa_rashi.clone()),
+    ("surya_rashi", |p| p.current_surya_rashi.clone()),
+    ("surya_nakshatra", |p| p.current_surya_nakshatra.clone()),
+    ("surya_nakshatra_pada", |p| {
+        p.current_surya_nakshatra_pada.to_string()
+    }),
+    ("moon_illumination", |p| p.moon_illumination.to_string()),
+    ("moon_phase_angle", |p| p.moon_phase_angle.to_string()),
+    ("moon_phase", |p| p.moon_phase.clone()),
+    ("masa", |p| p.current_masa.clone()),
+    ("purnimanta_masa", |p| p.current_purnimanta_masa.clone()),
+    ("is_adhika_masa", |p| p.is_adhika_masa.to_string()),
+    ("samvatsara", |p| p.current_samvatsara.clone()),
+    ("kali_year", |p| p.current_kali_year.to_string()),
+    ("ayana", |p| p.current_ayana.clone()),
+    ("ritu", |p| p.current_ritu.clone()),
+];
+
+impl Panchanga {
+    /// Value of a field named as in [`panchanga_diff`], as text
+    pub fn field(&self, name: &str) -> Option<String> {
+        FIELDS
+            .iter()
+            .find(|(field, _)| *field == name)
+            .map(|(_, value)| value(self))
+    }
+}
+
+/// Names the fields whose values differ between two Panchangas: the limbs with
+/// their details, then the Rashis, the Moon's phase and the calendar
+///
+/// Nothing is rounded: moon illuminations a rounding error apart differ.
+pub fn panchanga_diff(a: &Panchanga, b: &Panchanga) -> Vec<&'static str> {
+    FIELDS
+        .iter()
+        .filter(|(_, value)| value(a) != value(b))
+        .map(|(field, _)| *field)
+        .collect()
+}
@@ -0,0 +1,111 @@
+//! Text renderings of a Panchanga, for printing it from a library or a command line
+//!
+//! Both renderings list the same rows: the five limbs with the Paksha, then the
+//! month, the year and the Moon's phase.
+
+use std::fmt;
+
+use crate::panchang::Panchanga;
+
+impl Panchanga {
+    /// Label and value of each row of the text renderings
+    fn rows(&self) -> Vec<(&'static str, String)> {
+        let adhika = if self.is_adhika_masa { " (Adhika)" } else { "" };
+        vec![
+            (
+                "Tithi",
+                format!(
+                    "{} ({} of 30)",
+                    self.current_tithi, self.current_tithi_number
+                ),
+            ),
+            ("Paksha", self.current_paksha.clone()),
+            (
+                "Nakshatra",
+                format!(
+                    "{}, pada {}, lord {}",
+                    self.current_nakshatra,
+                    self.current_nakshatra_pada,
+                    self.current_nakshatra_lord
+                ),
+            ),
+            ("Yoga", self.current_yoga.clone()),
+            ("Karana", self.current_karana.clone()),
+            (
+                "Rashi",
+                format!(
+                    "{}, lord {}",
+                    self.current_chandra_rashi, self.current_rashi_lord
+                ),
+            ),
+            ("Surya Rashi", self.current_surya_rashi.clone()),
+            ("Masa", format!("{}{}", self.current_masa, adhika)),
+            (
+                "Purnimanta Masa",
+                format!("{}{}", self.current_purnimanta_masa, adhika),
+            ),
+            ("Samvatsara", self.current_samvatsara.clone()),
+            ("Ayana", self.current_ayana.clone()),
+            ("Ritu", self.current_ritu.clone()),
+            (
+                "Moon",
+                format!(
+                    "{}, {:.1}% illuminated",
+                    self.moon_phase,
+                    self.moon_illumination * 100.0
+                ),
+            ),
+        ]
+    }
+
+    /// Renders the Panchanga as a boxed ASCII table with an element and a value
+    /// column, one row per element
+    pub fn to_table(&self) -> String {
+        let rows = self.rows();
+        let label_width = rows
+            .iter()
+            .map(|(label, _)| label.len())
+            .max()
+            .unwrap_or(0)
+            .max("Element".len());
+        let value_width = rows
+            .iter()
+            .map(|(_, value)| value.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("Value".len());
+
+        let border = format!(
+            "+{}+{}+\n",
+            "-".repeat(label_width + 2),
+            "-".repeat(value_width + 2)
+        );
+        let line = |label: &str, value: &str| {
+            format!("| {:<label_width$} | {:<value_width$} |\n", label, value)
+        };
+
+        let mut table = border.clone();
+        table.push_str(&line("Element", "Value"));
+        table.push_str(&border);
+        for (label, value) in &rows {
+            table.push_str(&line(label, value));
+        }
+        table.push_str(&border);
+        table
+    }
+}
+
+impl fmt::Display for Panchanga {
+    /// Writes one line per element, with the values aligned after their labels
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows = self.rows();
+        let width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+        for (index, (label, value)) in rows.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{:<width$}  {}", label, value)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,169 @@
+//! Dosha detection - inauspicious conditions that follow from the Moon's position
+//! and the weekday
+//!
+//! Like the bala calculations, these only depend on positions in the fixed
+//! Nakshatra sequence and on the Vaara, not on any time window. Bhadra is the
+//! exception, whose face and tail are fixed shares of its Tithi's bounds.
+
+/// Index of Dhanishta, the Nakshatra in whose second half Panchaka begins
+const DHANISHTA: usize = 22;
+
+/// Name of Panchaka by weekday, starting from Sunday; Wednesday and Thursday give
+/// Panchaka without a named type
+static PANCHAKA: [&str; 7] = [
+    "Roga Panchaka",
+    "Raja Panchaka",
+    "Agni Panchaka",
+    "Panchaka",
+    "Panchaka",
+    "Chora Panchaka",
+    "Mrityu Panchaka",
+];
+
+/// Ghatis a Tithi is reckoned as, whatever its length
+const TITHI_GHATIS: f64 = 60.0;
+
+/// Praharas (watches) a Tithi is divided into
+const TITHI_PRAHARAS: f64 = 8.0;
+
+/// Ghatis of Bhadra's mukha (face), from the start of its Prahara
+const BHADRA_FACE_GHATIS: f64 = 5.0;
+
+/// Ghatis of Bhadra's puchha (tail), up to the end of its Prahara
+const BHADRA_TAIL_GHATIS: f64 = 3.0;
+
+/// Prahara (1-8) of the Tithi holding Bhadra's face, for each of the month's eight
+/// Bhadras: Shukla Chaturthi, Ashtami, Ekadashi and Purnima, then Krishna Tritiya,
+/// Saptami, Dashami and Chaturdashi
+static BHADRA_FACE_PRAHARA: [usize; 8] = [5, 2, 7, 4, 8, 3, 6, 1];
+
+/// Prahara (1-8) of the Tithi holding Bhadra's tail, in the order of
+/// `BHADRA_FACE_PRAHARA`
+static BHADRA_TAIL_PRAHARA: [usize; 8] = [8, 1, 6, 3, 7, 2, 5, 4];
+
+/// Half-Tithis (0-59) of a month between two Bhadras
+const HALF_TITHIS_PER_BHADRA: usize = 7;
+
+/// Half-Tithis (0-59) of a month from which the Krishna Paksha begins
+const KRISHNA_HALF_TITHIS_START: usize = 30;
+
+/// Disha Shool by weekday, starting from Sunday: the direction in which travel is
+/// avoided and the Parihar, what is taken before setting out if the journey cannot wait
+static DISHA_SHOOL: [(&str, &str); 7] = [
+    ("West", "Ghee or daliya"),
+    ("East", "Milk, or looking into a mirror"),
+    ("North", "Jaggery"),
+    ("North", "Sesame or coriander seeds"),
+    ("South", "Curd"),
+    ("West", "Barley"),
+    ("East", "Ginger or urad dal"),
+];
+
+/// Disha Shool of a weekday
+#[derive(Debug, Clone, Copy)]
+pub struct DishaShool {
+    /// Direction in which travel is inauspicious
+    pub direction: &'static str,
+    /// Parihar (remedy) for a journey that cannot be put off
+    pub remedy: &'static str,
+}
+
+/// Looks up the Disha Shool, the direction inauspicious for travel on a weekday
+///
+/// # Arguments
+/// * `weekday` - Weekday index, 0 for Sunday through 6 for Saturday, here the
+///   Vaara of the day
+///
+/// # Returns
+/// The direction with its Parihar
+pub fn disha_shool(weekday: usize) -> DishaShool {
+    let (direction, remedy) = DISHA_SHOOL[weekday % 7];
+    DishaShool { direction, remedy }
+}
+
+/// Detects Panchaka, the Moon's transit of the last five Nakshatras
+///
+/// Panchaka runs from the second half of Dhanishta through Revati, which is the
+/// Moon's passage through Kumbha and Meena. Its type follows the weekday, here the
+/// Vaara of the day.
+///
+/// # Arguments
+/// * `nakshatra_index` - Index (0-26) of the Moon's Nakshatra
+/// * `pada` - Pada (1-4) of the Nakshatra
+/// * `weekday` - Weekday index, 0 for Sunday through 6 for Saturday
+///
+/// # Returns
+/// The name of the Panchaka (e.g. Mrityu Panchaka), or `None` outside Panchaka
+pub fn panchaka(nakshatra_index: usize, pada: u8, weekday: usize) -> Option<&'static str> {
+    let in_panchaka = match nakshatra_index {
+        DHANISHTA => pada >= 3,
+        index => index > DHANISHTA,
+    };
+    in_panchaka.then(|| PANCHAKA[weekday % 7])
+}
+
+/// Bhadra, the Visti Karana, with its face and tail
+#[derive(Debug, Clone, Copy)]
+pub struct Bhadra {
+    /// Paksha the Bhadra falls in, Shukla or Krishna
+    pub paksha: &'static str,
+    /// Vrishchiki (the scorpion) in the Shukla Paksha, Sarpini (the serpent) in the Krishna
+    pub form: &'static str,
+    /// Mukha (face), 5 ghatis, as a (start, end) pair in the unit of the Tithi's bounds
+    pub face: (f64, f64),
+    /// Puchha (tail), 3 ghatis
+    pub tail: (f64, f64),
+    /// The part that harms: the scorpion stings with its tail, the serpent bites with
+    /// its face
+    pub avoid: &'static str,
+    /// Whether it is a day Bhadra, of the second half of its Tithi, rather than a
+    /// night Bhadra, of the first half
+    pub day_bhadra: bool,
+}
+
+impl Bhadra {
+    /// Whether the Bhadra does no harm, being a day Bhadra that falls at night or
+    /// a night Bhadra that falls by day
+    pub fn harmless(&self, by_day: bool) -> bool {
+        by_day != self.day_bhadra
+    }
+}
+
+/// Classifies a Bhadra from the half-Tithi it holds and places its face and tail
+///
+/// The Tithi holding the Bhadra is reckoned as 60 ghatis in 8 Praharas whatever its
+/// length, so the face and the tail scale with the Moon's speed like Varjyam does.
+/// The face is the first 5 ghatis of one Prahara and the tail the last 3 of another,
+/// both within the Bhadra, in the Praharas the Muhurta texts give for each Tithi.
+///
+/// # Arguments
+/// * `tithi_start` - Start of the Tithi holding the Bhadra, in any unit of time
+/// * `tithi_end` - End of the Tithi, in the same unit
+/// * `half_tithi` - Half-Tithi (0-59) of the month that Visti holds
+///
+/// # Returns
+/// The Bhadra with its face and tail in the unit of `tithi_start` and `tithi_end`
+pub fn bhadra(tithi_start: f64, tithi_end: f64, half_tithi: usize) -> Bhadra {
+    let ghati = (tithi_end - tithi_start) / TITHI_GHATIS;
+    let prahara = (tithi_end - tithi_start) / TITHI_PRAHARAS;
+    let prahara_start = |number: usize| tithi_start + prahara * (number - 1) as f64;
+
+    let half_tithi = half_tithi % 60;
+    let nth = (half_tithi / HALF_TITHIS_PER_BHADRA).saturating_sub(1) % 8;
+    let face_start = prahara_start(BHADRA_FACE_PRAHARA[nth]);
+    let tail_end = prahara_start(BHADRA_TAIL_PRAHARA[nth]) + prahara;
+    let (paksha, form, avoid) = if half_tithi < KRISHNA_HALF_TITHIS_START {
+        ("Shukla", "Vrishchiki", "tail")
+    } else {
+        ("Krishna", "Sarpini", "face")
+    };
+
+    Bhadra {
+        paksha,
+        form,
+        face: (face_start, face_start + ghati * BHADRA_FACE_GHATIS),
+        tail: (tail_end - ghati * BHADRA_TAIL_GHATIS, tail_end),
+        avoid,
+        day_bhadra: half_tithi % 2 == 1,
+    }
+}
@@ -0,0 +1,100 @@
+#[cfg(feature = "server")]
+use actix_web::ResponseError;
+#[cfg(feature = "server")]
+use actix_web::http::StatusCode;
+
+/// Errors raised while validating a Panchang request
+#[derive(Debug, thiserror::Error)]
+pub enum PanchangError {
+    /// The date is not in DD/MM/YYYY format
+    #[error("Error parsing date: {0}")]
+    InvalidDateFormat(String),
+    /// The date is well-formed but does not exist in the calendar
+    #[error("Error parsing date: {0}")]
+    DateOutOfRange(String),
+    /// The start and end dates do not form an acceptable range
+    #[error("Invalid date range: {0}")]
+    InvalidDateRange(String),
+    /// The time is not in HH:MM format
+    #[error("Error parsing time: {0}")]
+    InvalidTimeFormat(String),
+    /// The instant is not given exactly once, as an epoch or as a date and time,
+    /// or the epoch is out of range
+    #[error("Error parsing instant: {0}")]
+    InvalidInstant(String),
+    /// The timezone offset is malformed or out of range
+    #[error("Error parsing timezone: {0}")]
+    InvalidTimezone(String),
+    /// The latitude or longitude is beyond the poles or the antimeridian
+    #[error("Invalid location: {0}")]
+    InvalidLocation(String),
+    /// A janma (birth) Nakshatra or Rashi is not a known name
+    #[error("Error parsing birth details: {0}")]
+    InvalidBirthDetails(String),
+    /// A Tithi or Paksha is not a known name, or the Tithi does not fall in the Paksha
+    #[error("Error parsing Tithi: {0}")]
+    InvalidTithi(String),
+    /// The Tithi searched for does not prevail at sunrise within the searched days
+    #[error("Tithi not found: {0}")]
+    TithiNotFound(String),
+    /// The Ayanamsa systems to compare are fewer than two or repeat one
+    #[error("Invalid comparison: {0}")]
+    InvalidComparison(String),
+    /// The response schema version asked for is not one the server serves
+    #[error("Unsupported API version: {0}")]
+    UnsupportedApiVersion(String),
+    /// The calculation took longer than the server allows, in milliseconds
+    #[error("Calculation timed out after {0} ms")]
+    CalculationTimeout(u128),
+    /// The calculation panicked, so no result could be produced
+    #[error("Calculation failed: {0}")]
+    CalculationFailed(String),
+}
+
+impl PanchangError {
+    /// Stable machine-readable code of the error, sent as `code` in the envelope
+    pub fn code(&self) -> &'static str {
+        match self {
+            PanchangError::InvalidDateFormat(_) => "INVALID_DATE",
+            PanchangError::DateOutOfRange(_) => "DATE_OUT_OF_RANGE",
+            PanchangError::InvalidDateRange(_) => "INVALID_DATE_RANGE",
+            PanchangError::InvalidTimeFormat(_) => "INVALID_TIME",
+            PanchangError::InvalidInstant(_) => "INVALID_INSTANT",
+            PanchangError::InvalidTimezone(_) => "INVALID_TIMEZONE",
+            PanchangError::InvalidLocation(_) => "INVALID_LOCATION",
+            PanchangError::InvalidBirthDetails(_) => "INVALID_BIRTH_DETAILS",
+            PanchangError::InvalidTithi(_) => "INVALID_TITHI",
+            PanchangError::TithiNotFound(_) => "TITHI_NOT_FOUND",
+            PanchangError::InvalidComparison(_) => "INVALID_COMPARISON",
+            PanchangError::UnsupportedApiVersion(_) => "UNSUPPORTED_API_VERSION",
+            PanchangError::CalculationTimeout(_) => "CALCULATION_TIMEOUT",
+            PanchangError::CalculationFailed(_) => "INTERNAL_ERROR",
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl ResponseError for PanchangError {
+    /// Malformed input is a bad request, while a well-formed date that does not
+    /// exist is reported as unprocessable, a response version the server does not
+    /// serve as not acceptable, a calculation cut short as a timeout and one that
+    /// panicked as an internal error
+    fn status_code(&self) -> StatusCode {
+        match self {
+            PanchangError::InvalidDateFormat(_)
+            | PanchangError::InvalidTimeFormat(_)
+            | PanchangError::InvalidInstant(_)
+            | PanchangError::InvalidTimezone(_)
+            | PanchangError::InvalidLocation(_)
+            | PanchangError::InvalidBirthDetails(_)
+            | PanchangError::InvalidTithi(_)
+            | PanchangError::InvalidComparison(_) => StatusCode::BAD_REQUEST,
+            PanchangError::DateOutOfRange(_)
+            | PanchangError::InvalidDateRange(_)
+            | PanchangError::TithiNotFound(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            PanchangError::UnsupportedApiVersion(_) => StatusCode::NOT_ACCEPTABLE,
+            PanchangError::CalculationTimeout(_) => StatusCode::GATEWAY_TIMEOUT,
+            PanchangError::CalculationFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
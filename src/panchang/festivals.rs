@@ -0,0 +1,54 @@
+//! Festival markers - widely observed days that follow from the Tithi and weekday alone
+//!
+//! This is rule-based rather than a festival calendar: only the monthly observances
+//! fixed by the Tithi of a Paksha, some of them renamed when they fall on a
+//! particular weekday, are recognised.
+
+/// Weekday indices, starting from Sunday
+const MONDAY: usize = 1;
+const TUESDAY: usize = 2;
+const SATURDAY: usize = 6;
+
+/// Returns the observances falling on a Tithi
+///
+/// # Arguments
+/// * `tithi` - Tithi number within its Paksha, 1 (Pratipada) to 15 (Poornima or Amavasya)
+/// * `paksha` - The Paksha, "Shukla" or "Krishna"
+/// * `weekday` - Weekday index, 0 for Sunday through 6 for Saturday
+///
+/// # Returns
+/// The names of the observances, empty when there are none
+pub fn special_days(tithi: usize, paksha: &str, weekday: usize) -> Vec<String> {
+    let shukla = paksha == "Shukla";
+    let weekday = weekday % 7;
+    let mut days = Vec::new();
+
+    match (tithi, shukla) {
+        (4, true) => days.push("Vinayaka Chaturthi"),
+        (4, false) if weekday == TUESDAY => days.push("Angaraki Sankashti Chaturthi"),
+        (4, false) => days.push("Sankashti Chaturthi"),
+        (6, true) => days.push("Skanda Shashti"),
+        (8, true) => days.push("Masik Durgashtami"),
+        (8, false) => days.push("Kalashtami"),
+        (11, _) => days.push("Ekadashi"),
+        (13, _) => days.push(match weekday {
+            MONDAY => "Soma Pradosh",
+            TUESDAY => "Bhauma Pradosh",
+            SATURDAY => "Shani Pradosh",
+            _ => "Pradosh",
+        }),
+        (14, false) => days.push("Masik Shivaratri"),
+        (15, true) => days.push("Purnima"),
+        (15, false) => {
+            days.push("Amavasya");
+            match weekday {
+                MONDAY => days.push("Somavati Amavasya"),
+                SATURDAY => days.push("Shani Amavasya"),
+                _ => {}
+            }
+        }
+        _ => {}
+    }
+
+    days.into_iter().map(str::to_string).collect()
+}
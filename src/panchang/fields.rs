@@ -0,0 +1,154 @@
+//! Selection of the response fields a client needs
+//!
+//! A request's `fields` names the response fields to send, such as `tithi,nakshatra`,
+//! so that a constrained client receives only those. The Panchang is still calculated
+//! and cached whole, and the other fields are left out as it is serialized.
+
+use serde::de::{self, Deserializer};
+use serde::ser::{self, Serializer};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Every field of a Panchang response, in the order it is serialized
+pub static RESPONSE_FIELDS: [&str; 59] = [
+    "vaara",
+    "tithi",
+    "tithi_start",
+    "tithi_end",
+    "tithi_status",
+    "sunrise_tithis",
+    "paksha",
+    "masa",
+    "adhika_masa",
+    "samvatsara",
+    "kali_year",
+    "kali_ahargana",
+    "ayana",
+    "ritu",
+    "nakshatra",
+    "nakshatra_pada",
+    "nakshatra_lord",
+    "yoga",
+    "yoga_start",
+    "yoga_end",
+    "critical_yogas",
+    "karana",
+    "bhadra",
+    "rashi",
+    "rashi_lord",
+    "navamsa_rashi",
+    "rashi_end",
+    "next_rashi",
+    "surya_rashi",
+    "surya_nakshatra",
+    "surya_nakshatra_pada",
+    "sankranti",
+    "lagna",
+    "moon_illumination",
+    "phase_angle",
+    "phase_name",
+    "sunrise",
+    "sunset",
+    "moonrise",
+    "moonset",
+    "rahu_kaal",
+    "yamaganda",
+    "gulika",
+    "abhijit_muhurta",
+    "varjyam",
+    "amrita_kaal",
+    "hora",
+    "festivals",
+    "panchaka",
+    "disha_shool",
+    "tarabala",
+    "chandrabala",
+    "choghadiya",
+    "accuracy_warning",
+    "sun_longitude",
+    "moon_longitude",
+    "samples",
+    "labels",
+    "meta",
+];
+
+/// Response fields selected by a comma-separated list such as `tithi,nakshatra`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseFields(Vec<&'static str>);
+
+impl ResponseFields {
+    /// Reads a comma-separated list of response fields, rejecting unknown names and
+    /// a list naming none
+    pub fn parse(list: &str) -> Result<ResponseFields, String> {
+        let mut fields = Vec::new();
+        for name in list
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+        {
+            match RESPONSE_FIELDS.iter().find(|field| **field == name) {
+                Some(field) => fields.push(*field),
+                None => return Err(format!("unknown field `{}` in `fields`", name)),
+            }
+        }
+        if fields.is_empty() {
+            return Err(String::from("`fields` names no response field"));
+        }
+        Ok(ResponseFields(fields))
+    }
+
+    /// Whether a response field is selected
+    pub fn contains(&self, field: &str) -> bool {
+        self.0.contains(&field)
+    }
+
+    /// Removes the response fields not selected from a serialized response, or from
+    /// each of a list of them or of its samples, keeping other keys such as a day's
+    /// `date`
+    fn retain(&self, value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                map.retain(|key, _| self.contains(key) || !RESPONSE_FIELDS.contains(&key.as_str()));
+                // Each sample is a Panchang of its own, with the same fields selected
+                if let Some(samples) = map.get_mut("samples") {
+                    self.retain(samples);
+                }
+            }
+            Value::Array(items) => items.iter_mut().for_each(|item| self.retain(item)),
+            _ => {}
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ResponseFields {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let list = String::deserialize(deserializer)?;
+        ResponseFields::parse(&list).map_err(de::Error::custom)
+    }
+}
+
+/// A response serialized with only the fields a request selected, or whole when it
+/// selected none
+#[derive(Debug)]
+pub struct Selected<T> {
+    value: T,
+    fields: Option<ResponseFields>,
+}
+
+impl<T> Selected<T> {
+    /// Selects `fields` of a response, or a list of responses
+    pub fn new(value: T, fields: Option<ResponseFields>) -> Self {
+        Selected { value, fields }
+    }
+}
+
+impl<T: Serialize> Serialize for Selected<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let Some(fields) = &self.fields else {
+            return self.value.serialize(serializer);
+        };
+        let mut value = serde_json::to_value(&self.value).map_err(ser::Error::custom)?;
+        fields.retain(&mut value);
+        value.serialize(serializer)
+    }
+}
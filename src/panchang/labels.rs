@@ -0,0 +1,166 @@
+//! Display labels of the response fields in the languages frontends show them in
+//!
+//! The response keys stay the same in every language; the labels only give each
+//! key the name a frontend should print next to its value.
+
+use std::collections::BTreeMap;
+
+/// Language of the field labels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Language {
+    #[default]
+    English,
+    Hindi,
+    Tamil,
+}
+
+impl Language {
+    /// Reads a language tag such as `hi`, `hi-IN` or `ta_IN` by its primary subtag
+    pub fn from_tag(tag: &str) -> Option<Language> {
+        let primary = tag.trim().split(['-', '_']).next().unwrap_or_default();
+        match primary.to_ascii_lowercase().as_str() {
+            "en" => Some(Language::English),
+            "hi" => Some(Language::Hindi),
+            "ta" => Some(Language::Tamil),
+            _ => None,
+        }
+    }
+
+    /// Picks the preferred supported language of an `Accept-Language` header
+    ///
+    /// Languages are tried from the highest quality value down, keeping the header's
+    /// order among equal values, and English is used when none is supported.
+    pub fn negotiate(accept_language: &str) -> Language {
+        let mut ranges: Vec<(&str, f32)> = accept_language
+            .split(',')
+            .map(|range| {
+                let mut parts = range.split(';');
+                let tag = parts.next().unwrap_or_default();
+                let quality = parts
+                    .filter_map(|param| param.trim().strip_prefix("q="))
+                    .find_map(|q| q.trim().parse().ok())
+                    .unwrap_or(1.0);
+                (tag, quality)
+            })
+            .filter(|(_, quality)| *quality > 0.0)
+            .collect();
+        ranges.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        ranges
+            .into_iter()
+            .find_map(|(tag, _)| Language::from_tag(tag))
+            .unwrap_or_default()
+    }
+}
+
+/// Label of each response field in English, Hindi and Tamil
+static LABELS: [(&str, [&str; 3]); 56] = [
+    ("vaara", ["Weekday", "वार", "கிழமை"]),
+    ("tithi", ["Tithi", "तिथि", "திதி"]),
+    ("tithi_start", ["Tithi begins", "तिथि आरंभ", "திதி தொடக்கம்"]),
+    ("tithi_end", ["Tithi ends", "तिथि समाप्ति", "திதி முடிவு"]),
+    ("tithi_status", ["Tithi status", "तिथि स्थिति", "திதி நிலை"]),
+    (
+        "sunrise_tithis",
+        ["Tithis at sunrise", "उदय तिथि", "உதய திதி"],
+    ),
+    ("paksha", ["Paksha", "पक्ष", "பட்சம்"]),
+    ("masa", ["Month", "मास", "மாதம்"]),
+    ("adhika_masa", ["Adhika Masa", "अधिक मास", "அதிக மாதம்"]),
+    ("samvatsara", ["Samvatsara", "संवत्सर", "சம்வத்சரம்"]),
+    ("kali_year", ["Kali year", "कलि संवत्", "கலி ஆண்டு"]),
+    (
+        "kali_ahargana",
+        ["Kali Ahargana", "कलि अहर्गण", "கலி அஹர்கணம்"],
+    ),
+    ("ayana", ["Ayana", "अयन", "அயனம்"]),
+    ("ritu", ["Season", "ऋतु", "ருது"]),
+    ("nakshatra", ["Nakshatra", "नक्षत्र", "நட்சத்திரம்"]),
+    ("nakshatra_pada", ["Pada", "चरण", "பாதம்"]),
+    (
+        "nakshatra_lord",
+        ["Nakshatra lord", "नक्षत्र स्वामी", "நட்சத்திர அதிபதி"],
+    ),
+    ("yoga", ["Yoga", "योग", "யோகம்"]),
+    ("yoga_start", ["Yoga begins", "योग आरंभ", "யோகம் தொடக்கம்"]),
+    ("yoga_end", ["Yoga ends", "योग समाप्ति", "யோகம் முடிவு"]),
+    (
+        "critical_yogas",
+        ["Inauspicious Yogas", "अशुभ योग", "அசுப யோகங்கள்"],
+    ),
+    ("karana", ["Karana", "करण", "கரணம்"]),
+    ("bhadra", ["Bhadra", "भद्रा", "பத்ரா"]),
+    ("rashi", ["Moon sign", "चंद्र राशि", "சந்திர ராசி"]),
+    ("rashi_lord", ["Moon sign lord", "राशि स्वामी", "ராசி அதிபதி"]),
+    ("navamsa_rashi", ["Navamsa", "नवांश", "நவாம்சம்"]),
+    (
+        "rashi_end",
+        ["Moon sign ends", "चंद्र राशि समाप्ति", "சந்திர ராசி முடிவு"],
+    ),
+    (
+        "next_rashi",
+        ["Next Moon sign", "अगली चंद्र राशि", "அடுத்த சந்திர ராசி"],
+    ),
+    ("surya_rashi", ["Sun sign", "सूर्य राशि", "சூரிய ராசி"]),
+    (
+        "surya_nakshatra",
+        ["Sun's Nakshatra", "सूर्य नक्षत्र", "சூரிய நட்சத்திரம்"],
+    ),
+    (
+        "surya_nakshatra_pada",
+        ["Sun's pada", "सूर्य नक्षत्र चरण", "சூரிய பாதம்"],
+    ),
+    ("sankranti", ["Sankranti", "संक्रांति", "சங்கராந்தி"]),
+    ("lagna", ["Ascendant", "लग्न", "லக்னம்"]),
+    (
+        "moon_illumination",
+        ["Moon illumination", "चंद्र प्रकाश", "சந்திர ஒளி"],
+    ),
+    ("phase_angle", ["Phase angle", "कला कोण", "கலை கோணம்"]),
+    ("phase_name", ["Moon phase", "चंद्र कला", "சந்திர கலை"]),
+    ("sunrise", ["Sunrise", "सूर्योदय", "சூரிய உதயம்"]),
+    ("sunset", ["Sunset", "सूर्यास्त", "சூரிய அஸ்தமனம்"]),
+    ("moonrise", ["Moonrise", "चंद्रोदय", "சந்திர உதயம்"]),
+    ("moonset", ["Moonset", "चंद्रास्त", "சந்திர அஸ்தமனம்"]),
+    ("rahu_kaal", ["Rahu Kaal", "राहु काल", "ராகு காலம்"]),
+    ("yamaganda", ["Yamaganda", "यमगण्ड", "எமகண்டம்"]),
+    ("gulika", ["Gulika Kaal", "गुलिक काल", "குளிகை"]),
+    (
+        "abhijit_muhurta",
+        ["Abhijit Muhurta", "अभिजित मुहूर्त", "அபிஜித் முகூர்த்தம்"],
+    ),
+    ("varjyam", ["Varjyam", "वर्ज्यम्", "வர்ஜ்யம்"]),
+    ("amrita_kaal", ["Amrita Kaal", "अमृत काल", "அமிர்த காலம்"]),
+    ("hora", ["Hora", "होरा", "ஹோரை"]),
+    ("festivals", ["Festivals", "व्रत और त्योहार", "விரதங்கள்"]),
+    ("panchaka", ["Panchaka", "पंचक", "பஞ்சகம்"]),
+    ("disha_shool", ["Disha Shool", "दिशा शूल", "சூலம்"]),
+    ("tarabala", ["Tarabala", "ताराबल", "தாரா பலம்"]),
+    ("chandrabala", ["Chandrabala", "चंद्रबल", "சந்திர பலம்"]),
+    ("choghadiya", ["Choghadiya", "चौघड़िया", "சௌகடியா"]),
+    (
+        "accuracy_warning",
+        ["Accuracy warning", "सटीकता चेतावनी", "துல்லிய எச்சரிக்கை"],
+    ),
+    (
+        "sun_longitude",
+        ["Sun's longitude", "सूर्य भोगांश", "சூரிய ஸ்புடம்"],
+    ),
+    (
+        "moon_longitude",
+        ["Moon's longitude", "चंद्र भोगांश", "சந்திர ஸ்புடம்"],
+    ),
+];
+
+/// Maps each response field to its label in a language
+pub fn field_labels(language: Language) -> BTreeMap<String, String> {
+    let column = match language {
+        Language::English => 0,
+        Language::Hindi => 1,
+        Language::Tamil => 2,
+    };
+    LABELS
+        .iter()
+        .map(|(field, labels)| (field.to_string(), labels[column].to_string()))
+        .collect()
+}
@@ -1,9 +1,56 @@
 // Re-export the main panchang functionality
 pub use self::panchang::*;
 
+// Re-export the muhurta (time window) functionality
+pub use self::muhurta::*;
+
+// Re-export the bala (personal strength) functionality
+pub use self::bala::*;
+
+// Re-export the dosha (inauspicious condition) detection
+pub use self::dosha::*;
+
+// Re-export the festival markers
+pub use self::festivals::*;
+
+// Re-export the field-by-field comparison
+pub use self::diff::panchanga_diff;
+
+// Re-export the selection of response fields
+pub use self::fields::{RESPONSE_FIELDS, ResponseFields, Selected};
+
+// Re-export the sample times of a day
+pub use self::samples::{MAX_SAMPLES, SampleTime, Samples};
+
+// Re-export the versions of the response schema
+pub use self::version::{ApiVersion, V1Shape, Versioned};
+
+// Re-export the localized field labels
+pub use self::labels::*;
+
+// Re-export the error type shared by the parsers and handlers
+pub use self::error::PanchangError;
+
 // Re-export the API types and handlers
-pub use self::api::{PanchangRequest, PanchangResponse,calculate_panchang};
+pub use self::api::{PanchangRequest, PanchangResponse, PanchangResponseV1, PanchangSample, calculate_panchang};
+pub use self::api::{ValidatedRequest, calculate_panchang_validated};
+pub use self::api::{PanchangDay, PanchangDayV1, PanchangRangeRequest, calculate_panchang_day, calculate_panchang_range};
+pub use self::api::{Location, PanchangNowRequest, RequestCalendar, RequestLocation, RequestOptions, calculate_panchang_now};
+pub use self::api::{NextTithiRequest, TithiOccurrence, TithiOccurrenceV1, calculate_next_tithi};
+pub use self::api::{PanchangCompareRequest, PanchangComparison, PanchangComparisonV1, calculate_panchang_comparison};
 
 // Internal modules
 mod api;
+mod bala;
+mod diff;
+mod display;
+mod dosha;
+mod error;
+mod festivals;
+mod fields;
+mod labels;
+mod muhurta;
+#[allow(clippy::module_inception)]
 mod panchang;
+mod samples;
+mod version;
@@ -0,0 +1,290 @@
+//! Muhurta calculations - time windows within the day derived from sunrise and sunset
+//!
+//! These periods do not depend on planetary positions directly. They are found by
+//! dividing the daytime into equal parts and assigning the parts to named periods
+//! according to fixed weekday tables.
+
+/// Slot (1-8) of the daytime occupied by Rahu Kaal for each weekday, starting from Sunday
+static RAHU_KAAL_SLOT: [usize; 7] = [8, 2, 7, 5, 6, 4, 3];
+
+/// Slot (1-8) of the daytime occupied by Yamaganda for each weekday, starting from Sunday
+static YAMAGANDA_SLOT: [usize; 7] = [5, 4, 3, 2, 1, 7, 6];
+
+/// Slot (1-8) of the daytime occupied by Gulika Kaal for each weekday, starting from Sunday
+static GULIKA_SLOT: [usize; 7] = [7, 6, 5, 4, 3, 2, 1];
+
+/// The 7 Choghadiyas in their daytime succession, each with whether it is auspicious
+static CHOGHADIYA: [(&str, bool); 7] = [
+    ("Udveg", false),
+    ("Char", true),
+    ("Labh", true),
+    ("Amrit", true),
+    ("Kaal", false),
+    ("Shubh", true),
+    ("Rog", false),
+];
+
+/// Index into `CHOGHADIYA` of the first daytime Choghadiya for each weekday, starting from Sunday
+static DAY_CHOGHADIYA_START: [usize; 7] = [0, 3, 6, 2, 5, 1, 4];
+
+/// Index into `CHOGHADIYA` of the first nighttime Choghadiya for each weekday, starting from Sunday
+static NIGHT_CHOGHADIYA_START: [usize; 7] = [5, 1, 4, 0, 3, 6, 2];
+
+/// The 7 classical planets in the order in which they rule successive Horas, the
+/// Chaldean order (Shani, Guru, Mangala, Surya, Shukra, Budha, Chandra) from Surya
+static HORA_LORD: [&str; 7] = [
+    "Surya", "Shukra", "Budha", "Chandra", "Shani", "Guru", "Mangala",
+];
+
+/// Number of Horas in each of the daytime and the night
+const HORAS: usize = 12;
+
+/// Ghati (of 60 in a Nakshatra) at which Varjyam begins, for each Nakshatra from Ashwini
+static VARJYAM_GHATI: [f64; 27] = [
+    50.0, 24.0, 30.0, 40.0, 14.0, 11.0, 30.0, 20.0, 32.0, 30.0, 20.0, 18.0, 21.0, 20.0, 14.0, 14.0,
+    10.0, 14.0, 20.0, 24.0, 20.0, 10.0, 10.0, 18.0, 16.0, 24.0, 30.0,
+];
+
+/// Ghati (of 60 in a Nakshatra) at which Amrita Kaal begins, for each Nakshatra from Ashwini
+static AMRITA_GHATI: [f64; 27] = [
+    42.0, 48.0, 54.0, 52.0, 38.0, 35.0, 54.0, 44.0, 56.0, 54.0, 44.0, 42.0, 45.0, 44.0, 38.0, 38.0,
+    34.0, 38.0, 44.0, 48.0, 44.0, 34.0, 34.0, 42.0, 40.0, 48.0, 54.0,
+];
+
+/// Ghatis in a Nakshatra, whatever its actual length
+const NAKSHATRA_GHATIS: f64 = 60.0;
+
+/// Ghatis that Varjyam and Amrita Kaal each last
+const PORTION_GHATIS: f64 = 4.0;
+
+/// Number of muhurtas into which the daytime is divided
+const DAY_MUHURTAS: f64 = 15.0;
+
+/// Weekday index of Wednesday, on which Abhijit Muhurta is void
+const WEDNESDAY: usize = 3;
+
+/// The inauspicious bands of a day, each as local (start, end) decimal hours
+#[derive(Debug, Clone, Copy)]
+pub struct InauspiciousPeriods {
+    /// Rahu Kaal, the period ruled by Rahu
+    pub rahu_kaal: (f64, f64),
+    /// Yamaganda, the period ruled by Yama
+    pub yamaganda: (f64, f64),
+    /// Gulika Kaal, the period ruled by Gulika (son of Shani)
+    pub gulika: (f64, f64),
+}
+
+/// Returns the bounds of one of the eight equal parts of the daytime
+///
+/// # Arguments
+/// * `sunrise` - Local sunrise in decimal hours
+/// * `sunset` - Local sunset in decimal hours
+/// * `slot` - The part of the day, from 1 (starting at sunrise) to 8 (ending at sunset)
+fn day_slot(sunrise: f64, sunset: f64, slot: usize) -> (f64, f64) {
+    let part = (sunset - sunrise) / 8.0;
+    let start = sunrise + part * (slot - 1) as f64;
+    (start, start + part)
+}
+
+/// Calculates Rahu Kaal, Yamaganda and Gulika Kaal for a day
+///
+/// The daytime from sunrise to sunset is divided into eight equal parts and each
+/// band occupies the part assigned to it for the weekday.
+///
+/// # Arguments
+/// * `sunrise` - Local sunrise in decimal hours
+/// * `sunset` - Local sunset in decimal hours
+/// * `weekday` - Weekday index, 0 for Sunday through 6 for Saturday
+///
+/// # Returns
+/// The three inauspicious bands as local decimal hours
+pub fn inauspicious_periods(sunrise: f64, sunset: f64, weekday: usize) -> InauspiciousPeriods {
+    let weekday = weekday % 7;
+
+    InauspiciousPeriods {
+        rahu_kaal: day_slot(sunrise, sunset, RAHU_KAAL_SLOT[weekday]),
+        yamaganda: day_slot(sunrise, sunset, YAMAGANDA_SLOT[weekday]),
+        gulika: day_slot(sunrise, sunset, GULIKA_SLOT[weekday]),
+    }
+}
+
+/// One Choghadiya, an eighth of the daytime or of the night
+#[derive(Debug, Clone, Copy)]
+pub struct ChoghadiyaSlot {
+    /// Local start in decimal hours; night slots after midnight run past 24
+    pub start: f64,
+    /// Local end in decimal hours; night slots after midnight run past 24
+    pub end: f64,
+    /// Name of the Choghadiya (Amrit, Shubh, Labh, Char, Rog, Kaal, Udveg)
+    pub name: &'static str,
+    /// Whether the Choghadiya is auspicious
+    pub auspicious: bool,
+}
+
+/// Calculates the eight daytime and eight nighttime Choghadiyas of a day
+///
+/// The daytime (sunrise to sunset) and the night (sunset to the next sunrise) are
+/// each divided into eight equal parts. Daytime Choghadiyas follow one another in
+/// the order of `CHOGHADIYA`, while at night each is five places further on,
+/// and both sequences start at a weekday-dependent Choghadiya.
+///
+/// # Arguments
+/// * `sunrise` - Local sunrise in decimal hours
+/// * `sunset` - Local sunset in decimal hours
+/// * `next_sunrise` - Local sunrise of the following day in decimal hours
+/// * `weekday` - Weekday index, 0 for Sunday through 6 for Saturday
+///
+/// # Returns
+/// The 16 Choghadiyas in time order, the daytime ones first
+pub fn choghadiya(
+    sunrise: f64,
+    sunset: f64,
+    next_sunrise: f64,
+    weekday: usize,
+) -> Vec<ChoghadiyaSlot> {
+    let weekday = weekday % 7;
+    let day_part = (sunset - sunrise) / 8.0;
+    let night_part = (next_sunrise + 24.0 - sunset) / 8.0;
+
+    let day = (0..8).map(|slot| {
+        (
+            sunrise + day_part * slot as f64,
+            day_part,
+            DAY_CHOGHADIYA_START[weekday] + slot,
+        )
+    });
+    let night = (0..8).map(|slot| {
+        (
+            sunset + night_part * slot as f64,
+            night_part,
+            NIGHT_CHOGHADIYA_START[weekday] + 5 * slot,
+        )
+    });
+
+    day.chain(night)
+        .map(|(start, part, index)| {
+            let (name, auspicious) = CHOGHADIYA[index % 7];
+            ChoghadiyaSlot {
+                start,
+                end: start + part,
+                name,
+                auspicious,
+            }
+        })
+        .collect()
+}
+
+/// A Hora (planetary hour) with its ruling planet
+#[derive(Debug, Clone, Copy)]
+pub struct HoraSlot {
+    /// Local start in decimal hours
+    pub start: f64,
+    /// Local end in decimal hours, past 24 for a Hora ending after midnight
+    pub end: f64,
+    /// Ruling planet (Surya, Chandra, ...)
+    pub lord: &'static str,
+}
+
+/// Finds the Hora (planetary hour) in effect at a time of day
+///
+/// The daytime and the night are each divided into twelve equal Horas, so day and
+/// night Horas differ in length. The first Hora at sunrise is ruled by the weekday's
+/// lord and each following Hora by the next planet of `HORA_LORD`. The 24 Horas of
+/// a day advance the sequence by three places, which is how each weekday's lord
+/// follows the previous one.
+///
+/// # Arguments
+/// * `sunrise` - Local sunrise in decimal hours
+/// * `sunset` - Local sunset in decimal hours
+/// * `next_sunrise` - Local sunrise of the following day in decimal hours
+/// * `weekday` - Weekday index, 0 for Sunday through 6 for Saturday
+/// * `now` - Local time in decimal hours, past 24 for the night after midnight
+///
+/// # Returns
+/// The Hora containing `now`, or `None` if `now` is outside the day from sunrise
+/// to the next sunrise
+pub fn current_hora(
+    sunrise: f64,
+    sunset: f64,
+    next_sunrise: f64,
+    weekday: usize,
+    now: f64,
+) -> Option<HoraSlot> {
+    let next_sunrise = next_sunrise + 24.0;
+    if now < sunrise || now >= next_sunrise {
+        return None;
+    }
+
+    let (start, part, offset) = if now < sunset {
+        (sunrise, (sunset - sunrise) / HORAS as f64, 0)
+    } else {
+        (sunset, (next_sunrise - sunset) / HORAS as f64, HORAS)
+    };
+    let hora = (((now - start) / part) as usize).min(HORAS - 1);
+    let start = start + part * hora as f64;
+
+    Some(HoraSlot {
+        start,
+        end: start + part,
+        lord: HORA_LORD[(weekday % 7 * 3 + offset + hora) % 7],
+    })
+}
+
+/// Calculates Abhijit Muhurta, the auspicious muhurta around local midday
+///
+/// The daytime is divided into fifteen muhurtas and Abhijit is the eighth, so it is
+/// centred on the midpoint between sunrise and sunset and lasts about 48 minutes.
+///
+/// # Arguments
+/// * `sunrise` - Local sunrise in decimal hours
+/// * `sunset` - Local sunset in decimal hours
+/// * `weekday` - Weekday index, 0 for Sunday through 6 for Saturday
+///
+/// # Returns
+/// Abhijit's start and end as local decimal hours, or `None` on Wednesdays, when
+/// it is traditionally considered void
+pub fn abhijit_muhurta(sunrise: f64, sunset: f64, weekday: usize) -> Option<(f64, f64)> {
+    if weekday % 7 == WEDNESDAY {
+        return None;
+    }
+
+    let part = (sunset - sunrise) / DAY_MUHURTAS;
+    let start = sunrise + part * 7.0;
+    Some((start, start + part))
+}
+
+/// Varjyam and Amrita Kaal of a Nakshatra, each as a (start, end) pair in the unit
+/// of the Nakshatra's bounds
+#[derive(Debug, Clone, Copy)]
+pub struct NakshatraPortions {
+    /// Varjyam (Tyajya), the inauspicious portion
+    pub varjyam: (f64, f64),
+    /// Amrita Kaal, the auspicious portion
+    pub amrita_kaal: (f64, f64),
+}
+
+/// Calculates the Varjyam and Amrita Kaal of a Nakshatra from its bounds
+///
+/// The Nakshatra is reckoned as 60 ghatis whatever its length, and each portion
+/// lasts 4 of them from a ghati tabulated for the Nakshatra, so both scale with the
+/// Moon's speed.
+///
+/// # Arguments
+/// * `start` - Start of the Nakshatra, in any unit of time
+/// * `end` - End of the Nakshatra, in the same unit
+/// * `nakshatra` - Index (0-26) of the Nakshatra, from Ashwini
+///
+/// # Returns
+/// The two portions in the unit of `start` and `end`
+pub fn nakshatra_portions(start: f64, end: f64, nakshatra: usize) -> NakshatraPortions {
+    let ghati = (end - start) / NAKSHATRA_GHATIS;
+    let portion = |from: f64| {
+        let begins = start + ghati * from;
+        (begins, begins + ghati * PORTION_GHATIS)
+    };
+
+    NakshatraPortions {
+        varjyam: portion(VARJYAM_GHATI[nakshatra % 27]),
+        amrita_kaal: portion(AMRITA_GHATI[nakshatra % 27]),
+    }
+}
@@ -10,15 +10,21 @@
 //! The calculations are based on astronomical algorithms for computing planetary positions
 //! and traditional Hindu astrological principles.
 
-use std::f64::consts::PI;
+use crate::panchang::PanchangError;
+use chrono::{DateTime, Datelike, Duration, TimeZone, Utc};
+use panchang_core::{
+    D2R, R2D, arc_index, calc_ayanamsa, days_since_j2000, elements_at, karana_index, moon_distance,
+    moon_latitude, moon_long, rev, sun_long,
+};
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
 
-/// Conversion constant from degrees to radians
-const D2R: f64 = PI / 180.0;
-/// Conversion constant from radians to degrees
-const R2D: f64 = 180.0 / PI;
+// The Sidereal reference systems are defined with the astronomy
+pub use panchang_core::Ayanamsa;
 
 /// Represents the five main elements of Panchanga (Hindu Calendar)
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
 pub struct Panchanga {
     /// Current day (not used in current implementation)
     // current_day: String,
@@ -26,21 +32,98 @@ pub struct Panchanga {
     pub current_yoga: String,
     /// Current Nakshatra (Lunar Mansion)
     pub current_nakshatra: String,
+    /// Number (1-27) of the current Nakshatra, counting from Ashwini
+    pub current_nakshatra_number: u8,
+    /// Current Nakshatra in the 28-Nakshatra system, which carves Abhijit out of
+    /// Uttara Ashada and Sravana
+    pub current_nakshatra_28: String,
+    /// Current pada (quarter, 1-4) of the Nakshatra
+    pub current_nakshatra_pada: u8,
+    /// Ruling planet (Vimshottari dasha lord) of the current Nakshatra
+    pub current_nakshatra_lord: String,
     /// Current Tithi (Lunar Day)
     pub current_tithi: String,
+    /// Number (1-30) of the current Tithi, counting from Shukla Pratipada
+    pub current_tithi_number: u8,
     /// Current Karana (Half Lunar Day)
     pub current_karana: String,
     /// Current Paksha (Lunar Phase - Shukla/Krishna)
     pub current_paksha: String,
-    /// Current Rashi (Zodiac Sign)
-    pub current_rashi: String,
+    /// Current Rashi (Zodiac Sign) of the Moon
+    #[serde(alias = "currentRashi")]
+    pub current_chandra_rashi: String,
+    /// Number (1-12) of the Moon's current Rashi, counting from Mesha
+    pub current_chandra_rashi_number: u8,
+    /// Ruling planet of the Moon's current Rashi
+    pub current_rashi_lord: String,
+    /// Rashi of the Moon in the Navamsa (D9) chart, the sign of its Nakshatra pada
+    pub current_navamsa_rashi: String,
+    /// Current Rashi (Zodiac Sign) of the Sun
+    pub current_surya_rashi: String,
+    /// Nakshatra the Sun is in, which it changes about every 13 days
+    pub current_surya_nakshatra: String,
+    /// Pada (quarter, 1-4) of the Sun's Nakshatra
+    pub current_surya_nakshatra_pada: u8,
+    /// Illuminated fraction of the Moon's disc (0.0-1.0)
+    pub moon_illumination: f64,
+    /// Moon's phase angle in degrees, the Sun-Moon-Earth angle (180 at new moon, 0 at
+    /// full moon)
+    pub moon_phase_angle: f64,
+    /// Name of the Moon's phase (New Moon, Waxing Crescent, ...)
+    pub moon_phase: String,
+    /// Current Masa (lunar month) in the Amanta reckoning
+    pub current_masa: String,
+    /// Current Masa (lunar month) in the Purnimanta reckoning
+    pub current_purnimanta_masa: String,
+    /// Whether the current Masa is an Adhika (intercalary) month
+    pub is_adhika_masa: bool,
+    /// Current Samvatsara (year of the 60-year cycle)
+    pub current_samvatsara: String,
+    /// Current Kali year, the years elapsed since the start of the Kali Yuga, which
+    /// turns with the Saka year at Chaitra
+    pub current_kali_year: i32,
+    /// Current Ayana (Uttarayana/Dakshinayana)
+    pub current_ayana: String,
+    /// Current Ritu (season)
+    pub current_ritu: String,
 }
 
-/// Standard Gregorian calendar months (not used in current implementation)
-// static MONTHS: [&str; 12] = [
-//     "January", "February", "March", "April", "May", "June",
-//     "July", "August", "September", "October", "November", "December"
-// ];
+/// Intermediate values a Panchanga is derived from, for checking a calculation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CalculationDetails {
+    /// UTC instant the elements were calculated for
+    pub instant: DateTime<Utc>,
+    /// Fractional day number of the instant, counted from 1999-12-31 0h UT
+    pub day_number: f64,
+    /// Ayanamsa in degrees, subtracted from tropical longitudes to make them sidereal
+    pub ayanamsa: f64,
+    /// Tropical longitude of the Sun in degrees
+    pub sun_longitude: f64,
+    /// Tropical longitude of the Moon in degrees
+    pub moon_longitude: f64,
+    /// Sidereal longitude (0-360) of the Sun in degrees, from which the Surya Rashi,
+    /// Yoga, Ayana and Ritu are found
+    pub sidereal_sun_longitude: f64,
+    /// Sidereal longitude (0-360) of the Moon in degrees, from which the Nakshatra,
+    /// Rashi and Yoga are found
+    pub sidereal_moon_longitude: f64,
+}
+
+/// Standard Gregorian calendar months, used in date validation messages
+static MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
 
 /// The 12 Rashis (zodiac signs) in Hindu astrology
 /// Each Rashi corresponds to a 30° arc of the ecliptic
@@ -59,6 +142,371 @@ static RASHI: [&str; 12] = [
     "Meena",
 ];
 
+/// Devanagari spellings of `RASHI`
+static RASHI_DEVANAGARI: [&str; 12] = [
+    "मेष",
+    "वृषभ",
+    "मिथुन",
+    "कर्क",
+    "सिंह",
+    "कन्या",
+    "तुला",
+    "वृश्चिक",
+    "धनु",
+    "मकर",
+    "कुम्भ",
+    "मीन",
+];
+
+/// IAST transliterations of `RASHI`
+static RASHI_IAST: [&str; 12] = [
+    "Meṣa",
+    "Vṛṣabha",
+    "Mithuna",
+    "Karka",
+    "Siṃha",
+    "Kanyā",
+    "Tulā",
+    "Vṛścika",
+    "Dhanu",
+    "Makara",
+    "Kumbha",
+    "Mīna",
+];
+
+/// The classical ruling planet of each Rashi, in the order of `RASHI`
+static RASHI_LORD: [&str; 12] = [
+    "Mangala", "Shukra", "Budha", "Chandra", "Surya", "Budha", "Shukra", "Mangala", "Guru",
+    "Shani", "Shani", "Guru",
+];
+
+/// The 7 Vaaras (weekdays) in Hindu calendar, starting from Sunday
+/// Each Vaara is named after the planet ruling its first hora
+static VAARA: [&str; 7] = [
+    "Ravivara",
+    "Somavara",
+    "Mangalavara",
+    "Budhavara",
+    "Guruvara",
+    "Shukravara",
+    "Shanivara",
+];
+
+/// Devanagari spellings of `VAARA`
+static VAARA_DEVANAGARI: [&str; 7] = [
+    "रविवार",
+    "सोमवार",
+    "मंगलवार",
+    "बुधवार",
+    "गुरुवार",
+    "शुक्रवार",
+    "शनिवार",
+];
+
+/// IAST transliterations of `VAARA`
+static VAARA_IAST: [&str; 7] = [
+    "Ravivāra",
+    "Somavāra",
+    "Maṅgalavāra",
+    "Budhavāra",
+    "Guruvāra",
+    "Śukravāra",
+    "Śanivāra",
+];
+
+/// English names of the weekdays, in the same order as `VAARA`
+static WEEKDAY: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+/// The 8 phases of the Moon, each centred on a multiple of 45° of elongation
+static MOON_PHASE: [&str; 8] = [
+    "New Moon",
+    "Waxing Crescent",
+    "First Quarter",
+    "Waxing Gibbous",
+    "Full Moon",
+    "Waning Gibbous",
+    "Last Quarter",
+    "Waning Crescent",
+];
+
+/// The 12 Masas (lunar months) in Hindu calendar, starting from Chaitra
+/// An Amanta Masa runs from one Amavasya to the next and is named after the
+/// solar Rashi the Sun enters during it (Chaitra contains the Mesha Sankranti)
+static MASA: [&str; 12] = [
+    "Chaitra",
+    "Vaishakha",
+    "Jyeshtha",
+    "Ashadha",
+    "Shravana",
+    "Bhadrapada",
+    "Ashwin",
+    "Kartika",
+    "Margashirsha",
+    "Pausha",
+    "Magha",
+    "Phalguna",
+];
+
+/// Devanagari spellings of `MASA`
+static MASA_DEVANAGARI: [&str; 12] = [
+    "चैत्र",
+    "वैशाख",
+    "ज्येष्ठ",
+    "आषाढ",
+    "श्रावण",
+    "भाद्रपद",
+    "आश्विन",
+    "कार्तिक",
+    "मार्गशीर्ष",
+    "पौष",
+    "माघ",
+    "फाल्गुन",
+];
+
+/// IAST transliterations of `MASA`
+static MASA_IAST: [&str; 12] = [
+    "Caitra",
+    "Vaiśākha",
+    "Jyeṣṭha",
+    "Āṣāḍha",
+    "Śrāvaṇa",
+    "Bhādrapada",
+    "Āśvina",
+    "Kārttika",
+    "Mārgaśīrṣa",
+    "Pauṣa",
+    "Māgha",
+    "Phālguna",
+];
+
+/// The 60 Samvatsaras (years) of the Jovian cycle, starting from Prabhava
+static SAMVATSARA: [&str; 60] = [
+    "Prabhava",
+    "Vibhava",
+    "Shukla",
+    "Pramoda",
+    "Prajapati",
+    "Angirasa",
+    "Shrimukha",
+    "Bhava",
+    "Yuva",
+    "Dhatri",
+    "Ishvara",
+    "Bahudhanya",
+    "Pramathi",
+    "Vikrama",
+    "Vrisha",
+    "Chitrabhanu",
+    "Subhanu",
+    "Tarana",
+    "Parthiva",
+    "Vyaya",
+    "Sarvajit",
+    "Sarvadhari",
+    "Virodhi",
+    "Vikriti",
+    "Khara",
+    "Nandana",
+    "Vijaya",
+    "Jaya",
+    "Manmatha",
+    "Durmukhi",
+    "Hevilambi",
+    "Vilambi",
+    "Vikari",
+    "Sharvari",
+    "Plava",
+    "Shubhakrit",
+    "Shobhakrit",
+    "Krodhi",
+    "Vishvavasu",
+    "Parabhava",
+    "Plavanga",
+    "Kilaka",
+    "Saumya",
+    "Sadharana",
+    "Virodhikrit",
+    "Paridhavi",
+    "Pramadicha",
+    "Ananda",
+    "Rakshasa",
+    "Nala",
+    "Pingala",
+    "Kalayukti",
+    "Siddharthi",
+    "Raudra",
+    "Durmati",
+    "Dundubhi",
+    "Rudhirodgari",
+    "Raktakshi",
+    "Krodhana",
+    "Akshaya",
+];
+
+/// Devanagari spellings of `SAMVATSARA`
+static SAMVATSARA_DEVANAGARI: [&str; 60] = [
+    "प्रभव",
+    "विभव",
+    "शुक्ल",
+    "प्रमोद",
+    "प्रजापति",
+    "अंगिरस",
+    "श्रीमुख",
+    "भाव",
+    "युवा",
+    "धातृ",
+    "ईश्वर",
+    "बहुधान्य",
+    "प्रमाथी",
+    "विक्रम",
+    "वृष",
+    "चित्रभानु",
+    "सुभानु",
+    "तारण",
+    "पार्थिव",
+    "व्यय",
+    "सर्वजित्",
+    "सर्वधारी",
+    "विरोधी",
+    "विकृति",
+    "खर",
+    "नन्दन",
+    "विजय",
+    "जय",
+    "मन्मथ",
+    "दुर्मुखी",
+    "हेविलम्बी",
+    "विलम्बी",
+    "विकारी",
+    "शार्वरी",
+    "प्लव",
+    "शुभकृत्",
+    "शोभकृत्",
+    "क्रोधी",
+    "विश्वावसु",
+    "पराभव",
+    "प्लवंग",
+    "कीलक",
+    "सौम्य",
+    "साधारण",
+    "विरोधिकृत्",
+    "परिधावी",
+    "प्रमादी",
+    "आनन्द",
+    "राक्षस",
+    "नल",
+    "पिंगल",
+    "कालयुक्ति",
+    "सिद्धार्थी",
+    "रौद्र",
+    "दुर्मति",
+    "दुन्दुभि",
+    "रुधिरोद्गारी",
+    "रक्ताक्षी",
+    "क्रोधन",
+    "अक्षय",
+];
+
+/// IAST transliterations of `SAMVATSARA`
+static SAMVATSARA_IAST: [&str; 60] = [
+    "Prabhava",
+    "Vibhava",
+    "Śukla",
+    "Pramoda",
+    "Prajāpati",
+    "Aṅgirasa",
+    "Śrīmukha",
+    "Bhāva",
+    "Yuvā",
+    "Dhātṛ",
+    "Īśvara",
+    "Bahudhānya",
+    "Pramāthī",
+    "Vikrama",
+    "Vṛṣa",
+    "Citrabhānu",
+    "Subhānu",
+    "Tāraṇa",
+    "Pārthiva",
+    "Vyaya",
+    "Sarvajit",
+    "Sarvadhārī",
+    "Virodhī",
+    "Vikṛti",
+    "Khara",
+    "Nandana",
+    "Vijaya",
+    "Jaya",
+    "Manmatha",
+    "Durmukhī",
+    "Hevilambī",
+    "Vilambī",
+    "Vikārī",
+    "Śārvarī",
+    "Plava",
+    "Śubhakṛt",
+    "Śobhakṛt",
+    "Krodhī",
+    "Viśvāvasu",
+    "Parābhava",
+    "Plavaṅga",
+    "Kīlaka",
+    "Saumya",
+    "Sādhāraṇa",
+    "Virodhikṛt",
+    "Paridhāvī",
+    "Pramādī",
+    "Ānanda",
+    "Rākṣasa",
+    "Nala",
+    "Piṅgala",
+    "Kālayukti",
+    "Siddhārthī",
+    "Raudra",
+    "Durmati",
+    "Dundubhi",
+    "Rudhirodgārī",
+    "Raktākṣī",
+    "Krodhana",
+    "Akṣaya",
+];
+
+/// Offset aligning the Saka era with the Samvatsara cycle, so that Saka year 1945
+/// (beginning at Chaitra in 2023 CE) is Shobhakrit, the 37th year of the cycle
+const SAMVATSARA_SAKA_OFFSET: i32 = 11;
+
+/// The Saka era starts 78 years after the Common Era, at Chaitra
+const SAKA_ERA_OFFSET: i32 = 78;
+
+/// Kali years elapsed when the Saka era begins
+const KALI_SAKA_OFFSET: i32 = 3179;
+
+/// Kali Ahargana of the day before 1 January 2000, day number 1 of [`days_since_j2000`]
+///
+/// The Kali Yuga began at midnight of 17/18 February 3102 BCE in the Julian calendar,
+/// Julian Day 588465.5, which is 1863079 days before 0h of 1 January 2000.
+const KALI_AHARGANA_J2000: i32 = 1_863_078;
+
+/// The 6 Ritus (seasons) in Hindu calendar, starting from Vasanta
+/// Each Ritu spans two solar Rashis, Vasanta beginning as the Sun enters Meena
+static RITU: [&str; 6] = [
+    "Vasanta", "Grishma", "Varsha", "Sharad", "Hemanta", "Shishira",
+];
+
+/// Devanagari spellings of `RITU`
+static RITU_DEVANAGARI: [&str; 6] = ["वसन्त", "ग्रीष्म", "वर्षा", "शरद्", "हेमन्त", "शिशिर"];
+
+/// IAST transliterations of `RITU`
+static RITU_IAST: [&str; 6] = ["Vasanta", "Grīṣma", "Varṣā", "Śarad", "Hemanta", "Śiśira"];
+
 /// The 30 Tithis (lunar days) in Hindu calendar
 /// Each Tithi is defined as 12° of angular distance between the Sun and Moon
 /// The first 15 Tithis are in Shukla Paksha (waxing phase)
@@ -96,6 +544,74 @@ static TITHI: [&str; 30] = [
     "Amavasya",
 ];
 
+/// Devanagari spellings of `TITHI`
+static TITHI_DEVANAGARI: [&str; 30] = [
+    "प्रतिपदा",
+    "द्वितीया",
+    "तृतीया",
+    "चतुर्थी",
+    "पञ्चमी",
+    "षष्ठी",
+    "सप्तमी",
+    "अष्टमी",
+    "नवमी",
+    "दशमी",
+    "एकादशी",
+    "द्वादशी",
+    "त्रयोदशी",
+    "चतुर्दशी",
+    "पूर्णिमा",
+    "प्रतिपदा",
+    "द्वितीया",
+    "तृतीया",
+    "चतुर्थी",
+    "पञ्चमी",
+    "षष्ठी",
+    "सप्तमी",
+    "अष्टमी",
+    "नवमी",
+    "दशमी",
+    "एकादशी",
+    "द्वादशी",
+    "त्रयोदशी",
+    "चतुर्दशी",
+    "अमावस्या",
+];
+
+/// IAST transliterations of `TITHI`
+static TITHI_IAST: [&str; 30] = [
+    "Pratipadā",
+    "Dvitīyā",
+    "Tṛtīyā",
+    "Caturthī",
+    "Pañcamī",
+    "Ṣaṣṭhī",
+    "Saptamī",
+    "Aṣṭamī",
+    "Navamī",
+    "Daśamī",
+    "Ekādaśī",
+    "Dvādaśī",
+    "Trayodaśī",
+    "Caturdaśī",
+    "Pūrṇimā",
+    "Pratipadā",
+    "Dvitīyā",
+    "Tṛtīyā",
+    "Caturthī",
+    "Pañcamī",
+    "Ṣaṣṭhī",
+    "Saptamī",
+    "Aṣṭamī",
+    "Navamī",
+    "Daśamī",
+    "Ekādaśī",
+    "Dvādaśī",
+    "Trayodaśī",
+    "Caturdaśī",
+    "Amāvasyā",
+];
+
 /// The 11 Karanas (half lunar days) in Hindu astrology
 /// Each Karana is half of a Tithi (6° of angular distance between Sun and Moon)
 /// The first 7 Karanas (Bava to Visti) repeat 8 times in a lunar month
@@ -114,6 +630,39 @@ static KARAN: [&str; 11] = [
     "Kimstughna",
 ];
 
+/// Index of Visti, also called Bhadra, in `KARAN`
+const VISTI: usize = 6;
+
+/// Devanagari spellings of `KARAN`
+static KARAN_DEVANAGARI: [&str; 11] = [
+    "बव",
+    "बालव",
+    "कौलव",
+    "तैतिल",
+    "गर",
+    "वणिज",
+    "विष्टि",
+    "शकुनि",
+    "चतुष्पद",
+    "नाग",
+    "किंस्तुघ्न",
+];
+
+/// IAST transliterations of `KARAN`
+static KARAN_IAST: [&str; 11] = [
+    "Bava",
+    "Bālava",
+    "Kaulava",
+    "Taitila",
+    "Gara",
+    "Vaṇija",
+    "Viṣṭi",
+    "Śakuni",
+    "Catuṣpada",
+    "Nāga",
+    "Kiṃstughna",
+];
+
 /// The 27 Yogas in Hindu astrology
 /// Yoga is calculated by dividing the sum of lunar and solar longitudes by 13°20'
 /// Each Yoga represents specific energy combinations and their effects
@@ -147,6 +696,72 @@ static YOGA: [&str; 27] = [
     "Vaidhruthi",
 ];
 
+/// Devanagari spellings of `YOGA`
+static YOGA_DEVANAGARI: [&str; 27] = [
+    "विष्कम्भ",
+    "प्रीति",
+    "आयुष्मान्",
+    "सौभाग्य",
+    "शोभन",
+    "अतिगण्ड",
+    "सुकर्मा",
+    "धृति",
+    "शूल",
+    "गण्ड",
+    "वृद्धि",
+    "ध्रुव",
+    "व्याघात",
+    "हर्षण",
+    "वज्र",
+    "सिद्धि",
+    "व्यतीपात",
+    "वरीयान्",
+    "परिघ",
+    "शिव",
+    "सिद्ध",
+    "साध्य",
+    "शुभ",
+    "शुक्ल",
+    "ब्रह्म",
+    "इन्द्र",
+    "वैधृति",
+];
+
+/// IAST transliterations of `YOGA`
+static YOGA_IAST: [&str; 27] = [
+    "Viṣkambha",
+    "Prīti",
+    "Āyuṣmān",
+    "Saubhāgya",
+    "Śobhana",
+    "Atigaṇḍa",
+    "Sukarmā",
+    "Dhṛti",
+    "Śūla",
+    "Gaṇḍa",
+    "Vṛddhi",
+    "Dhruva",
+    "Vyāghāta",
+    "Harṣaṇa",
+    "Vajra",
+    "Siddhi",
+    "Vyatīpāta",
+    "Varīyān",
+    "Parigha",
+    "Śiva",
+    "Siddha",
+    "Sādhya",
+    "Śubha",
+    "Śukla",
+    "Brahma",
+    "Indra",
+    "Vaidhṛti",
+];
+
+/// Indices in `YOGA` of Vyatipata and Vaidhruthi, the Yogas during which new
+/// undertakings are avoided
+const CRITICAL_YOGAS: [usize; 2] = [16, 26];
+
 /// The 27 Nakshatras (lunar mansions) in Hindu astrology
 /// Each Nakshatra spans 13°20' of the ecliptic
 /// The Moon's position in a Nakshatra determines the lunar mansion for that time
@@ -180,316 +795,1690 @@ static NAKSHATRA: [&str; 27] = [
     "Revathi",
 ];
 
-// Global variables for storing intermediate calculations
-/// Stores the Sun's mean longitude
-static mut SUN_MEAN_LONGITUDE: f64 = 0.0;
-/// Stores the Moon's mean longitude
-static mut MOON_MEAN_LONGITUDE: f64 = 0.0;
-/// Stores the Sun's mean anomaly
-static mut SUN_MEAN_ANOMALY: f64 = 0.0;
-/// Stores the Moon's mean anomaly
-static mut MOON_MEAN_ANOMALY: f64 = 0.0;
+/// Abhijit, the intercalary 28th Nakshatra, in roman, Devanagari and IAST
+static ABHIJIT: [&str; 3] = ["Abhijit", "अभिजित्", "Abhijit"];
+
+/// Sidereal longitudes, in degrees, bounding Abhijit: the last pada of Uttara
+/// Ashada (276°40') to the first fifteenth of Sravana (280°53'20")
+const ABHIJIT_ARC: (f64, f64) = (276.0 + 40.0 / 60.0, 280.0 + 53.0 / 60.0 + 20.0 / 3600.0);
+
+/// Devanagari spellings of `NAKSHATRA`
+static NAKSHATRA_DEVANAGARI: [&str; 27] = [
+    "अश्विनी",
+    "भरणी",
+    "कृत्तिका",
+    "रोहिणी",
+    "मृगशिरा",
+    "आर्द्रा",
+    "पुनर्वसु",
+    "पुष्य",
+    "आश्लेषा",
+    "मघा",
+    "पूर्वा फाल्गुनी",
+    "उत्तरा फाल्गुनी",
+    "हस्त",
+    "चित्रा",
+    "स्वाती",
+    "विशाखा",
+    "अनुराधा",
+    "ज्येष्ठा",
+    "मूल",
+    "पूर्वाषाढा",
+    "उत्तराषाढा",
+    "श्रवण",
+    "धनिष्ठा",
+    "शतभिषा",
+    "पूर्वा भाद्रपदा",
+    "उत्तरा भाद्रपदा",
+    "रेवती",
+];
+
+/// IAST transliterations of `NAKSHATRA`
+static NAKSHATRA_IAST: [&str; 27] = [
+    "Aśvinī",
+    "Bharaṇī",
+    "Kṛttikā",
+    "Rohiṇī",
+    "Mṛgaśirā",
+    "Ārdrā",
+    "Punarvasu",
+    "Puṣya",
+    "Āśleṣā",
+    "Maghā",
+    "Pūrva Phalgunī",
+    "Uttara Phalgunī",
+    "Hasta",
+    "Citrā",
+    "Svātī",
+    "Viśākhā",
+    "Anurādhā",
+    "Jyeṣṭhā",
+    "Mūla",
+    "Pūrva Āṣāḍhā",
+    "Uttara Āṣāḍhā",
+    "Śravaṇa",
+    "Dhaniṣṭhā",
+    "Śatabhiṣā",
+    "Pūrva Bhādrapadā",
+    "Uttara Bhādrapadā",
+    "Revatī",
+];
+
+/// The Vimshottari dasha lords, which rule the Nakshatras in this order from
+/// Ashwini, repeating every 9 Nakshatras
+static NAKSHATRA_LORD: [&str; 9] = [
+    "Ketu", "Shukra", "Surya", "Chandra", "Mangala", "Rahu", "Guru", "Shani", "Budha",
+];
+
+/// The 2 Pakshas (lunar fortnights), with their Devanagari and IAST spellings
+static PAKSHA: [&str; 2] = ["Shukla", "Krishna"];
+static PAKSHA_DEVANAGARI: [&str; 2] = ["शुक्ल", "कृष्ण"];
+static PAKSHA_IAST: [&str; 2] = ["Śukla", "Kṛṣṇa"];
+
+/// The 2 Ayanas (half-years of the Sun's course), with their Devanagari and IAST spellings
+static AYANA: [&str; 2] = ["Uttarayana", "Dakshinayana"];
+static AYANA_DEVANAGARI: [&str; 2] = ["उत्तरायण", "दक्षिणायन"];
+static AYANA_IAST: [&str; 2] = ["Uttarāyaṇa", "Dakṣiṇāyana"];
 
-/// Normalizes an angle to the range [0, 360) degrees
+/// The 9 Grahas (planets) that appear as lords, with their Devanagari and IAST spellings
+static GRAHA: [&str; 9] = [
+    "Surya", "Chandra", "Mangala", "Budha", "Guru", "Shukra", "Shani", "Rahu", "Ketu",
+];
+static GRAHA_DEVANAGARI: [&str; 9] = ["सूर्य", "चन्द्र", "मंगल", "बुध", "गुरु", "शुक्र", "शनि", "राहु", "केतु"];
+static GRAHA_IAST: [&str; 9] = [
+    "Sūrya",
+    "Candra",
+    "Maṅgala",
+    "Budha",
+    "Guru",
+    "Śukra",
+    "Śani",
+    "Rāhu",
+    "Ketu",
+];
+
+/// Converts a fractional day number relative to J2000.0 into a UTC instant
 ///
 /// # Arguments
-/// * `x` - The angle to normalize in degrees
+/// * `d` - Number of days since J2000.0, as produced by `days_since_j2000`
 ///
 /// # Returns
-/// The normalized angle in degrees
-fn rev(x: f64) -> f64 {
-    x - (x / 360.0).floor() * 360.0
+/// The corresponding UTC date and time, rounded to the millisecond
+fn day_number_to_utc(d: f64) -> DateTime<Utc> {
+    // Day number 0.0 falls on 31 December 1999 at 0h UT
+    let epoch = Utc.with_ymd_and_hms(1999, 12, 31, 0, 0, 0).unwrap();
+    epoch + Duration::milliseconds((d * 86_400_000.0).round() as i64)
 }
 
-/// Calculates the Ayanamsa (precession of equinoxes) using Lahiri's method
-///
-/// The Ayanamsa is the angular difference between the Tropical and Sidereal zodiacs.
-/// This implementation uses Lahiri's method, which is officially used in Indian ephemeris.
+/// Converts a UTC instant into a fractional day number relative to J2000.0
 ///
 /// # Arguments
-/// * `d` - Number of days since J2000.0 (January 1, 2000 12:00 UT)
+/// * `utc` - The instant to convert
 ///
 /// # Returns
-/// The Ayanamsa value in degrees
-fn calc_ayanamsa(d: f64) -> f64 {
-    // Convert to Julian centuries since J2000.0
-    let t = (d + 36523.5) / 36525.0;
+/// The day number, precise to the microsecond
+fn utc_to_day_number(utc: DateTime<Utc>) -> f64 {
+    // Day number 0.0 falls on 31 December 1999 at 0h UT
+    let epoch = Utc.with_ymd_and_hms(1999, 12, 31, 0, 0, 0).unwrap();
+    let elapsed = utc - epoch;
+    match elapsed.num_microseconds() {
+        Some(microseconds) => microseconds as f64 / 86_400_000_000.0,
+        None => elapsed.num_milliseconds() as f64 / 86_400_000.0,
+    }
+}
+
+/// Reckonings of where a lunar month (Masa) ends
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MonthSystem {
+    /// Months end at Amavasya (new moon), common in South and West India
+    #[default]
+    Amanta,
+    /// Months end at Poornima (full moon), common in North India
+    Purnimanta,
+}
 
-    // Calculate the longitude of the Moon's ascending node
-    let o = 259.183275 - 1934.142008333206 * t + 0.0020777778 * t * t;
+/// How the Tithis fall on a day's sunrise and the next
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TithiStatus {
+    /// The Tithi at the next sunrise follows the one at this sunrise
+    Normal,
+    /// A Tithi begins and ends between the sunrises, so no sunrise is named by it
+    Kshaya,
+    /// The same Tithi is in effect at both sunrises, so two days are named by it
+    Vriddhi,
+}
 
-    // Calculate the mean longitude of the Sun
-    let l = 279.696678 + 36000.76892 * t + 0.0003025 * t * t;
+/// The Tithis in effect at a day's sunrise and at the next, by name
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TithiSunrises {
+    /// Whether a Tithi is skipped or repeated between the sunrises
+    pub status: TithiStatus,
+    /// Tithi in effect at the day's sunrise
+    pub at_sunrise: &'static str,
+    /// Tithi in effect at the next day's sunrise
+    pub at_next_sunrise: &'static str,
+    /// Tithi that begins and ends between the sunrises, when it is kshaya
+    pub skipped: Option<&'static str>,
+}
 
-    // Calculate Ayanamsa using Lahiri's formula
-    let mut ayan =
-        17.23 * (o * D2R).sin() + 1.27 * (l * 2.0 * D2R).sin() - (5025.64 + 1.11 * t) * t;
-    ayan = (ayan - 80861.27) / 3600.0; // Convert to degrees
-    ayan
+/// Number of Nakshatras the zodiac is divided into
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
+pub enum NakshatraSystem {
+    /// The 27 equal Nakshatras of 13°20'
+    #[default]
+    TwentySeven,
+    /// The 27 Nakshatras with Abhijit inserted between Uttara Ashada and Sravana
+    TwentyEight,
 }
 
-/// Calculates the Sun's true geocentric longitude
-///
-/// This function implements a simplified VSOP87 algorithm to calculate
-/// the Sun's position in the ecliptic coordinate system.
+impl TryFrom<u8> for NakshatraSystem {
+    type Error = String;
+
+    fn try_from(count: u8) -> Result<Self, Self::Error> {
+        match count {
+            27 => Ok(NakshatraSystem::TwentySeven),
+            28 => Ok(NakshatraSystem::TwentyEight),
+            other => Err(format!("nakshatra_system must be 27 or 28, got {}", other)),
+        }
+    }
+}
+
+impl From<NakshatraSystem> for u8 {
+    fn from(system: NakshatraSystem) -> u8 {
+        match system {
+            NakshatraSystem::TwentySeven => 27,
+            NakshatraSystem::TwentyEight => 28,
+        }
+    }
+}
+
+#[cfg(feature = "openapi")]
+impl utoipa::PartialSchema for NakshatraSystem {
+    /// Documents the system as the integer it is written as
+    fn schema() -> utoipa::openapi::RefOr<utoipa::openapi::schema::Schema> {
+        utoipa::openapi::ObjectBuilder::new()
+            .schema_type(utoipa::openapi::schema::Type::Integer)
+            .enum_values(Some([27, 28]))
+            .default(Some(27.into()))
+            .into()
+    }
+}
+
+#[cfg(feature = "openapi")]
+impl utoipa::ToSchema for NakshatraSystem {}
+
+/// Scripts in which element names can be written
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Script {
+    /// The plain romanization used by the calculations (e.g. Prathame)
+    #[default]
+    Roman,
+    /// Devanagari script (e.g. प्रतिपदा)
+    Devanagari,
+    /// IAST transliteration with diacritics (e.g. Pratipadā)
+    Iast,
+}
+
+/// Kinds of element names that have Devanagari and IAST variants
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NameKind {
+    Tithi,
+    Paksha,
+    Nakshatra,
+    Yoga,
+    Karana,
+    Rashi,
+    Vaara,
+    Masa,
+    Samvatsara,
+    Ayana,
+    Ritu,
+    /// A planet ruling a Nakshatra or Rashi
+    Graha,
+}
+
+impl NameKind {
+    /// The parallel roman, Devanagari and IAST tables for this kind of name
+    fn tables(self) -> [&'static [&'static str]; 3] {
+        match self {
+            NameKind::Tithi => [&TITHI, &TITHI_DEVANAGARI, &TITHI_IAST],
+            NameKind::Paksha => [&PAKSHA, &PAKSHA_DEVANAGARI, &PAKSHA_IAST],
+            NameKind::Nakshatra => [&NAKSHATRA, &NAKSHATRA_DEVANAGARI, &NAKSHATRA_IAST],
+            NameKind::Yoga => [&YOGA, &YOGA_DEVANAGARI, &YOGA_IAST],
+            NameKind::Karana => [&KARAN, &KARAN_DEVANAGARI, &KARAN_IAST],
+            NameKind::Rashi => [&RASHI, &RASHI_DEVANAGARI, &RASHI_IAST],
+            NameKind::Vaara => [&VAARA, &VAARA_DEVANAGARI, &VAARA_IAST],
+            NameKind::Masa => [&MASA, &MASA_DEVANAGARI, &MASA_IAST],
+            NameKind::Samvatsara => [&SAMVATSARA, &SAMVATSARA_DEVANAGARI, &SAMVATSARA_IAST],
+            NameKind::Ayana => [&AYANA, &AYANA_DEVANAGARI, &AYANA_IAST],
+            NameKind::Ritu => [&RITU, &RITU_DEVANAGARI, &RITU_IAST],
+            NameKind::Graha => [&GRAHA, &GRAHA_DEVANAGARI, &GRAHA_IAST],
+        }
+    }
+}
+
+/// Writes an element name, as returned by `calculate_panchanga`, in a script
 ///
 /// # Arguments
-/// * `d` - Number of days since J2000.0 (January 1, 2000 12:00 UT)
+/// * `kind` - The kind of element the name belongs to
+/// * `roman` - The roman name
+/// * `script` - The script to write it in
 ///
 /// # Returns
-/// The Sun's true geocentric longitude in degrees
-fn sun_long(d: f64) -> f64 {
-    // Calculate the Sun's mean orbital elements
-    let perihelion_longitude = 282.9404 + 4.70935e-5 * d; // Argument of perihelion
-    // let semi_major_axis = 1.000000;  // Semi-major axis (in AU)
-    let orbital_eccentricity = 0.016709 - 1.151e-9 * d; // Eccentricity
-    let mean_anomaly = rev(356.0470 + 0.9856002585 * d); // Mean anomaly
+/// The name in the requested script, or the roman name unchanged if it is not
+/// one of the names of that kind
+pub fn name_in_script(kind: NameKind, roman: &str, script: Script) -> String {
+    let [roman_names, devanagari_names, iast_names] = kind.tables();
+    let (names, abhijit) = match script {
+        Script::Roman => return roman.to_string(),
+        Script::Devanagari => (devanagari_names, ABHIJIT[1]),
+        Script::Iast => (iast_names, ABHIJIT[2]),
+    };
 
-    unsafe {
-        SUN_MEAN_ANOMALY = mean_anomaly; // Store mean anomaly for later use
-        SUN_MEAN_LONGITUDE = perihelion_longitude + mean_anomaly; // Store mean longitude
+    // Abhijit only exists in the 28-Nakshatra system, outside the tables
+    if kind == NameKind::Nakshatra && roman == ABHIJIT[0] {
+        return abhijit.to_string();
     }
 
-    // Solve Kepler's equation iteratively
-    let mean_anomaly_radians = mean_anomaly * D2R;
-    let eccentric_anomaly = mean_anomaly
-        + R2D
-            * orbital_eccentricity
-            * mean_anomaly_radians.sin()
-            * (1.0 + orbital_eccentricity * mean_anomaly_radians.cos());
+    // Tables such as TITHI repeat names, whose variants repeat alike
+    roman_names
+        .iter()
+        .position(|name| *name == roman)
+        .map_or_else(|| roman.to_string(), |index| names[index].to_string())
+}
 
-    // Convert to rectangular coordinates
-    let eccentric_anomaly_radians = eccentric_anomaly * D2R;
-    let x_coord = eccentric_anomaly_radians.cos() - orbital_eccentricity;
-    let y_coord = eccentric_anomaly_radians.sin()
-        * (1.0 - orbital_eccentricity * orbital_eccentricity).sqrt();
+/// Altitude in degrees of the Sun's centre at sunrise and sunset
+///
+/// The upper limb, a semidiameter of 16′ above the centre, then touches the
+/// horizon, which refraction lowers by 34′.
+pub const SUNRISE_ALTITUDE: f64 = -0.833;
 
-    // Calculate true anomaly and return true longitude
-    let true_anomaly = rev(R2D * y_coord.atan2(x_coord));
-    rev(true_anomaly + perihelion_longitude)
-}
+/// Equatorial radius of the Earth in kilometres
+const EARTH_RADIUS_KM: f64 = 6378.14;
 
-/// Calculates the Moon's true geocentric longitude
+/// Calculates local sunrise and sunset for an observer
 ///
-/// This function implements a simplified ELP2000 algorithm for lunar position calculation.
-/// It accounts for various periodic perturbations in the Moon's orbit.
+/// This uses the standard solar declination / hour-angle method: the Sun's
+/// position is evaluated at local noon, its declination and right ascension
+/// give the time of transit and the hour angle at which the Sun's upper limb
+/// appears on the horizon, with its centre [`SUNRISE_ALTITUDE`] below it.
 ///
 /// # Arguments
-/// * `d` - Number of days since J2000.0 (January 1, 2000 12:00 UT)
+/// * `day` - Day of month
+/// * `month` - Month number (1-12)
+/// * `year` - Year
+/// * `latitude` - Observer latitude in degrees (north positive)
+/// * `longitude` - Observer longitude in degrees (east positive)
+/// * `timezone_offset` - Time zone offset from GMT in hours
 ///
 /// # Returns
-/// The Moon's true geocentric longitude in degrees
-fn moon_long(d: f64) -> f64 {
-    // Calculate the Moon's mean orbital elements
-    let ascending_node_longitude = 125.1228 - 0.0529538083 * d; // Longitude of ascending node
-    let orbital_inclination = 5.1454; // Inclination to ecliptic
-    let perigee_argument = rev(318.0634 + 0.1643573223 * d); // Argument of perigee
-    let semi_major_axis = 60.2666; // Semi-major axis (Earth radii)
-    let orbital_eccentricity = 0.054900; // Eccentricity
-    let mean_anomaly = rev(115.3654 + 13.0649929509 * d); // Mean anomaly
+/// Local sunrise and sunset as decimal hours, or `None` when the Sun does not
+/// cross the horizon that day (polar day or polar night)
+pub fn sunrise_sunset(
+    day: i32,
+    month: i32,
+    year: i32,
+    latitude: f64,
+    longitude: f64,
+    timezone_offset: f64,
+) -> Option<(f64, f64)> {
+    // Days since J2000.0 at local noon on the observer's meridian
+    let d = days_since_j2000(day, month, year) as f64 + 0.5 - longitude / 360.0;
 
-    unsafe {
-        MOON_MEAN_ANOMALY = mean_anomaly; // Store mean anomaly for later use
-        MOON_MEAN_LONGITUDE = ascending_node_longitude + perigee_argument + mean_anomaly; // Store mean longitude
-    }
+    let sun = sun_long(d);
+    let obliquity = 23.4393 - 3.563e-7 * d; // Obliquity of the ecliptic
 
-    // Solve Kepler's equation iteratively for eccentric anomaly
-    let mut anomaly_radians = mean_anomaly * D2R;
-    let mut eccentric_anomaly = mean_anomaly
-        + R2D
-            * orbital_eccentricity
-            * anomaly_radians.sin()
-            * (1.0 + orbital_eccentricity * anomaly_radians.cos());
-    let mut eccentric_correction;
+    // Convert the Sun's ecliptic longitude to equatorial coordinates
+    let longitude_radians = sun.longitude * D2R;
+    let obliquity_radians = obliquity * D2R;
+    let right_ascension =
+        rev(R2D
+            * (obliquity_radians.cos() * longitude_radians.sin()).atan2(longitude_radians.cos()));
+    let declination = R2D * (obliquity_radians.sin() * longitude_radians.sin()).asin();
 
-    loop {
-        anomaly_radians = eccentric_anomaly * D2R;
-        eccentric_correction = eccentric_anomaly
-            - (eccentric_anomaly
-                - R2D * orbital_eccentricity * anomaly_radians.sin()
-                - mean_anomaly)
-                / (1.0 - orbital_eccentricity * anomaly_radians.cos());
-        if (eccentric_anomaly - eccentric_correction).abs() <= 0.005 {
-            break;
-        }
-        eccentric_anomaly = eccentric_correction;
-    }
+    // Local sidereal time at noon gives the UT of the Sun's transit
+    let sidereal_time = rev(rev(sun.mean_longitude + 180.0) + 180.0 + longitude);
+    let hour_angle_at_noon = rev(sidereal_time - right_ascension + 180.0) - 180.0;
+    let transit = 12.0 - hour_angle_at_noon / 15.0;
 
-    // Convert to rectangular coordinates in the orbital plane
-    anomaly_radians = eccentric_anomaly * D2R;
-    let orbital_x = semi_major_axis * (anomaly_radians.cos() - orbital_eccentricity);
-    let orbital_y = semi_major_axis
-        * (1.0 - orbital_eccentricity * orbital_eccentricity).sqrt()
-        * anomaly_radians.sin();
-
-    let orbital_radius = (orbital_x * orbital_x + orbital_y * orbital_y).sqrt(); // Distance to Moon
-    let true_anomaly = rev(R2D * orbital_y.atan2(orbital_x)); // True anomaly
-
-    // Convert to ecliptic coordinates
-    let node_radians = ascending_node_longitude * D2R;
-    let argument_radians = (true_anomaly + perigee_argument) * D2R;
-    let inclination_radians = orbital_inclination * D2R;
-
-    let ecliptic_x = orbital_radius
-        * (node_radians.cos() * argument_radians.cos()
-            - node_radians.sin() * argument_radians.sin() * inclination_radians.cos());
-    let ecliptic_y = orbital_radius
-        * (node_radians.sin() * argument_radians.cos()
-            + node_radians.cos() * argument_radians.sin() * inclination_radians.cos());
-    // let ecliptic_z = orbital_radius * argument_radians.sin() * inclination_radians.sin();
-
-    unsafe {
-        let mean_elongation = MOON_MEAN_LONGITUDE - SUN_MEAN_LONGITUDE; // Mean elongation
-        let argument_of_latitude = MOON_MEAN_LONGITUDE - ascending_node_longitude; // Argument of latitude
-
-        // Calculate longitude with periodic perturbations
-        let mut ecliptic_longitude = R2D * ecliptic_y.atan2(ecliptic_x);
-
-        // Apply major periodic perturbations
-        ecliptic_longitude += -1.274 * ((MOON_MEAN_ANOMALY - 2.0 * mean_elongation) * D2R).sin(); // Evection
-        ecliptic_longitude += 0.658 * ((2.0 * mean_elongation) * D2R).sin(); // Variation
-        ecliptic_longitude += -0.186 * (SUN_MEAN_ANOMALY * D2R).sin(); // Yearly equation
-        ecliptic_longitude +=
-            -0.059 * ((2.0 * MOON_MEAN_ANOMALY - 2.0 * mean_elongation) * D2R).sin();
-        ecliptic_longitude +=
-            -0.057 * ((MOON_MEAN_ANOMALY - 2.0 * mean_elongation + SUN_MEAN_ANOMALY) * D2R).sin();
-        ecliptic_longitude += 0.053 * ((MOON_MEAN_ANOMALY + 2.0 * mean_elongation) * D2R).sin();
-        ecliptic_longitude += 0.046 * ((2.0 * mean_elongation - SUN_MEAN_ANOMALY) * D2R).sin();
-        ecliptic_longitude += 0.041 * ((MOON_MEAN_ANOMALY - SUN_MEAN_ANOMALY) * D2R).sin();
-        ecliptic_longitude += -0.035 * (mean_elongation * D2R).sin();
-        ecliptic_longitude += -0.031 * ((MOON_MEAN_ANOMALY + SUN_MEAN_ANOMALY) * D2R).sin();
-        ecliptic_longitude +=
-            -0.015 * ((2.0 * argument_of_latitude - 2.0 * mean_elongation) * D2R).sin();
-        ecliptic_longitude += 0.011 * ((MOON_MEAN_ANOMALY - 4.0 * mean_elongation) * D2R).sin();
-
-        rev(ecliptic_longitude)
+    // Hour angle at which the Sun's upper limb is on the horizon
+    let (latitude, declination) = (latitude * D2R, declination * D2R);
+    let cos_hour_angle = ((SUNRISE_ALTITUDE * D2R).sin() - latitude.sin() * declination.sin())
+        / (latitude.cos() * declination.cos());
+    if !(-1.0..=1.0).contains(&cos_hour_angle) {
+        return None;
     }
+    let half_day = R2D * cos_hour_angle.acos() / 15.0;
+
+    Some((
+        transit - half_day + timezone_offset,
+        transit + half_day + timezone_offset,
+    ))
 }
 
-/// Calculates all elements of Panchanga (Hindu astrological calendar)
+/// Calculates how far the Moon's upper limb is above the horizon at an instant
 ///
-/// This function computes the five main elements of Panchanga:
-/// - Tithi (lunar day)
-/// - Nakshatra (lunar mansion)
-/// - Yoga (luni-solar day)
-/// - Karana (half lunar day)
-/// - Rashi (zodiac sign)
+/// The Moon's ecliptic longitude and latitude are turned into equatorial
+/// coordinates, whose hour angle at the local sidereal time gives its geocentric
+/// altitude. The Moon is risen when that altitude exceeds 0.7275 times its
+/// horizontal parallax less 34′ of refraction and its 15.5′ semidiameter (Meeus,
+/// Astronomical Algorithms, chapter 15): seen from the surface the Moon stands up to
+/// a degree lower than from the Earth's centre.
+///
+/// # Returns
+/// The altitude above the rising horizon in degrees, negative when the Moon is down
+fn moon_above_horizon(d: f64, latitude: f64, longitude: f64) -> f64 {
+    let obliquity = (23.4393 - 3.563e-7 * d) * D2R; // Obliquity of the ecliptic
+    let (ecliptic_longitude, ecliptic_latitude) = (moon_long(d) * D2R, moon_latitude(d) * D2R);
+    let right_ascension = (ecliptic_longitude.sin() * obliquity.cos()
+        - ecliptic_latitude.tan() * obliquity.sin())
+    .atan2(ecliptic_longitude.cos());
+    let declination = (ecliptic_latitude.sin() * obliquity.cos()
+        + ecliptic_latitude.cos() * obliquity.sin() * ecliptic_longitude.sin())
+    .asin();
+
+    // Greenwich mean sidereal time, with the day number counted from 2000 January 0.0
+    let sidereal_time = 280.46061837 + 360.98564736629 * (d - 1.5) + longitude;
+    let hour_angle = sidereal_time * D2R - right_ascension;
+    let latitude = latitude * D2R;
+    let altitude = R2D
+        * (latitude.sin() * declination.sin()
+            + latitude.cos() * declination.cos() * hour_angle.cos())
+        .asin();
+
+    let parallax = R2D * (EARTH_RADIUS_KM / moon_distance(d)).asin();
+    altitude - (0.7275 * parallax - 0.5667)
+}
+
+/// Calculates local moonrise and moonset for an observer
+///
+/// The Moon moves about 13° a day against the stars, so unlike the Sun its
+/// crossings of the horizon are searched for through the local day, from midnight
+/// to midnight, with its position recomputed as it goes. It rises about 50 minutes
+/// later each day, so once a month the day has no moonrise, and once no moonset.
 ///
 /// # Arguments
-/// * `dd` - Day of month
-/// * `mm` - Month number (1-12)
-/// * `yy` - Year
-/// * `hr` - Hour in local time
-/// * `zhr` - Time zone offset from GMT in hours
+/// * `day` - Day of month
+/// * `month` - Month number (1-12)
+/// * `year` - Year
+/// * `latitude` - Observer latitude in degrees (north positive)
+/// * `longitude` - Observer longitude in degrees (east positive)
+/// * `timezone_offset` - Time zone offset from GMT in hours
 ///
 /// # Returns
-/// A Panchanga struct containing all calculated elements
-pub fn calculate_panchanga(
+/// Local moonrise and moonset as decimal hours from midnight, each `None` when it
+/// does not happen that day
+pub fn moonrise_moonset(
     day: i32,
     month: i32,
     year: i32,
-    hour: f64,
+    latitude: f64,
+    longitude: f64,
     timezone_offset: f64,
-) -> Panchanga {
-    let mut panchanga_data = Panchanga {
-        // current_day: String::new(),
-        current_yoga: String::new(),
-        current_nakshatra: String::new(),
-        current_tithi: String::new(),
-        current_karana: String::new(),
-        current_paksha: String::new(),
-        current_rashi: String::new(),
-    };
-
-    // Calculate Julian Day number relative to J2000.0
-    let days_since_j2000 =
-        (367 * year - 7 * (year + (month + 9) / 12) / 4 + 275 * month / 9 + day - 730530) as f64;
+) -> (Option<f64>, Option<f64>) {
+    const AFTER_CROSSING: f64 = 1.0 / 1440.0; // One minute
 
-    // Calculate basic astronomical values
-    let ayanamsa = calc_ayanamsa(days_since_j2000);
-    let sun_longitude = sun_long(days_since_j2000 + ((hour - timezone_offset) / 24.0));
-    let moon_longitude = moon_long(days_since_j2000 + ((hour - timezone_offset) / 24.0));
+    let midnight = days_since_j2000(day, month, year) as f64 - timezone_offset / 24.0;
+    let risen = |d: f64| usize::from(moon_above_horizon(d, latitude, longitude) >= 0.0);
 
-    // Calculate Tithi (lunar day)
-    let mut adjusted_moon_longitude = moon_longitude
-        + if moon_longitude < sun_longitude {
-            360.0
+    let (mut moonrise, mut moonset) = (None, None);
+    let mut d = midnight;
+    while let Some(crossing) = find_transition(risen, d, true, midnight + 1.0 - d) {
+        if crossing >= midnight + 1.0 {
+            break;
+        }
+        let hours = (crossing - midnight) * 24.0;
+        d = crossing + AFTER_CROSSING;
+        if risen(d) == 1 {
+            moonrise.get_or_insert(hours);
         } else {
-            0.0
-        };
-    let adjusted_sun_longitude = sun_longitude;
-    let mut tithi_index = ((adjusted_moon_longitude - adjusted_sun_longitude) / 12.0) as usize; // Each Tithi = 12 degrees
-
-    panchanga_data.current_tithi = TITHI[tithi_index].to_string();
-    panchanga_data.current_paksha = if tithi_index <= 14 {
-        "Shukla"
-    } else {
-        "Krishna"
+            moonset.get_or_insert(hours);
+        }
     }
-    .to_string();
+    (moonrise, moonset)
+}
 
-    // Calculate Nakshatra (lunar mansion)
-    adjusted_moon_longitude = rev(moon_longitude + ayanamsa);
-    panchanga_data.current_nakshatra =
-        NAKSHATRA[(adjusted_moon_longitude * 6.0 / 80.0) as usize].to_string(); // Each Nakshatra = 13°20'
-
-    // Calculate Yoga (luni-solar day)
-    adjusted_moon_longitude = moon_longitude + ayanamsa;
-    let adjusted_sun_longitude = sun_longitude + ayanamsa;
-    panchanga_data.current_yoga = YOGA
-        [(rev(adjusted_moon_longitude + adjusted_sun_longitude) * 6.0 / 80.0) as usize]
-        .to_string(); // Each Yoga = 13°20'
-
-    // Calculate Karana (half lunar day)
-    adjusted_moon_longitude = moon_longitude
-        + if moon_longitude < sun_longitude {
-            360.0
-        } else {
-            0.0
-        };
-    let adjusted_sun_longitude = sun_longitude;
-    tithi_index = ((adjusted_moon_longitude - adjusted_sun_longitude) / 6.0) as usize; // Each Karana = 6 degrees
+/// Finds the instant at which a segment-valued function of time changes value
+///
+/// Starting from `d`, time is stepped by one hour in the given direction until
+/// `segment` returns a different value, and the crossing is then narrowed down
+/// by bisection to under a second.
+///
+/// # Arguments
+/// * `segment` - Maps a day number to the index of the arc it falls in
+/// * `d` - Number of days since J2000.0 to start searching from
+/// * `forward` - Search forward in time when `true`, backward when `false`
+/// * `max_days` - How far to search before giving up
+///
+/// # Returns
+/// The day number of the transition, or `None` if none occurs within `max_days`
+fn find_transition<F: Fn(f64) -> usize>(
+    segment: F,
+    d: f64,
+    forward: bool,
+    max_days: f64,
+) -> Option<f64> {
+    const STEP: f64 = 1.0 / 24.0; // One hour
+    const PRECISION: f64 = 1.0 / 86_400.0; // One second
 
-    // Apply special rules for Karana calculation
-    if tithi_index == 0 {
-        tithi_index = 10;
-    }
-    if tithi_index >= 57 {
-        tithi_index -= 50;
+    let direction = if forward { 1.0 } else { -1.0 };
+    let current = segment(d);
+
+    // Step until the segment changes to bracket the transition
+    let mut inside = d;
+    let mut outside = d;
+    loop {
+        if (outside - d).abs() >= max_days {
+            return None;
+        }
+        outside += direction * STEP;
+        if segment(outside) != current {
+            break;
+        }
+        inside = outside;
     }
-    if tithi_index > 0 && tithi_index < 57 {
-        tithi_index = (tithi_index - 1) - ((tithi_index - 1) / 7 * 7);
+
+    // Bisect the bracket down to the requested precision
+    while (outside - inside).abs() > PRECISION {
+        let middle = (inside + outside) / 2.0;
+        if segment(middle) == current {
+            inside = middle;
+        } else {
+            outside = middle;
+        }
     }
-    panchanga_data.current_karana = KARAN[tithi_index].to_string();
 
-    // Calculate Rashi (zodiac sign)
-    adjusted_moon_longitude = rev(moon_longitude + ayanamsa);
-    panchanga_data.current_rashi = RASHI[(adjusted_moon_longitude / 30.0) as usize].to_string(); // Each Rashi = 30 degrees
+    Some((inside + outside) / 2.0)
+}
 
-    panchanga_data
+/// Calculates the Moon–Sun elongation in degrees at an instant
+fn elongation_at(d: f64) -> f64 {
+    let sun = sun_long(d);
+    rev(moon_long(d) - sun.longitude)
 }
 
-pub fn parse_time(time_str: &str) -> Result<(f64, i32), &'static str> {
-    let parts: Vec<&str> = time_str.split(':').collect();
-    if parts.len() != 2 {
-        return Err("Invalid time format");
+/// Calculates the index (0-29) of the Tithi in effect at an instant
+fn tithi_index_at(d: f64) -> usize {
+    arc_index(elongation_at(d), 30)
+}
+
+/// Calculates the index (0-26) of the Yoga in effect at an instant
+fn yoga_index_at(d: f64, ayanamsa_system: Ayanamsa) -> usize {
+    let ayanamsa = calc_ayanamsa(d, ayanamsa_system);
+    arc_index(sun_long(d).longitude + moon_long(d) + 2.0 * ayanamsa, 27)
+}
+
+/// Calculates the index (0-26) of the Nakshatra in effect at an instant
+fn nakshatra_index_at(d: f64, ayanamsa_system: Ayanamsa) -> usize {
+    arc_index(moon_long(d) + calc_ayanamsa(d, ayanamsa_system), 27)
+}
+
+/// Calculates the half-Tithi (0-59) of the month in effect at an instant, each
+/// holding one Karana
+fn half_tithi_index_at(d: f64) -> usize {
+    arc_index(elongation_at(d), 60)
+}
+
+/// Calculates the Paksha in effect at an instant, 0 for Shukla and 1 for Krishna
+fn paksha_index_at(d: f64) -> usize {
+    arc_index(elongation_at(d), 2)
+}
+
+/// Finds the new moon (Amavasya ending) preceding or following an instant
+///
+/// Searching for Paksha changes yields full and new moons alternately, so at
+/// most two changes have to be found.
+///
+/// # Arguments
+/// * `d` - Number of days since J2000.0 to search from
+/// * `forward` - Find the following new moon when `true`, the preceding one when `false`
+///
+/// # Returns
+/// The day number of the new moon
+fn new_moon(d: f64, forward: bool) -> Option<f64> {
+    const NUDGE: f64 = 1.0 / 1440.0; // One minute, to step past a found crossing
+
+    let mut from = d;
+    for _ in 0..2 {
+        let crossing = find_transition(paksha_index_at, from, forward, 31.0)?;
+        // Elongation is near 0° at a new moon and near 180° at a full moon
+        if rev(elongation_at(crossing) + 90.0) < 180.0 {
+            return Some(crossing);
+        }
+        from = crossing + if forward { NUDGE } else { -NUDGE };
     }
+    None
+}
+
+/// Calculates the Amanta Masa in effect at an instant
+///
+/// The month begun by the preceding new moon is named after the Rashi the Sun
+/// enters during it. When the Sun enters no new Rashi before the following new
+/// moon, the month is Adhika and takes the name of the month that follows it.
+///
+/// # Arguments
+/// * `d` - Number of days since J2000.0
+/// * `ayanamsa_system` - The Ayanamsa used for the Sun's sidereal Rashi
+///
+/// # Returns
+/// The Masa index (0 for Chaitra) and whether it is an Adhika month
+fn masa_at(d: f64, ayanamsa_system: Ayanamsa) -> Option<(usize, bool)> {
+    let start = new_moon(d, false)?;
+    let end = new_moon(d, true)?;
+    let start_rashi = solar_rashi_at(start, ayanamsa_system);
 
-    let hours: f64 = parts[0].parse().map_err(|_| "Invalid hours")?;
-    let minutes: i32 = parts[1].parse().map_err(|_| "Invalid minutes")?;
+    Some((
+        (start_rashi + 1) % 12,
+        start_rashi == solar_rashi_at(end, ayanamsa_system),
+    ))
+}
 
-    Ok((hours, minutes))
+/// Calculates the index (0-11) of the Sun's sidereal Rashi at an instant
+fn solar_rashi_at(d: f64, ayanamsa_system: Ayanamsa) -> usize {
+    arc_index(
+        sun_long(d).longitude + calc_ayanamsa(d, ayanamsa_system),
+        12,
+    )
 }
 
-pub fn parse_date(date_str: &str) -> Result<(f64, i32, i32), &'static str> {
-    let parts: Vec<&str> = date_str.split('/').collect();
-    if parts.len() != 3 {
-        return Err("Invalid date format");
+/// Calculates the index (0-11) of the Moon's sidereal Rashi at an instant
+fn chandra_rashi_at(d: f64, ayanamsa_system: Ayanamsa) -> usize {
+    arc_index(moon_long(d) + calc_ayanamsa(d, ayanamsa_system), 12)
+}
+
+/// Finds the Sankranti, the Sun's entry into a new sidereal Rashi, on a local date
+///
+/// The Sun moves about 1° a day, so at most one Sankranti falls on a date. The
+/// instant is found by bisection on the Sun's sidereal longitude crossing a 30°
+/// boundary, to within a second.
+///
+/// # Arguments
+/// * `day` - Day of month
+/// * `month` - Month number (1-12)
+/// * `year` - Year
+/// * `timezone_offset` - Time zone offset from GMT in hours, bounding the local date
+/// * `ayanamsa_system` - The Ayanamsa used for the Sun's sidereal longitude
+///
+/// # Returns
+/// The Rashi entered and the UTC instant of the ingress, or `None` when the Sun
+/// stays in one Rashi all day
+pub fn sankranti_on(
+    day: i32,
+    month: i32,
+    year: i32,
+    timezone_offset: f64,
+    ayanamsa_system: Ayanamsa,
+) -> Option<(String, DateTime<Utc>)> {
+    let midnight = days_since_j2000(day, month, year) as f64 - timezone_offset / 24.0;
+    let rashi_at = |d: f64| solar_rashi_at(d, ayanamsa_system);
+
+    let ingress = find_transition(rashi_at, midnight, true, 1.0)?;
+    if ingress >= midnight + 1.0 {
+        return None;
     }
 
-    let day: f64 = parts[0].parse().map_err(|_| "Invalid day")?;
-    let month: i32 = parts[1].parse().map_err(|_| "Invalid month")?;
-    let year: i32 = parts[2].parse().map_err(|_| "Invalid year")?;
+    let entered = rashi_at(midnight + 1.0);
+    Some((RASHI[entered].to_string(), day_number_to_utc(ingress)))
+}
+
+/// Calculates the Lagna (ascendant), the point of the ecliptic rising in the east
+///
+/// The local sidereal time gives the right ascension of the meridian (RAMC), from
+/// which the rising degree follows with the obliquity of the ecliptic and the
+/// observer's latitude.
+///
+/// # Arguments
+/// * `utc` - The instant
+/// * `latitude` - Observer latitude in degrees (north positive)
+/// * `longitude` - Observer longitude in degrees (east positive)
+/// * `ayanamsa_system` - The Ayanamsa used to make the ascendant sidereal
+///
+/// # Returns
+/// The Rashi rising and the sidereal longitude (0-360) of the ascendant in degrees
+pub fn lagna(
+    utc: DateTime<Utc>,
+    latitude: f64,
+    longitude: f64,
+    ayanamsa_system: Ayanamsa,
+) -> (String, f64) {
+    let d = utc_to_day_number(utc);
+
+    // Greenwich mean sidereal time, counted from J2000.0 (day number 1.5)
+    let t = (d - 1.5) / 36525.0;
+    let sidereal_time = 280.46061837 + 360.98564736629 * (d - 1.5) + 0.000387933 * t * t;
+    let ramc = rev(sidereal_time + longitude) * D2R;
+    let obliquity = (23.4393 - 3.563e-7 * d) * D2R; // Obliquity of the ecliptic
+
+    let tropical = R2D
+        * ramc
+            .cos()
+            .atan2(-(ramc.sin() * obliquity.cos() + (latitude * D2R).tan() * obliquity.sin()));
+    let sidereal = rev(tropical + calc_ayanamsa(d, ayanamsa_system));
+    (RASHI[arc_index(sidereal, 12)].to_string(), sidereal)
+}
+
+/// Calculates the lunar month (Masa) of a date in the Amanta reckoning
+///
+/// The month is evaluated at local noon using Lahiri's Ayanamsa.
+///
+/// # Arguments
+/// * `day` - Day of month
+/// * `month` - Month number (1-12)
+/// * `year` - Year
+/// * `timezone_offset` - Time zone offset from GMT in hours
+///
+/// # Returns
+/// The name of the Masa and whether it is an Adhika (intercalary) month
+pub fn lunar_month(day: i32, month: i32, year: i32, timezone_offset: f64) -> (String, bool) {
+    let noon = days_since_j2000(day, month, year) as f64 + (12.0 - timezone_offset) / 24.0;
+    let (index, adhika) = masa_at(noon, Ayanamsa::Lahiri).unwrap_or((0, false));
+    (MASA[index].to_string(), adhika)
+}
+
+/// Calculates when the Tithi in effect at the given local time began and ends
+///
+/// The boundaries are the instants at which the Moon–Sun elongation crosses
+/// the 12° multiples on either side of the current Tithi. A Tithi lasts
+/// between roughly 19 and 27 hours, so it regularly spans a day boundary.
+///
+/// # Arguments
+/// * `day` - Day of month
+/// * `month` - Month number (1-12)
+/// * `year` - Year
+/// * `hour` - Hour in local time
+/// * `timezone_offset` - Time zone offset from GMT in hours
+///
+/// # Returns
+/// The UTC start and end of the current Tithi
+pub fn tithi_bounds(
+    day: i32,
+    month: i32,
+    year: i32,
+    hour: f64,
+    timezone_offset: f64,
+) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let instant = days_since_j2000(day, month, year) as f64 + (hour - timezone_offset) / 24.0;
+
+    let start = find_transition(tithi_index_at, instant, false, 2.0)?;
+    let end = find_transition(tithi_index_at, instant, true, 2.0)?;
+
+    Some((day_number_to_utc(start), day_number_to_utc(end)))
+}
+
+/// Finds the Tithis in effect at a day's sunrise and at the next, and whether one is
+/// skipped (kshaya) or repeated (vriddhi) between them
+///
+/// A day is named by the Tithi in effect at its sunrise. A Tithi lasts between roughly
+/// 19 and 27 hours against about 24 from one sunrise to the next, so a short one may
+/// begin after a sunrise and end before the next, naming no day, and a long one may
+/// begin before a sunrise and end after the next, naming two.
+///
+/// # Arguments
+/// * `day` - Day of month
+/// * `month` - Month number (1-12)
+/// * `year` - Year
+/// * `sunrise` - Local sunrise on the date in decimal hours
+/// * `next_sunrise` - Local sunrise on the next date in decimal hours
+/// * `timezone_offset` - Time zone offset from GMT in hours
+///
+/// # Returns
+/// The names of the Tithis at both sunrises and of the one skipped between them
+pub fn tithi_sunrises(
+    day: i32,
+    month: i32,
+    year: i32,
+    sunrise: f64,
+    next_sunrise: f64,
+    timezone_offset: f64,
+) -> TithiSunrises {
+    let midnight = days_since_j2000(day, month, year) as f64 - timezone_offset / 24.0;
+    let at_sunrise = tithi_index_at(midnight + sunrise / 24.0);
+    let at_next_sunrise = tithi_index_at(midnight + 1.0 + next_sunrise / 24.0);
+
+    let (status, skipped) = match (at_next_sunrise + 30 - at_sunrise) % 30 {
+        0 => (TithiStatus::Vriddhi, None),
+        1 => (TithiStatus::Normal, None),
+        _ => (TithiStatus::Kshaya, Some(TITHI[(at_sunrise + 1) % 30])),
+    };
+    TithiSunrises {
+        status,
+        at_sunrise: TITHI[at_sunrise],
+        at_next_sunrise: TITHI[at_next_sunrise],
+        skipped,
+    }
+}
+
+/// Calculates when the Yoga in effect at the given local time began and ends
+///
+/// The boundaries are the instants at which the sum of the Sun's and the Moon's
+/// sidereal longitudes crosses the 13°20' multiples on either side of the current
+/// Yoga. A Yoga lasts between roughly 20 and 27 hours, so when two Yogas begin on
+/// the same day, the one in effect at `hour` is the one bounded.
+///
+/// # Arguments
+/// * `day` - Day of month
+/// * `month` - Month number (1-12)
+/// * `year` - Year
+/// * `hour` - Hour in local time
+/// * `timezone_offset` - Time zone offset from GMT in hours
+/// * `ayanamsa_system` - The Ayanamsa used for the sidereal longitudes
+///
+/// # Returns
+/// The UTC start and end of the current Yoga
+pub fn yoga_bounds(
+    day: i32,
+    month: i32,
+    year: i32,
+    hour: f64,
+    timezone_offset: f64,
+    ayanamsa_system: Ayanamsa,
+) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let instant = days_since_j2000(day, month, year) as f64 + (hour - timezone_offset) / 24.0;
+    let yoga_index = |d| yoga_index_at(d, ayanamsa_system);
+
+    let start = find_transition(yoga_index, instant, false, 2.0)?;
+    let end = find_transition(yoga_index, instant, true, 2.0)?;
+
+    Some((day_number_to_utc(start), day_number_to_utc(end)))
+}
+
+/// Calculates when the Nakshatra in effect at the given local time began and ends
+///
+/// The boundaries are the instants at which the Moon's sidereal longitude crosses
+/// the 13°20' multiples on either side of the current Nakshatra, which takes the
+/// Moon between roughly 20 and 27 hours.
+///
+/// # Arguments
+/// * `day` - Day of month
+/// * `month` - Month number (1-12)
+/// * `year` - Year
+/// * `hour` - Hour in local time
+/// * `timezone_offset` - Time zone offset from GMT in hours
+/// * `ayanamsa_system` - The Ayanamsa used for the Moon's sidereal longitude
+///
+/// # Returns
+/// The UTC start and end of the current Nakshatra
+pub fn nakshatra_bounds(
+    day: i32,
+    month: i32,
+    year: i32,
+    hour: f64,
+    timezone_offset: f64,
+    ayanamsa_system: Ayanamsa,
+) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let instant = days_since_j2000(day, month, year) as f64 + (hour - timezone_offset) / 24.0;
+    let nakshatra_index = |d| nakshatra_index_at(d, ayanamsa_system);
+
+    let start = find_transition(nakshatra_index, instant, false, 2.0)?;
+    let end = find_transition(nakshatra_index, instant, true, 2.0)?;
+
+    Some((day_number_to_utc(start), day_number_to_utc(end)))
+}
+
+/// Calculates when the Moon leaves the Rashi it is in at the given local time
+///
+/// The boundary is the instant at which the Moon's sidereal longitude next crosses
+/// a multiple of 30°. The Moon stays in a Rashi for about two and a quarter days,
+/// and the Rashi after Meena is Mesha again.
+///
+/// # Arguments
+/// * `day` - Day of month
+/// * `month` - Month number (1-12)
+/// * `year` - Year
+/// * `hour` - Hour in local time
+/// * `timezone_offset` - Time zone offset from GMT in hours
+/// * `ayanamsa_system` - The Ayanamsa used for the Moon's sidereal longitude
+///
+/// # Returns
+/// The Rashi the Moon enters next and the UTC instant it does
+pub fn rashi_end(
+    day: i32,
+    month: i32,
+    year: i32,
+    hour: f64,
+    timezone_offset: f64,
+    ayanamsa_system: Ayanamsa,
+) -> Option<(String, DateTime<Utc>)> {
+    let instant = days_since_j2000(day, month, year) as f64 + (hour - timezone_offset) / 24.0;
+    let rashi_at = |d: f64| chandra_rashi_at(d, ayanamsa_system);
+
+    let end = find_transition(rashi_at, instant, true, 3.0)?;
+    let entered = (rashi_at(instant) + 1) % 12;
+    Some((RASHI[entered].to_string(), day_number_to_utc(end)))
+}
+
+/// Finds the windows of Vyatipata and Vaidhruthi, the most inauspicious Yogas,
+/// that overlap a local date
+///
+/// The Yogas in effect from midnight to midnight are walked in turn, at most three
+/// since each lasts 20 hours or more, and each window is bounded with the same
+/// boundary search as `yoga_bounds`, so it may begin the day before or end the day
+/// after.
+///
+/// # Arguments
+/// * `day` - Day of month
+/// * `month` - Month number (1-12)
+/// * `year` - Year
+/// * `timezone_offset` - Time zone offset from GMT in hours, bounding the local date
+/// * `ayanamsa_system` - The Ayanamsa used for the sidereal longitudes
+///
+/// # Returns
+/// The name and the UTC start and end of each critical Yoga during the date
+pub fn critical_yogas_on(
+    day: i32,
+    month: i32,
+    year: i32,
+    timezone_offset: f64,
+    ayanamsa_system: Ayanamsa,
+) -> Vec<(String, DateTime<Utc>, DateTime<Utc>)> {
+    let midnight = days_since_j2000(day, month, year) as f64 - timezone_offset / 24.0;
+    let yoga_index = |d| yoga_index_at(d, ayanamsa_system);
+
+    let mut windows = Vec::new();
+    let mut d = midnight;
+    while d < midnight + 1.0 {
+        let index = yoga_index(d);
+        let Some(end) = find_transition(yoga_index, d, true, 2.0) else {
+            break;
+        };
+        if CRITICAL_YOGAS.contains(&index) {
+            let start = find_transition(yoga_index, d, false, 2.0).unwrap_or(d);
+            windows.push((
+                YOGA[index].to_string(),
+                day_number_to_utc(start),
+                day_number_to_utc(end),
+            ));
+        }
+        // Step past the boundary, found to within a second, into the next Yoga
+        d = end + 1.0 / 86_400.0;
+    }
+    windows
+}
+
+/// UTC start and end of a window
+type Bounds = (DateTime<Utc>, DateTime<Utc>);
+
+/// Finds the window of Visti, the Karana also called Bhadra, that overlaps a local date
+///
+/// Visti is the last of the seven movable Karanas, so it holds one half-Tithi in
+/// every seven, eight in a month, and no two fall within a day of each other. The
+/// Karanas in effect from midnight to midnight are walked in turn, and the window is
+/// bounded where the elongation crosses the 6° multiples on either side, so it may
+/// begin the day before or end the day after. The Tithi holding it, of which it is
+/// one half, is bounded by the 12° multiples.
+///
+/// # Arguments
+/// * `day` - Day of month
+/// * `month` - Month number (1-12)
+/// * `year` - Year
+/// * `timezone_offset` - Time zone offset from GMT in hours, bounding the local date
+///
+/// # Returns
+/// The half-Tithi (0-59) of the month Visti holds, its UTC start and end and those
+/// of its Tithi, or `None` when it does not overlap the date
+pub fn bhadra_on(
+    day: i32,
+    month: i32,
+    year: i32,
+    timezone_offset: f64,
+) -> Option<(usize, Bounds, Bounds)> {
+    let midnight = days_since_j2000(day, month, year) as f64 - timezone_offset / 24.0;
+
+    let mut d = midnight;
+    while d < midnight + 1.0 {
+        let half_tithi = half_tithi_index_at(d);
+        let end = find_transition(half_tithi_index_at, d, true, 1.0)?;
+        // The middle of the half-Tithi, clear of the boundaries, names its Karana
+        if karana_index(half_tithi as f64 * 6.0 + 3.0) == VISTI {
+            let start = find_transition(half_tithi_index_at, d, false, 1.0).unwrap_or(d);
+            let middle = (start + end) / 2.0;
+            let tithi_start = find_transition(tithi_index_at, middle, false, 2.0)?;
+            let tithi_end = find_transition(tithi_index_at, middle, true, 2.0)?;
+            return Some((
+                half_tithi,
+                (day_number_to_utc(start), day_number_to_utc(end)),
+                (day_number_to_utc(tithi_start), day_number_to_utc(tithi_end)),
+            ));
+        }
+        // Step past the boundary, found to within a second, into the next Karana
+        d = end + 1.0 / 86_400.0;
+    }
+    None
+}
+
+/// Calculates the civil weekday of a Gregorian date
+///
+/// Uses the same day-number arithmetic as the rest of the calculations, so the
+/// weekday can never drift from the date the Panchanga is computed for. The
+/// formula is linear in `day`, so `day - 1` yields the previous weekday even
+/// on the first of a month.
+///
+/// # Arguments
+/// * `day` - Day of month
+/// * `month` - Month number (1-12)
+/// * `year` - Year
+///
+/// # Returns
+/// The weekday index, 0 for Sunday through 6 for Saturday
+pub fn weekday(day: i32, month: i32, year: i32) -> usize {
+    // Day number 1 (1 January 2000) was a Saturday
+    (days_since_j2000(day, month, year) + 5).rem_euclid(7) as usize
+}
+
+/// Calculates the Kali Ahargana of a Gregorian date, the whole days elapsed from the
+/// start of the Kali Yuga, at midnight of 17/18 February 3102 BCE, to the start of the date
+///
+/// The Kali Yuga began on a Friday, so the Ahargana is a multiple of 7 on Fridays.
+///
+/// # Arguments
+/// * `day` - Day of month
+/// * `month` - Month number (1-12)
+/// * `year` - Year, 1 or later
+///
+/// # Returns
+/// The number of days, 1863079 on 1 January 2000
+pub fn kali_ahargana(day: i32, month: i32, year: i32) -> i32 {
+    days_since_j2000(day, month, year) + KALI_AHARGANA_J2000
+}
+
+/// Calculates the Vaara (weekday) of a Gregorian date
+///
+/// # Arguments
+/// * `day` - Day of month
+/// * `month` - Month number (1-12)
+/// * `year` - Year
+///
+/// # Returns
+/// The Sanskrit name of the Vaara and its English equivalent
+pub fn vaara(day: i32, month: i32, year: i32) -> (String, String) {
+    let index = weekday(day, month, year);
+    (VAARA[index].to_string(), WEEKDAY[index].to_string())
+}
+
+/// Calculates the Moon's elongation east of the Sun from their longitudes
+///
+/// The elongation runs from 0° at new moon through 180° at full moon back to 360°,
+/// and so tells the waxing half of the month from the waning half.
+///
+/// # Arguments
+/// * `sun_longitude` - The Sun's longitude in degrees
+/// * `moon_longitude` - The Moon's longitude in degrees
+///
+/// # Returns
+/// The elongation in degrees, in the range [0, 360)
+pub fn moon_elongation(sun_longitude: f64, moon_longitude: f64) -> f64 {
+    rev(moon_longitude - sun_longitude)
+}
+
+/// Calculates the Moon's phase angle, the angle between the Sun and the Earth seen
+/// from the Moon, from their longitudes
+///
+/// The Moon's distance is small beside the Sun's, so the phase angle is taken as
+/// 180° less the angular distance between the Sun and the Moon, ignoring the Moon's
+/// latitude. It is 180° at new moon and 0° at full moon, alike on the waxing and
+/// waning sides.
+///
+/// # Arguments
+/// * `sun_longitude` - The Sun's longitude in degrees
+/// * `moon_longitude` - The Moon's longitude in degrees
+///
+/// # Returns
+/// The phase angle in degrees, in the range [0, 180]
+pub fn moon_phase_angle(sun_longitude: f64, moon_longitude: f64) -> f64 {
+    let elongation = moon_elongation(sun_longitude, moon_longitude);
+    180.0 - elongation.min(360.0 - elongation)
+}
+
+/// Calculates the illuminated fraction of the Moon's disc
+///
+/// # Arguments
+/// * `sun_longitude` - The Sun's longitude in degrees
+/// * `moon_longitude` - The Moon's longitude in degrees
+///
+/// # Returns
+/// The illuminated fraction, 0.0 at Amavasya and 1.0 at Poornima
+pub fn moon_illumination(sun_longitude: f64, moon_longitude: f64) -> f64 {
+    let elongation = moon_elongation(sun_longitude, moon_longitude) * D2R;
+    (1.0 - elongation.cos()) / 2.0
+}
+
+/// Returns the name of a year in the 60-year Samvatsara cycle
+///
+/// Uses the South Indian (luni-solar) convention, in which the cycle advances at
+/// each Chaitra Shukla Pratipada together with the Saka year.
+///
+/// # Arguments
+/// * `saka_year` - Year of the Saka era (Gregorian year - 78 from Chaitra onwards)
+///
+/// # Returns
+/// The Samvatsara name
+pub fn samvatsara(saka_year: i32) -> &'static str {
+    SAMVATSARA[(saka_year + SAMVATSARA_SAKA_OFFSET).rem_euclid(60) as usize]
+}
+
+/// Names the Nakshatra at a sidereal longitude in a Nakshatra system
+///
+/// In the 28-Nakshatra system Abhijit spans 4°13'20" between Uttara Ashada and
+/// Sravana, and the other Nakshatras keep their 27-Nakshatra boundaries.
+///
+/// # Arguments
+/// * `sidereal_longitude` - The Moon's sidereal longitude in degrees
+/// * `system` - The Nakshatra system
+pub fn nakshatra_name(sidereal_longitude: f64, system: NakshatraSystem) -> &'static str {
+    let longitude = rev(sidereal_longitude);
+    if system == NakshatraSystem::TwentyEight && (ABHIJIT_ARC.0..ABHIJIT_ARC.1).contains(&longitude)
+    {
+        return ABHIJIT[0];
+    }
+    NAKSHATRA[arc_index(longitude, 27)]
+}
+
+/// Looks up the index (0-26) of a Nakshatra by name, ignoring case
+pub fn nakshatra_index(name: &str) -> Option<usize> {
+    NAKSHATRA
+        .iter()
+        .position(|nakshatra| nakshatra.eq_ignore_ascii_case(name.trim()))
+}
+
+/// Looks up the numbers (1-30) of a Tithi by name, ignoring case
+///
+/// The names of the first fourteen Tithis repeat in both Pakshas, so they give two
+/// numbers, while Poornima and Amavasya give one each.
+pub fn tithi_numbers(name: &str) -> Vec<u8> {
+    (1..=30)
+        .filter(|number| TITHI[*number as usize - 1].eq_ignore_ascii_case(name.trim()))
+        .collect()
+}
+
+/// Names the Navamsa (D9) Rashi of a sidereal longitude in degrees
+///
+/// Each Rashi is divided into nine Navamsas of 3°20', one Nakshatra pada each.
+/// They are counted from the Rashi itself in the movable Rashis (Mesha, Karka, Tula,
+/// Makara), from the ninth Rashi from it in the fixed ones and from the fifth in the
+/// dual ones, which comes to counting the 108 padas of the zodiac round from Mesha.
+pub fn navamsa(sidereal_longitude: f64) -> String {
+    RASHI[arc_index(sidereal_longitude, 108) % 12].to_string()
+}
+
+/// Looks up the index (0-11) of a Rashi by name, ignoring case
+pub fn rashi_index(name: &str) -> Option<usize> {
+    RASHI
+        .iter()
+        .position(|rashi| rashi.eq_ignore_ascii_case(name.trim()))
+}
+
+/// Names the Karana (half Tithi) for the Moon's and Sun's longitudes
+///
+/// A lunar month has 60 Karanas of 6° of elongation each. The first, from new moon,
+/// is the fixed Kimstughna and the last three are the fixed Sakuni, Chatuspada and
+/// Naga; in between, the seven movable Karanas from Bava to Visti repeat eight times.
+///
+/// # Arguments
+/// * `moon_long` - The Moon's longitude in degrees
+/// * `sun_long` - The Sun's longitude in degrees, in the same frame as `moon_long`
+///
+/// # Returns
+/// The Karana name
+pub fn karana(moon_long: f64, sun_long: f64) -> &'static str {
+    KARAN[karana_index(moon_long - sun_long)]
+}
+
+/// Calculates the Ayana and Ritu from the Sun's sidereal longitude
+///
+/// Uttarayana, the Sun's northward course, begins at Makara Sankranti (270°) and
+/// lasts until Karka Sankranti (90°); the rest of the year is Dakshinayana. Each
+/// Ritu covers two consecutive solar Rashis, starting with Vasanta at Meena (330°).
+///
+/// # Arguments
+/// * `sidereal_sun_longitude` - The Sun's sidereal longitude in degrees
+///
+/// # Returns
+/// The names of the Ayana and the Ritu
+pub fn ayana_ritu(sidereal_sun_longitude: f64) -> (&'static str, &'static str) {
+    let longitude = rev(sidereal_sun_longitude);
+    let ayana = if !(90.0..270.0).contains(&longitude) {
+        "Uttarayana"
+    } else {
+        "Dakshinayana"
+    };
+    (ayana, RITU[arc_index(longitude + 30.0, 6)]) // Each Ritu = 60 degrees
+}
+
+/// Calculates all elements of Panchanga (Hindu astrological calendar)
+///
+/// This function computes the five main elements of Panchanga:
+/// - Tithi (lunar day)
+/// - Nakshatra (lunar mansion)
+/// - Yoga (luni-solar day)
+/// - Karana (half lunar day)
+/// - Rashi (zodiac sign)
+///
+/// The local date and time are converted to a UTC instant, rounded to the
+/// millisecond, and handed to `calculate_panchanga_at`.
+///
+/// # Arguments
+/// * `dd` - Day of month
+/// * `mm` - Month number (1-12)
+/// * `yy` - Year
+/// * `hr` - Hour in local time
+/// * `zhr` - Time zone offset from GMT in hours
+/// * `ayanamsa_system` - The Ayanamsa used for the sidereal elements
+///
+/// # Returns
+/// A Panchanga struct containing all calculated elements
+pub fn calculate_panchanga(
+    day: i32,
+    month: i32,
+    year: i32,
+    hour: f64,
+    timezone_offset: f64,
+    ayanamsa_system: Ayanamsa,
+) -> Panchanga {
+    calculate_panchanga_detailed(day, month, year, hour, timezone_offset, ayanamsa_system).0
+}
+
+/// Calculates the Ayanamsa at a local date and time
+///
+/// # Arguments
+/// * `day` - Day of month
+/// * `month` - Month number (1-12)
+/// * `year` - Year
+/// * `hour` - Hour in local time
+/// * `timezone_offset` - Time zone offset from GMT in hours
+/// * `ayanamsa_system` - The Ayanamsa to calculate
+///
+/// # Returns
+/// The Ayanamsa in degrees, subtracted from tropical longitudes to make them sidereal
+pub fn ayanamsa_degrees(
+    day: i32,
+    month: i32,
+    year: i32,
+    hour: f64,
+    timezone_offset: f64,
+    ayanamsa_system: Ayanamsa,
+) -> f64 {
+    let instant = days_since_j2000(day, month, year) as f64 + (hour - timezone_offset) / 24.0;
+    -calc_ayanamsa(instant, ayanamsa_system)
+}
+
+/// Calculates all elements of Panchanga like `calculate_panchanga`, along with the
+/// intermediate values they were derived from
+///
+/// # Returns
+/// The Panchanga and the day number, Ayanamsa and longitudes behind it
+pub fn calculate_panchanga_detailed(
+    day: i32,
+    month: i32,
+    year: i32,
+    hour: f64,
+    timezone_offset: f64,
+    ayanamsa_system: Ayanamsa,
+) -> (Panchanga, CalculationDetails) {
+    let instant = days_since_j2000(day, month, year) as f64 + ((hour - timezone_offset) / 24.0);
+    panchanga_at(day_number_to_utc(instant), ayanamsa_system)
+}
+
+/// Calculates all elements of Panchanga at a UTC instant
+///
+/// The instant is converted to a fractional day number with microsecond
+/// precision, so there is no local date, hour or timezone offset to reconcile.
+///
+/// # Arguments
+/// * `utc` - The instant to calculate for
+/// * `ayanamsa_system` - The Ayanamsa used for the sidereal elements
+///
+/// # Returns
+/// A Panchanga struct containing all calculated elements
+pub fn calculate_panchanga_at(utc: DateTime<Utc>, ayanamsa_system: Ayanamsa) -> Panchanga {
+    panchanga_at(utc, ayanamsa_system).0
+}
+
+/// Calculates the Panchanga at a UTC instant along with its intermediate values
+fn panchanga_at(utc: DateTime<Utc>, ayanamsa_system: Ayanamsa) -> (Panchanga, CalculationDetails) {
+    let mut panchanga_data = Panchanga {
+        // current_day: String::new(),
+        current_yoga: String::new(),
+        current_nakshatra: String::new(),
+        current_nakshatra_number: 0,
+        current_nakshatra_28: String::new(),
+        current_nakshatra_pada: 0,
+        current_nakshatra_lord: String::new(),
+        current_tithi: String::new(),
+        current_tithi_number: 0,
+        current_karana: String::new(),
+        current_paksha: String::new(),
+        current_chandra_rashi: String::new(),
+        current_chandra_rashi_number: 0,
+        current_rashi_lord: String::new(),
+        current_navamsa_rashi: String::new(),
+        current_surya_rashi: String::new(),
+        current_surya_nakshatra: String::new(),
+        current_surya_nakshatra_pada: 0,
+        moon_illumination: 0.0,
+        moon_phase_angle: 0.0,
+        moon_phase: String::new(),
+        current_masa: String::new(),
+        current_purnimanta_masa: String::new(),
+        is_adhika_masa: false,
+        current_samvatsara: String::new(),
+        current_kali_year: 0,
+        current_ayana: String::new(),
+        current_ritu: String::new(),
+    };
+
+    // Calculate the fractional day number relative to J2000.0
+    let instant = utc_to_day_number(utc);
+
+    // Calculate the longitudes and the positions of the five limbs
+    let elements = elements_at(instant, ayanamsa_system);
+    let sun_longitude = elements.sun_longitude;
+    let moon_longitude = elements.moon_longitude;
+    let sidereal_sun_longitude = elements.sidereal_sun_longitude;
+    let sidereal_moon_longitude = elements.sidereal_moon_longitude;
+
+    // Calculate Tithi (lunar day)
+    let tithi_index = elements.tithi;
+    panchanga_data.current_tithi = TITHI[tithi_index].to_string();
+    panchanga_data.current_tithi_number = (tithi_index + 1) as u8;
+    panchanga_data.current_paksha = PAKSHA[elements.paksha].to_string();
+
+    // Calculate Nakshatra (lunar mansion)
+    panchanga_data.current_nakshatra = NAKSHATRA[elements.nakshatra].to_string();
+    panchanga_data.current_nakshatra_number = (elements.nakshatra + 1) as u8;
+    panchanga_data.current_nakshatra_28 =
+        nakshatra_name(sidereal_moon_longitude, NakshatraSystem::TwentyEight).to_string();
+    panchanga_data.current_nakshatra_lord = NAKSHATRA_LORD[elements.nakshatra % 9].to_string();
+    panchanga_data.current_nakshatra_pada = elements.nakshatra_pada;
+
+    // Calculate Yoga (luni-solar day) and Karana (half lunar day)
+    panchanga_data.current_yoga = YOGA[elements.yoga].to_string();
+    panchanga_data.current_karana = KARAN[elements.karana].to_string();
+
+    // Calculate Rashi (zodiac sign), and the Sun's Rashi, which changes at each Sankranti
+    panchanga_data.current_chandra_rashi = RASHI[elements.rashi].to_string();
+    panchanga_data.current_chandra_rashi_number = (elements.rashi + 1) as u8;
+    panchanga_data.current_rashi_lord = RASHI_LORD[elements.rashi].to_string();
+    panchanga_data.current_navamsa_rashi = navamsa(sidereal_moon_longitude);
+    panchanga_data.current_surya_rashi = RASHI[elements.surya_rashi].to_string();
+
+    // Calculate the Sun's Nakshatra, on the same boundaries as the Moon's
+    panchanga_data.current_surya_nakshatra = NAKSHATRA[elements.surya_nakshatra].to_string();
+    panchanga_data.current_surya_nakshatra_pada = elements.surya_nakshatra_pada;
+
+    // Calculate the Moon's phase
+    panchanga_data.moon_illumination = moon_illumination(sun_longitude, moon_longitude);
+    panchanga_data.moon_phase_angle = moon_phase_angle(sun_longitude, moon_longitude);
+    let elongation = moon_elongation(sun_longitude, moon_longitude);
+    panchanga_data.moon_phase = MOON_PHASE[arc_index(elongation + 22.5, 8)].to_string(); // Each phase = 45 degrees of elongation
+
+    // Calculate Masa (lunar month). In the Purnimanta reckoning months end at
+    // Poornima, so Krishna Paksha already belongs to the following month; an
+    // Adhika month is reckoned from Amavasya to Amavasya in both systems
+    let (masa_index, is_adhika) = masa_at(instant, ayanamsa_system).unwrap_or((0, false));
+    let purnimanta_index = if tithi_index > 14 && !is_adhika {
+        (masa_index + 1) % 12
+    } else {
+        masa_index
+    };
+    panchanga_data.current_masa = MASA[masa_index].to_string();
+    panchanga_data.current_purnimanta_masa = MASA[purnimanta_index].to_string();
+    panchanga_data.is_adhika_masa = is_adhika;
+
+    // Calculate Samvatsara. The Saka year begins at Chaitra, so the months from
+    // Ashvin to Phalguna falling early in the Gregorian year belong to the
+    // previous year
+    let saka_year = if utc.month() <= 6 && masa_index >= 6 {
+        utc.year() - SAKA_ERA_OFFSET - 1
+    } else {
+        utc.year() - SAKA_ERA_OFFSET
+    };
+    panchanga_data.current_samvatsara = samvatsara(saka_year).to_string();
+    panchanga_data.current_kali_year = saka_year + KALI_SAKA_OFFSET;
+
+    // Calculate Ayana and Ritu from the Sun's sidereal position
+    let (ayana, ritu) = ayana_ritu(sidereal_sun_longitude);
+    panchanga_data.current_ayana = ayana.to_string();
+    panchanga_data.current_ritu = ritu.to_string();
+
+    let details = CalculationDetails {
+        instant: utc,
+        day_number: instant,
+        ayanamsa: -elements.ayanamsa,
+        sun_longitude,
+        moon_longitude,
+        sidereal_sun_longitude,
+        sidereal_moon_longitude,
+    };
+    (panchanga_data, details)
+}
+
+/// Parses an unsigned field of decimal digits
+fn parse_digits(field: &str) -> Option<i32> {
+    if field.is_empty() || !field.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    field.parse().ok()
+}
+
+/// Splits an HH:MM string into its hour and minute fields
+fn split_hours_minutes(time_str: &str) -> Result<(i32, i32), &'static str> {
+    let parts: Vec<&str> = time_str.split(':').collect();
+    if parts.len() != 2 {
+        return Err("Invalid time format");
+    }
+
+    let hours = parse_digits(parts[0]).ok_or("Invalid hours")?;
+    let minutes = parse_digits(parts[1]).ok_or("Invalid minutes")?;
+
+    Ok((hours, minutes))
+}
+
+/// Parses a wall-clock time in HH:MM 24-hour format
+///
+/// Surrounding whitespace is ignored, and the fields must be ASCII digits.
+///
+/// # Returns
+/// The hours (0-23) and minutes (0-59)
+pub fn parse_clock_time(time_str: &str) -> Result<(f64, i32), PanchangError> {
+    let invalid = |reason: String| PanchangError::InvalidTimeFormat(reason);
+
+    let (hours, minutes) =
+        split_hours_minutes(time_str.trim()).map_err(|e| invalid(e.to_string()))?;
+    if !(0..=23).contains(&hours) {
+        return Err(invalid(format!(
+            "Hours must be between 0 and 23, got {}",
+            hours
+        )));
+    }
+    if !(0..=59).contains(&minutes) {
+        return Err(invalid(format!(
+            "Minutes must be between 0 and 59, got {}",
+            minutes
+        )));
+    }
+
+    Ok((hours as f64, minutes))
+}
+
+/// Parses a timezone offset from GMT in [+/-]HH:MM or [+/-]HH format, or `Z` for UTC
+///
+/// The sign applies to the hours and minutes together, so `-00:30` is half an hour
+/// west of Greenwich. Offsets in use range from -12:00 to +14:00. Surrounding
+/// whitespace is ignored, since a literal '+' in a query string decodes to a space.
+///
+/// # Returns
+/// The offset in decimal hours, negative west of Greenwich
+pub fn parse_tz_offset(zone: &str) -> Result<f64, PanchangError> {
+    let invalid = |reason: String| PanchangError::InvalidTimezone(reason);
+
+    let zone = zone.trim();
+    if zone.eq_ignore_ascii_case("z") {
+        return Ok(0.0);
+    }
+
+    let (sign, magnitude) = match zone.strip_prefix('-') {
+        Some(magnitude) => (-1.0, magnitude),
+        None => (1.0, zone.strip_prefix('+').unwrap_or(zone)),
+    };
+
+    // Whole-hour offsets may leave out the minutes
+    let (hours, minutes) = if magnitude.contains(':') {
+        split_hours_minutes(magnitude).map_err(|e| invalid(e.to_string()))?
+    } else {
+        (
+            parse_digits(magnitude).ok_or_else(|| invalid("Invalid hours".to_string()))?,
+            0,
+        )
+    };
+    if !(0..=59).contains(&minutes) {
+        return Err(invalid(format!(
+            "Minutes must be between 0 and 59, got {}",
+            minutes
+        )));
+    }
+
+    let offset = sign * (hours as f64 + minutes as f64 / 60.0);
+    if !(-12.0..=14.0).contains(&offset) {
+        return Err(invalid(format!(
+            "Offset {} must be between -12:00 and +14:00",
+            zone
+        )));
+    }
+
+    Ok(offset)
+}
+
+/// Calendars a date can be given in
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Calendar {
+    /// The Gregorian calendar for every date, extended back before its introduction
+    #[default]
+    Gregorian,
+    /// The Julian calendar before the Gregorian cutover and the Gregorian from it on,
+    /// as in the almanacs of the time
+    Julian,
+}
+
+/// First date of the Gregorian calendar, 15 October 1582, which followed Julian
+/// 4 October 1582 in the countries that adopted the reform at once
+pub const GREGORIAN_CUTOVER: (i32, i32, i32) = (15, 10, 1582);
+
+impl Calendar {
+    /// Parses a date in DD/MM/YYYY format in the calendar and converts it to the
+    /// proleptic Gregorian date the calculations use
+    ///
+    /// For `Julian`, a date before `cutover`, the first Gregorian date, is read as
+    /// a Julian date, so 29 February is valid in every fourth year, and the days
+    /// the reform skipped are rejected.
+    ///
+    /// # Returns
+    /// The Gregorian day, month (1-12) and year
+    pub fn parse_date(
+        self,
+        date_str: &str,
+        cutover: (i32, i32, i32),
+    ) -> Result<(i32, i32, i32), PanchangError> {
+        let (day, month, year) = parse_date_with(date_str, is_julian_leap_year)?;
+        let before_cutover = (year, month, day) < (cutover.2, cutover.1, cutover.0);
+        if self == Calendar::Gregorian || !before_cutover {
+            // Only the Julian reading allows 29 February in a Gregorian common year
+            return parse_date(date_str);
+        }
+
+        let (g_day, g_month, g_year) = julian_to_gregorian(day, month, year);
+        if (g_year, g_month, g_day) >= (cutover.2, cutover.1, cutover.0) {
+            return Err(PanchangError::DateOutOfRange(format!(
+                "{} is one of the days skipped by the change to the Gregorian calendar on {:02}/{:02}/{}",
+                date_str.trim(),
+                cutover.0,
+                cutover.1,
+                cutover.2
+            )));
+        }
+        Ok((g_day, g_month, g_year))
+    }
+
+    /// Formats a proleptic Gregorian date in DD/MM/YYYY format in the calendar, as
+    /// `parse_date` reads it back
+    ///
+    /// For `Julian`, a date before `cutover` is written as its Julian date.
+    pub fn format_date(self, (day, month, year): (i32, i32, i32), cutover: (i32, i32, i32)) -> String {
+        let before_cutover = (year, month, day) < (cutover.2, cutover.1, cutover.0);
+        let (day, month, year) = if self == Calendar::Julian && before_cutover {
+            gregorian_to_julian(day, month, year)
+        } else {
+            (day, month, year)
+        };
+        format!("{:02}/{:02}/{:04}", day, month, year)
+    }
+}
+
+/// Converts a Julian calendar date to the proleptic Gregorian date of the same day
+///
+/// The date is counted as a Julian Day Number, from which the Gregorian date
+/// follows; the calendars are 10 days apart in 1582 and 13 days in 1900-2099.
+///
+/// # Arguments
+/// * `day` - Day of month
+/// * `month` - Month number (1-12)
+/// * `year` - Year, 1 or later
+///
+/// # Returns
+/// The Gregorian day, month (1-12) and year
+pub fn julian_to_gregorian(day: i32, month: i32, year: i32) -> (i32, i32, i32) {
+    // Julian Day Number of the Julian date, counting years from March
+    let a = (14 - month) / 12;
+    let y = year + 4800 - a;
+    let m = month + 12 * a - 3;
+    let jdn = day + (153 * m + 2) / 5 + 365 * y + y / 4 - 32083;
+
+    // Gregorian date of the day number
+    let a = jdn + 32044;
+    let b = (4 * a + 3) / 146097;
+    let c = a - 146097 * b / 4;
+    let d = (4 * c + 3) / 1461;
+    let e = c - 1461 * d / 4;
+    let m = (5 * e + 2) / 153;
+    (
+        e - (153 * m + 2) / 5 + 1,
+        m + 3 - 12 * (m / 10),
+        100 * b + d - 4800 + m / 10,
+    )
+}
+
+/// Converts a proleptic Gregorian date to the Julian calendar date of the same day,
+/// the inverse of `julian_to_gregorian`
+///
+/// # Arguments
+/// * `day` - Day of month
+/// * `month` - Month number (1-12)
+/// * `year` - Year, 1 or later
+///
+/// # Returns
+/// The Julian day, month (1-12) and year
+pub fn gregorian_to_julian(day: i32, month: i32, year: i32) -> (i32, i32, i32) {
+    // Julian Day Number of the Gregorian date, counting years from March
+    let a = (14 - month) / 12;
+    let y = year + 4800 - a;
+    let m = month + 12 * a - 3;
+    let jdn = day + (153 * m + 2) / 5 + 365 * y + y / 4 - y / 100 + y / 400 - 32045;
+
+    // Julian date of the day number
+    let c = jdn + 32082;
+    let d = (4 * c + 3) / 1461;
+    let e = c - 1461 * d / 4;
+    let m = (5 * e + 2) / 153;
+    (
+        e - (153 * m + 2) / 5 + 1,
+        m + 3 - 12 * (m / 10),
+        d - 4800 + m / 10,
+    )
+}
+
+/// Checks whether a year is a leap year under the Julian rule, every fourth year
+///
+/// Every Gregorian leap year is also a Julian one, so a date valid in either
+/// calendar is valid under this rule.
+fn is_julian_leap_year(year: i32) -> bool {
+    year % 4 == 0
+}
+
+/// Checks whether a year is a leap year under the Gregorian rule
+///
+/// Years divisible by 4 are leap years, except century years, which are leap
+/// years only when divisible by 400 (so 2000 is a leap year but 1900 is not).
+fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+/// Returns the number of days in a month
+///
+/// # Arguments
+/// * `month` - Month number (1-12)
+/// * `year` - Year, needed for February
+/// * `is_leap` - The calendar's leap year rule
+fn days_in_month(month: i32, year: i32, is_leap: fn(i32) -> bool) -> i32 {
+    match month {
+        2 if is_leap(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Years in which the calculation is checked against published new moons, which
+/// it matches to about a minute
+///
+/// Further out the ΔT estimate and the solar theory lose accuracy, so the element
+/// of an instant close to a change may be a step off.
+pub const ACCURATE_YEARS: RangeInclusive<i32> = 1900..=2100;
+
+/// Parses a Gregorian date in DD/MM/YYYY format
+///
+/// Surrounding whitespace is ignored, and the fields must be ASCII digits. Years
+/// are limited to 1-9999, so that every accepted date is also a valid calendar
+/// date for the range and search endpoints.
+///
+/// # Returns
+/// The day, month (1-12) and year
+pub fn parse_date(date_str: &str) -> Result<(i32, i32, i32), PanchangError> {
+    parse_date_with(date_str, is_leap_year)
+}
+
+/// Parses a date in DD/MM/YYYY format in the calendar whose leap years are those
+/// for which `is_leap` holds
+fn parse_date_with(date_str: &str, is_leap: fn(i32) -> bool) -> Result<(i32, i32, i32), PanchangError> {
+    let invalid = |reason: &str| PanchangError::InvalidDateFormat(reason.to_string());
+
+    let parts: Vec<&str> = date_str.trim().split('/').collect();
+    if parts.len() != 3 {
+        return Err(invalid("Invalid date format"));
+    }
+
+    let day = parse_digits(parts[0]).ok_or_else(|| invalid("Invalid day"))?;
+    let month = parse_digits(parts[1]).ok_or_else(|| invalid("Invalid month"))?;
+    let year = parse_digits(parts[2]).ok_or_else(|| invalid("Invalid year"))?;
+
+    if !(1..=9999).contains(&year) {
+        return Err(PanchangError::DateOutOfRange(format!(
+            "Year must be between 1 and 9999, got {}",
+            year
+        )));
+    }
+    if !(1..=12).contains(&month) {
+        return Err(PanchangError::DateOutOfRange(
+            "Month must be between 1 and 12".to_string(),
+        ));
+    }
+    if !(1..=31).contains(&day) {
+        return Err(PanchangError::DateOutOfRange(
+            "Day must be between 1 and 31".to_string(),
+        ));
+    }
+
+    let month_name = MONTHS[(month - 1) as usize];
+    if day > days_in_month(month, year, is_leap) {
+        return Err(PanchangError::DateOutOfRange(if month == 2 && day == 29 {
+            format!("29 February is invalid in non-leap year {}", year)
+        } else {
+            format!(
+                "{} {} is invalid, {} has {} days",
+                day,
+                month_name,
+                month_name,
+                days_in_month(month, year, is_leap)
+            )
+        }));
+    }
 
     Ok((day, month, year))
 }
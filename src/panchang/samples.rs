@@ -0,0 +1,165 @@
+//! Times of the day a Panchang is sampled at
+//!
+//! A request's `samples` asks for the limbs at several times of its local date,
+//! either named times such as `sunrise,noon,sunset` or a count of times evenly spaced
+//! from midnight, so that a client sees where each limb changes during the day
+//! without calling the boundary finders.
+
+use serde::de::{self, Deserializer};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+
+/// Most samples a request may ask for, one every half hour of the day
+pub const MAX_SAMPLES: usize = 48;
+
+/// Time of the local date a sample is taken at
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SampleTime {
+    /// Local sunrise
+    Sunrise,
+    /// Midday, halfway between sunrise and sunset
+    Noon,
+    /// Local sunset
+    Sunset,
+    /// A wall-clock time, in decimal hours
+    Clock(f64),
+}
+
+impl SampleTime {
+    /// Reads `sunrise`, `noon`, `sunset` or an HH:MM time
+    fn parse(name: &str) -> Result<SampleTime, String> {
+        match name {
+            "sunrise" => Ok(SampleTime::Sunrise),
+            "noon" => Ok(SampleTime::Noon),
+            "sunset" => Ok(SampleTime::Sunset),
+            clock => super::parse_clock_time(clock)
+                .map(|(hours, minutes)| SampleTime::Clock(hours + minutes as f64 / 60.0))
+                .map_err(|_| {
+                    format!(
+                        "unknown time `{}` in `samples`, expected sunrise, noon, sunset or HH:MM",
+                        clock
+                    )
+                }),
+        }
+    }
+
+    /// The time as `parse` reads it
+    fn name(&self) -> String {
+        match self {
+            SampleTime::Sunrise => String::from("sunrise"),
+            SampleTime::Noon => String::from("noon"),
+            SampleTime::Sunset => String::from("sunset"),
+            SampleTime::Clock(hours) => {
+                let minutes = (hours * 60.0).round() as i64;
+                format!("{:02}:{:02}", minutes / 60, minutes % 60)
+            }
+        }
+    }
+}
+
+/// Times of the local date a request asks the limbs at
+#[derive(Debug, Clone, PartialEq)]
+pub enum Samples {
+    /// This many times evenly spaced from midnight, e.g. 4 for 00:00, 06:00, 12:00
+    /// and 18:00
+    Count(usize),
+    /// The named or wall-clock times, in the order given
+    Times(Vec<SampleTime>),
+}
+
+impl Samples {
+    /// Reads a count such as `4` or a comma-separated list of times such as
+    /// `sunrise,noon,sunset`, rejecting unknown times and more than `MAX_SAMPLES`
+    pub fn parse(list: &str) -> Result<Samples, String> {
+        if let Ok(count) = list.trim().parse::<usize>() {
+            return Samples::count(count);
+        }
+        let times = list
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(SampleTime::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        Samples::times(times)
+    }
+
+    /// Checks a count of samples
+    fn count(count: usize) -> Result<Samples, String> {
+        match count {
+            0 => Err(String::from("`samples` must ask for at least one time")),
+            count if count > MAX_SAMPLES => Err(format!(
+                "`samples` asks for {} times, at most {} are allowed",
+                count, MAX_SAMPLES
+            )),
+            count => Ok(Samples::Count(count)),
+        }
+    }
+
+    /// Checks a list of sample times
+    fn times(times: Vec<SampleTime>) -> Result<Samples, String> {
+        Samples::count(times.len())?;
+        Ok(Samples::Times(times))
+    }
+
+    /// Local times of the samples in decimal hours, in order
+    ///
+    /// The named times follow the Sun, so they are left out on a date it does not
+    /// rise and set.
+    ///
+    /// # Arguments
+    /// * `sun_times` - Local sunrise and sunset in decimal hours, if the Sun rises and sets
+    pub fn hours(&self, sun_times: Option<(f64, f64)>) -> Vec<f64> {
+        match self {
+            Samples::Count(count) => (0..*count)
+                .map(|sample| 24.0 * sample as f64 / *count as f64)
+                .collect(),
+            Samples::Times(times) => times
+                .iter()
+                .filter_map(|time| match time {
+                    SampleTime::Sunrise => sun_times.map(|(sunrise, _)| sunrise),
+                    SampleTime::Noon => sun_times.map(|(sunrise, sunset)| (sunrise + sunset) / 2.0),
+                    SampleTime::Sunset => sun_times.map(|(_, sunset)| sunset),
+                    SampleTime::Clock(hours) => Some(*hours),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Samples {
+    /// Reads a count as a number or a string, and the times as a list or a
+    /// comma-separated string, as a query string gives them
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Given {
+            Count(usize),
+            Text(String),
+            Times(Vec<String>),
+        }
+
+        match Given::deserialize(deserializer)? {
+            Given::Count(count) => Samples::count(count),
+            Given::Text(list) => Samples::parse(&list),
+            Given::Times(times) => times
+                .iter()
+                .map(|name| SampleTime::parse(name.trim()))
+                .collect::<Result<Vec<_>, _>>()
+                .and_then(Samples::times),
+        }
+        .map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for Samples {
+    /// Writes a count as a number and the times as a comma-separated string
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Samples::Count(count) => serializer.serialize_u64(*count as u64),
+            Samples::Times(times) => {
+                let names: Vec<String> = times.iter().map(SampleTime::name).collect();
+                serializer.serialize_str(&names.join(","))
+            }
+        }
+    }
+}
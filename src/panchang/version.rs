@@ -0,0 +1,106 @@
+//! Versions of the response schema
+//!
+//! Version 1 is the Panchang as the API first served it: the six limbs `tithi`,
+//! `paksha` (a name), `nakshatra`, `yoga`, `karana` and `rashi`. Version 2, the
+//! default, is the full response, which grows as limbs are added. A client pins the
+//! shape it was written against with `?v=1` or
+//! `Accept: application/vnd.panchang.v1+json`.
+
+use serde::{Serialize, Serializer};
+
+use super::PanchangError;
+
+/// Media type prefix of the versioned JSON responses, as in
+/// `application/vnd.panchang.v1+json`
+const VENDOR_PREFIX: &str = "application/vnd.panchang.v";
+
+/// Version of the response schema
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ApiVersion {
+    /// The six original limbs
+    V1,
+    /// The full response
+    #[default]
+    V2,
+}
+
+impl ApiVersion {
+    /// Number of the version, sent as `apiVersion` in the envelope
+    pub fn number(self) -> u8 {
+        match self {
+            ApiVersion::V1 => 1,
+            ApiVersion::V2 => 2,
+        }
+    }
+
+    /// Reads a version number such as `1` or `2`
+    pub fn parse(version: &str) -> Result<ApiVersion, PanchangError> {
+        match version.trim() {
+            "1" => Ok(ApiVersion::V1),
+            "2" => Ok(ApiVersion::V2),
+            other => Err(PanchangError::UnsupportedApiVersion(format!(
+                "`{}` is not a response version, expected 1 or 2",
+                other
+            ))),
+        }
+    }
+
+    /// Picks the version named by a vendor media type of an `Accept` header, or
+    /// `None` when it names none, as for `application/json` or `*/*`
+    pub fn negotiate(accept: &str) -> Result<Option<ApiVersion>, PanchangError> {
+        accept
+            .split(',')
+            .filter_map(|range| {
+                let media_type = range.split(';').next().unwrap_or_default().trim();
+                media_type
+                    .get(..VENDOR_PREFIX.len())
+                    .filter(|prefix| prefix.eq_ignore_ascii_case(VENDOR_PREFIX))
+                    .and_then(|_| media_type[VENDOR_PREFIX.len()..].strip_suffix("+json"))
+            })
+            .next()
+            .map(ApiVersion::parse)
+            .transpose()
+    }
+}
+
+/// A response with a version 1 shape
+pub trait V1Shape {
+    /// The response as version 1 serializes it
+    type V1: Serialize;
+
+    /// Narrows the response to its version 1 shape
+    fn into_v1(self) -> Self::V1;
+}
+
+impl<T: V1Shape> V1Shape for Vec<T> {
+    type V1 = Vec<T::V1>;
+
+    fn into_v1(self) -> Self::V1 {
+        self.into_iter().map(V1Shape::into_v1).collect()
+    }
+}
+
+/// A response serialized in the shape of a version
+pub enum Versioned<T: V1Shape> {
+    V1(T::V1),
+    V2(T),
+}
+
+impl<T: V1Shape> Versioned<T> {
+    /// Shapes a response, or a list of responses, for `version`
+    pub fn new(value: T, version: ApiVersion) -> Self {
+        match version {
+            ApiVersion::V1 => Versioned::V1(value.into_v1()),
+            ApiVersion::V2 => Versioned::V2(value),
+        }
+    }
+}
+
+impl<T: V1Shape + Serialize> Serialize for Versioned<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Versioned::V1(value) => value.serialize(serializer),
+            Versioned::V2(value) => value.serialize(serializer),
+        }
+    }
+}
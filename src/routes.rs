@@ -1,17 +1,36 @@
-use actix_web::{HttpResponse, Responder, get, post, web};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse, Responder, ResponseError, get, post, web};
+use actix_web::body::{BodySize, MessageBody};
+use actix_web::error::{InternalError, JsonPayloadError, QueryPayloadError};
+use actix_web::http::{StatusCode, header};
+use actix_web::http::header::HeaderValue;
 
 use serde::{Serialize, Deserialize};
 use chrono::{DateTime, Utc};
+use std::convert::Infallible;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
 
+use crate::middleware::RequestId;
+use crate::panchang::{ApiVersion, Language, PanchangError, Selected, Versioned};
+use crate::server::{AppState, within_budget};
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ApiResponse<T> {
     pub status: String,
     pub status_code: u16,
+    /// Version of the response schema `data` is in
+    pub api_version: u8,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub message: Option<String>,
 
+    /// Machine-readable error code, present only on errors
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<T>,
 
@@ -21,51 +40,644 @@ pub struct ApiResponse<T> {
 
 
 
+/// Flags accepted in the query string of the POST endpoints, alongside the body
+#[derive(Deserialize)]
+pub struct Flags {
+    /// Include the parsed inputs and intermediate values in the response
+    #[serde(default)]
+    verbose: bool,
+    /// Include the sidereal longitudes of the Sun and the Moon in the response
+    #[serde(default)]
+    longitudes: bool,
+}
+
+/// Language requested in the query string, which the POST endpoints accept as well
+#[derive(Deserialize)]
+struct LangParam {
+    lang: Option<String>,
+}
+
+/// Reads the language of the field labels from the `lang` query parameter, or else
+/// the `Accept-Language` header
+///
+/// Returns `None` when neither is given, and English when no requested language
+/// is supported.
+fn requested_language(req: &HttpRequest) -> Option<Language> {
+    if let Ok(param) = web::Query::<LangParam>::from_query(req.query_string())
+        && let Some(lang) = &param.lang
+    {
+        return Some(Language::from_tag(lang).unwrap_or_default());
+    }
+    req.headers()
+        .get(header::ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok())
+        .map(Language::negotiate)
+}
+
+/// Response version requested in the query string, which the POST endpoints accept as well
+#[derive(Deserialize)]
+struct VersionParam {
+    v: Option<String>,
+}
+
+/// Reads the response schema version from the `v` query parameter, or else a
+/// vendor media type of the `Accept` header, defaulting to the latest version
+fn requested_version(req: &HttpRequest) -> Result<ApiVersion, PanchangError> {
+    if let Ok(param) = web::Query::<VersionParam>::from_query(req.query_string())
+        && let Some(version) = &param.v
+    {
+        return ApiVersion::parse(version);
+    }
+    let accept = req.headers().get(header::ACCEPT).and_then(|value| value.to_str().ok()).unwrap_or_default();
+    Ok(ApiVersion::negotiate(accept)?.unwrap_or_default())
+}
+
+/// How long the health check waits for the database to answer
+const DB_PING_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Content type of the streamed range
+const NDJSON_CONTENT_TYPE: &str = "application/x-ndjson";
+
+/// Days of a streamed range calculated ahead of what the client has read
+const NDJSON_BUFFERED_DAYS: usize = 8;
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Serialize)]
+pub struct HealthStatus {
+    /// Status of the calculation engine, which is always up while serving
+    engine: SubsystemStatus,
+    /// Status of the database
+    db: SubsystemStatus,
+}
+
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Serialize)]
+pub struct SubsystemStatus {
+    /// One of "up", "down" or "disabled"
+    status: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+}
+
+impl SubsystemStatus {
+    fn new(status: &str, message: Option<String>) -> Self {
+        SubsystemStatus { status: status.to_string(), message }
+    }
+}
+
+/// Runs a lightweight query to check that the database is usable
+async fn ping_db(pool: &sqlx::PgPool) -> Result<(), String> {
+    match actix_web::rt::time::timeout(DB_PING_TIMEOUT, sqlx::query("SELECT 1").execute(pool)).await {
+        Ok(Ok(_)) => Ok(()),
+        Ok(Err(err)) => Err(err.to_string()),
+        Err(_) => Err(format!("no response within {}s", DB_PING_TIMEOUT.as_secs())),
+    }
+}
+
+/// Status of the database: disabled without a pool, otherwise up or down as pinged
+async fn db_status(state: &AppState) -> SubsystemStatus {
+    match &state.pool {
+        None => SubsystemStatus::new("disabled", None),
+        Some(pool) => match ping_db(pool).await {
+            Ok(()) => SubsystemStatus::new("up", None),
+            Err(err) => SubsystemStatus::new("down", Some(err)),
+        },
+    }
+}
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    get,
+    path = "/health",
+    tag = "health",
+    responses(
+        (status = 200, description = "Service is healthy, or degraded without its database", body = ApiResponse<HealthStatus>),
+        (status = 503, description = "Database is down and required", body = ApiResponse<HealthStatus>),
+    ),
+))]
 #[get("/health")]
-async fn health_chcek() -> impl Responder {
+async fn health_check(request_id: RequestId, state: web::Data<AppState>) -> impl Responder {
+    let db = db_status(&state).await;
+
+    // A failing database only degrades the service, the calculations still work,
+    // unless the deployment requires the database
+    let degraded = db.status == "down";
+    let status_code = if degraded && state.db_required {
+        StatusCode::SERVICE_UNAVAILABLE
+    } else {
+        StatusCode::OK
+    };
+
+    let response: ApiResponse<HealthStatus> = ApiResponse {
+        status: if degraded { "degraded" } else { "healthy" }.to_string(),
+        status_code: status_code.as_u16(),
+        api_version: ApiVersion::default().number(),
+        message: Some(if degraded { "Service is running without its database" } else { "Service is running" }.to_string()),
+        code: None,
+        data: Some(HealthStatus {
+            engine: SubsystemStatus::new("up", None),
+            db,
+        }),
+        timestamp: Utc::now(),
+        request_id: request_id.0,
+    };
 
+    HttpResponse::build(status_code).json(response)
+}
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    get,
+    path = "/healthz",
+    tag = "health",
+    responses(
+        (status = 200, description = "The process is serving requests", body = ApiResponse<serde_json::Value>),
+    ),
+))]
+#[get("/healthz")]
+async fn liveness(request_id: RequestId) -> impl Responder {
+    // Answering at all shows the workers are not stuck, so nothing else is checked
     let response: ApiResponse<()> = ApiResponse {
-        status: "healthy".to_string(),
+        status: "alive".to_string(),
         status_code: 200,
-        message: Some("Service is running".to_string()),
+        api_version: ApiVersion::default().number(),
+        message: Some("Service is alive".to_string()),
+        code: None,
         data: None,
         timestamp: Utc::now(),
-        request_id: "12345".to_string(),
+        request_id: request_id.0,
     };
-    
     HttpResponse::Ok().json(response)
 }
 
+#[cfg_attr(feature = "openapi", utoipa::path(
+    get,
+    path = "/readyz",
+    tag = "health",
+    responses(
+        (status = 200, description = "Ready to serve, with a usable database if one is configured", body = ApiResponse<HealthStatus>),
+        (status = 503, description = "The configured database is not usable yet", body = ApiResponse<HealthStatus>),
+    ),
+))]
+#[get("/readyz")]
+async fn readiness(request_id: RequestId, state: web::Data<AppState>) -> impl Responder {
+    let db = db_status(&state).await;
+
+    // Unlike /health, a configured database that is down always makes the service unready
+    let ready = db.status != "down";
+    let status_code = if ready { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+
+    let response: ApiResponse<HealthStatus> = ApiResponse {
+        status: if ready { "ready" } else { "unready" }.to_string(),
+        status_code: status_code.as_u16(),
+        api_version: ApiVersion::default().number(),
+        message: Some(if ready { "Service is ready" } else { "Service is waiting for its database" }.to_string()),
+        code: None,
+        data: Some(HealthStatus {
+            engine: SubsystemStatus::new("up", None),
+            db,
+        }),
+        timestamp: Utc::now(),
+        request_id: request_id.0,
+    };
+
+    HttpResponse::build(status_code).json(response)
+}
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    get,
+    path = "/metrics",
+    tag = "health",
+    responses(
+        (status = 200, description = "Request and database pool metrics in the Prometheus text format", body = String, content_type = "text/plain"),
+    ),
+))]
+#[get("/metrics")]
+async fn metrics(state: web::Data<AppState>) -> impl Responder {
+    HttpResponse::Ok()
+        .content_type(prometheus::TEXT_FORMAT)
+        .body(state.metrics.render(state.pool.as_ref()))
+}
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    post,
+    path = "/panchang",
+    tag = "panchang",
+    params(("verbose" = Option<bool>, Query, description = "Include the parsed inputs and intermediate values as `meta`"), ("longitudes" = Option<bool>, Query, description = "Include the sidereal `sun_longitude` and `moon_longitude`"), ("lang" = Option<String>, Query, description = "Language of the `labels`, `en`, `hi` or `ta`; overrides `Accept-Language`"), ("v" = Option<u8>, Query, description = "Response schema version, `1` for the six original limbs (`PanchangResponseV1`) or `2` (default) for the full response; overrides a `application/vnd.panchang.v1+json` `Accept` header")),
+    request_body = crate::panchang::PanchangRequest,
+    responses(
+        (status = 200, description = "Panchang for the requested date, time and location", body = ApiResponse<crate::panchang::PanchangResponse>),
+        (status = 400, description = "Invalid input, with the reason in `message`", body = ApiResponse<serde_json::Value>),
+        (status = 406, description = "The requested response version is not 1 or 2", body = ApiResponse<serde_json::Value>),
+        (status = 409, description = "The `Idempotency-Key` was already used for a different request", body = ApiResponse<serde_json::Value>),
+        (status = 413, description = "The body is larger than `MAX_BODY_BYTES`", body = ApiResponse<serde_json::Value>),
+        (status = 422, description = "The date does not exist or is outside `SUPPORTED_YEARS`", body = ApiResponse<serde_json::Value>),
+        (status = 429, description = "Rate limit exceeded, see the `Retry-After` header", body = ApiResponse<serde_json::Value>),
+        (status = 504, description = "The calculation took longer than `CALCULATION_TIMEOUT_MS`", body = ApiResponse<serde_json::Value>),
+    ),
+))]
 #[post("/panchang")]
-async fn panchang_handler(data: web::Json<crate::panchang::PanchangRequest>) -> impl Responder {
-    match crate::panchang::calculate_panchang(data).await {
-        Ok(panchang_data) => {
-            let response: ApiResponse<crate::panchang::PanchangResponse> = ApiResponse {
+async fn panchang_handler(req: HttpRequest, request_id: RequestId, state: web::Data<AppState>, flags: web::Query<Flags>, data: web::Json<crate::panchang::PanchangRequest>) -> impl Responder {
+    let data = data.into_inner().map_options(|options| options.with_verbose(flags.verbose).with_longitudes(flags.longitudes).with_default_ayanamsa(state.default_ayanamsa)).with_default_location(&state.default_location);
+    let version = match requested_version(&req) {
+        Ok(version) => version,
+        Err(error) => return error_response(request_id, error.status_code(), error.code(), error.to_string()),
+    };
+    panchang_response(request_id, &state, data.with_supported_years(state.supported_years.clone()), requested_language(&req), version).await
+}
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    get,
+    path = "/panchang",
+    tag = "panchang",
+    params(crate::panchang::PanchangRequest, crate::panchang::RequestCalendar, crate::panchang::RequestLocation, crate::panchang::RequestOptions, ("lang" = Option<String>, Query, description = "Language of the `labels`, `en`, `hi` or `ta`; overrides `Accept-Language`"), ("v" = Option<u8>, Query, description = "Response schema version, `1` for the six original limbs (`PanchangResponseV1`) or `2` (default) for the full response; overrides a `application/vnd.panchang.v1+json` `Accept` header")),
+    responses(
+        (status = 200, description = "Panchang for the requested date, time and location", body = ApiResponse<crate::panchang::PanchangResponse>),
+        (status = 400, description = "Invalid input, with the reason in `message`", body = ApiResponse<serde_json::Value>),
+        (status = 406, description = "The requested response version is not 1 or 2", body = ApiResponse<serde_json::Value>),
+        (status = 422, description = "The date does not exist or is outside `SUPPORTED_YEARS`", body = ApiResponse<serde_json::Value>),
+        (status = 429, description = "Rate limit exceeded, see the `Retry-After` header", body = ApiResponse<serde_json::Value>),
+        (status = 504, description = "The calculation took longer than `CALCULATION_TIMEOUT_MS`", body = ApiResponse<serde_json::Value>),
+    ),
+))]
+#[get("/panchang")]
+async fn panchang_query_handler(req: HttpRequest, request_id: RequestId, state: web::Data<AppState>, data: web::Query<crate::panchang::PanchangRequest>) -> impl Responder {
+    let data = data.into_inner().map_options(|options| options.with_default_ayanamsa(state.default_ayanamsa)).with_default_location(&state.default_location);
+    let version = match requested_version(&req) {
+        Ok(version) => version,
+        Err(error) => return error_response(request_id, error.status_code(), error.code(), error.to_string()),
+    };
+    panchang_response(request_id, &state, data.with_supported_years(state.supported_years.clone()), requested_language(&req), version).await
+}
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    get,
+    path = "/panchang/now",
+    tag = "panchang",
+    params(crate::panchang::PanchangNowRequest, crate::panchang::RequestOptions, ("lang" = Option<String>, Query, description = "Language of the `labels`, `en`, `hi` or `ta`; overrides `Accept-Language`"), ("v" = Option<u8>, Query, description = "Response schema version, `1` for the six original limbs (`PanchangResponseV1`) or `2` (default) for the full response; overrides a `application/vnd.panchang.v1+json` `Accept` header")),
+    responses(
+        (status = 200, description = "Panchang for the current instant at the location", body = ApiResponse<crate::panchang::PanchangResponse>),
+        (status = 400, description = "Invalid input, with the reason in `message`", body = ApiResponse<serde_json::Value>),
+        (status = 406, description = "The requested response version is not 1 or 2", body = ApiResponse<serde_json::Value>),
+        (status = 429, description = "Rate limit exceeded, see the `Retry-After` header", body = ApiResponse<serde_json::Value>),
+        (status = 504, description = "The calculation took longer than `CALCULATION_TIMEOUT_MS`", body = ApiResponse<serde_json::Value>),
+    ),
+))]
+#[get("/panchang/now")]
+async fn panchang_now_handler(req: HttpRequest, request_id: RequestId, state: web::Data<AppState>, data: web::Query<crate::panchang::PanchangNowRequest>) -> impl Responder {
+    let version = match requested_version(&req) {
+        Ok(version) => version,
+        Err(error) => return error_response(request_id, error.status_code(), error.code(), error.to_string()),
+    };
+    let data = data.into_inner().map_options(|options| options.with_default_ayanamsa(state.default_ayanamsa));
+    let (location, now, fields) = (state.default_location.clone(), Utc::now(), data.options().fields().cloned());
+    let mut result = within_budget(&state, async move { crate::panchang::calculate_panchang_now(&data, &location, now).await }).await;
+    if let Some(language) = requested_language(&req) {
+        result = result.map(|panchang| panchang.with_labels(language));
+    }
+    envelope(request_id, version, result.map(|panchang| Selected::new(Versioned::new(panchang, version), fields)), "Panchang data fetched successfully")
+}
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    post,
+    path = "/panchang/range",
+    tag = "panchang",
+    params(("verbose" = Option<bool>, Query, description = "Include the parsed inputs and intermediate values as `meta`"), ("longitudes" = Option<bool>, Query, description = "Include the sidereal `sun_longitude` and `moon_longitude`"), ("lang" = Option<String>, Query, description = "Language of the `labels`, `en`, `hi` or `ta`; overrides `Accept-Language`"), ("v" = Option<u8>, Query, description = "Response schema version, `1` for the six original limbs (`PanchangResponseV1`) or `2` (default) for the full response; overrides a `application/vnd.panchang.v1+json` `Accept` header")),
+    request_body = crate::panchang::PanchangRangeRequest,
+    responses(
+        (status = 200, description = "Panchang for each day of the range", body = ApiResponse<Vec<crate::panchang::PanchangDay>>),
+        (status = 400, description = "Invalid input, with the reason in `message`", body = ApiResponse<serde_json::Value>),
+        (status = 406, description = "The requested response version is not 1 or 2", body = ApiResponse<serde_json::Value>),
+        (status = 409, description = "The `Idempotency-Key` was already used for a different request", body = ApiResponse<serde_json::Value>),
+        (status = 413, description = "The body is larger than `MAX_BODY_BYTES`", body = ApiResponse<serde_json::Value>),
+        (status = 422, description = "The date does not exist or is outside `SUPPORTED_YEARS`", body = ApiResponse<serde_json::Value>),
+        (status = 429, description = "Rate limit exceeded, see the `Retry-After` header", body = ApiResponse<serde_json::Value>),
+        (status = 504, description = "The calculation took longer than `CALCULATION_TIMEOUT_MS`", body = ApiResponse<serde_json::Value>),
+    ),
+))]
+#[post("/panchang/range")]
+async fn panchang_range_handler(req: HttpRequest, request_id: RequestId, state: web::Data<AppState>, flags: web::Query<Flags>, data: web::Json<crate::panchang::PanchangRangeRequest>) -> impl Responder {
+    let version = match requested_version(&req) {
+        Ok(version) => version,
+        Err(error) => return error_response(request_id, error.status_code(), error.code(), error.to_string()),
+    };
+    let data = data.into_inner().map_options(|options| options.with_verbose(flags.verbose).with_longitudes(flags.longitudes).with_default_ayanamsa(state.default_ayanamsa)).with_default_location(&state.default_location);
+    let data = data.with_supported_years(state.supported_years.clone()).with_max_days(state.max_range_days);
+    let fields = data.options().fields().cloned();
+    let mut result = within_budget(&state, async move { crate::panchang::calculate_panchang_range(&data).await }).await;
+    if let Some(language) = requested_language(&req) {
+        result = result.map(|days| days.into_iter().map(|day| day.with_labels(language)).collect());
+    }
+    envelope(request_id, version, result.map(|days| Selected::new(Versioned::new(days, version), fields)), "Panchang range fetched successfully")
+}
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    get,
+    path = "/panchang/range.ndjson",
+    tag = "panchang",
+    params(crate::panchang::PanchangRangeRequest, crate::panchang::RequestCalendar, crate::panchang::RequestLocation, crate::panchang::RequestOptions, ("lang" = Option<String>, Query, description = "Language of the `labels`, `en`, `hi` or `ta`; overrides `Accept-Language`"), ("v" = Option<u8>, Query, description = "Response schema version, `1` for the six original limbs (`PanchangResponseV1`) or `2` (default) for the full response; overrides a `application/vnd.panchang.v1+json` `Accept` header")),
+    responses(
+        (status = 200, description = "Panchang of each day of the range, streamed as one JSON object per line as it is calculated. A day that cannot be calculated ends the stream with an error envelope as the last line, whose `statusCode` is 422 for an invalid day, 500 (`INTERNAL_ERROR`) for a failed calculation or 504 (`CALCULATION_TIMEOUT`) for one taking longer than `CALCULATION_TIMEOUT_MS`", body = crate::panchang::PanchangDay, content_type = "application/x-ndjson"),
+        (status = 400, description = "Invalid input, with the reason in `message`", body = ApiResponse<serde_json::Value>),
+        (status = 406, description = "The requested response version is not 1 or 2", body = ApiResponse<serde_json::Value>),
+        (status = 422, description = "The date does not exist or is outside `SUPPORTED_YEARS`, or the range spans more than `MAX_RANGE_DAYS` days", body = ApiResponse<serde_json::Value>),
+        (status = 429, description = "Rate limit exceeded, see the `Retry-After` header", body = ApiResponse<serde_json::Value>),
+    ),
+))]
+#[get("/panchang/range.ndjson")]
+async fn panchang_range_ndjson_handler(req: HttpRequest, request_id: RequestId, state: web::Data<AppState>, data: web::Query<crate::panchang::PanchangRangeRequest>) -> HttpResponse {
+    let version = match requested_version(&req) {
+        Ok(version) => version,
+        Err(error) => return error_response(request_id, error.status_code(), error.code(), error.to_string()),
+    };
+    let data = data.into_inner().map_options(|options| options.with_default_ayanamsa(state.default_ayanamsa)).with_default_location(&state.default_location);
+    let data = data.with_supported_years(state.supported_years.clone()).with_max_days(state.max_range_days);
+    // The whole range is checked before the first line is sent
+    let days = match data.days() {
+        Ok(days) => days,
+        Err(error) => return error_response(request_id, error.status_code(), error.code(), error.to_string()),
+    };
+    let (fields, language) = (data.options().fields().cloned(), requested_language(&req));
+
+    // Each day is calculated within the calculation budget, at most NDJSON_BUFFERED_DAYS
+    // ahead of the client, so memory stays flat however long the range
+    let (lines, body) = tokio::sync::mpsc::channel(NDJSON_BUFFERED_DAYS);
+    actix_web::rt::spawn(async move {
+        for request in days {
+            let result = within_budget(&state, async move { crate::panchang::calculate_panchang_day(&request).await }).await;
+            let line = match result {
+                Ok(day) => {
+                    let day = match language {
+                        Some(language) => day.with_labels(language),
+                        None => day,
+                    };
+                    ndjson_line(&Selected::new(Versioned::new(day, version), fields.clone()))
+                },
+                // A day failing, panicking or running out of time ends the stream with
+                // the error envelope as its last line
+                Err(error) => {
+                    let response: ApiResponse<()> = ApiResponse {
+                        status: "error".to_string(),
+                        status_code: error.status_code().as_u16(),
+                        api_version: version.number(),
+                        message: Some(error.to_string()),
+                        code: Some(error.code().to_string()),
+                        data: None,
+                        timestamp: Utc::now(),
+                        request_id: request_id.0,
+                    };
+                    let _ = lines.send(ndjson_line(&response)).await;
+                    break;
+                },
+            };
+            // The client going away closes the channel, and nothing more is calculated
+            if lines.send(line).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    HttpResponse::Ok().content_type(NDJSON_CONTENT_TYPE).body(NdjsonBody(body))
+}
+
+/// Serializes a value as one line of an NDJSON body
+fn ndjson_line<T: Serialize>(value: &T) -> web::Bytes {
+    let mut line = serde_json::to_vec(value).expect("responses always serialize");
+    line.push(b'\n');
+    web::Bytes::from(line)
+}
+
+/// Streamed body of an NDJSON response, sending each line as its own chunk as soon as
+/// it is calculated
+struct NdjsonBody(tokio::sync::mpsc::Receiver<web::Bytes>);
+
+impl MessageBody for NdjsonBody {
+    type Error = Infallible;
+
+    fn size(&self) -> BodySize {
+        BodySize::Stream
+    }
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<web::Bytes, Infallible>>> {
+        self.0.poll_recv(cx).map(|line| line.map(Ok))
+    }
+}
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    post,
+    path = "/panchang/next-tithi",
+    tag = "panchang",
+    params(("lang" = Option<String>, Query, description = "Language of the `labels`, `en`, `hi` or `ta`; overrides `Accept-Language`"), ("v" = Option<u8>, Query, description = "Response schema version, `1` for the original shape (`TithiOccurrenceV1`) or `2` (default) for the full response; overrides a `application/vnd.panchang.v1+json` `Accept` header")),
+    request_body = crate::panchang::NextTithiRequest,
+    responses(
+        (status = 200, description = "First date from `from_date` whose sunrise falls in the Tithi", body = ApiResponse<crate::panchang::TithiOccurrence>),
+        (status = 400, description = "Invalid input, with the reason in `message`", body = ApiResponse<serde_json::Value>),
+        (status = 406, description = "The requested response version is not 1 or 2", body = ApiResponse<serde_json::Value>),
+        (status = 409, description = "The `Idempotency-Key` was already used for a different request", body = ApiResponse<serde_json::Value>),
+        (status = 413, description = "The body is larger than `MAX_BODY_BYTES`", body = ApiResponse<serde_json::Value>),
+        (status = 422, description = "The Tithi does not prevail at sunrise within 60 days, or `from_date` is outside `SUPPORTED_YEARS`", body = ApiResponse<serde_json::Value>),
+        (status = 429, description = "Rate limit exceeded, see the `Retry-After` header", body = ApiResponse<serde_json::Value>),
+        (status = 504, description = "The calculation took longer than `CALCULATION_TIMEOUT_MS`", body = ApiResponse<serde_json::Value>),
+    ),
+))]
+#[post("/panchang/next-tithi")]
+async fn panchang_next_tithi_handler(req: HttpRequest, request_id: RequestId, state: web::Data<AppState>, data: web::Json<crate::panchang::NextTithiRequest>) -> impl Responder {
+    let version = match requested_version(&req) {
+        Ok(version) => version,
+        Err(error) => return error_response(request_id, error.status_code(), error.code(), error.to_string()),
+    };
+    let data = data.into_inner().with_supported_years(state.supported_years.clone()).with_default_location(&state.default_location);
+    let mut result = within_budget(&state, async move { crate::panchang::calculate_next_tithi(&data).await }).await;
+    if let Some(language) = requested_language(&req) {
+        result = result.map(|occurrence| occurrence.with_labels(language));
+    }
+    envelope(request_id, version, result.map(|occurrence| Versioned::new(occurrence, version)), "Next Tithi found successfully")
+}
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    post,
+    path = "/panchang/compare",
+    tag = "panchang",
+    params(("lang" = Option<String>, Query, description = "Language of the `labels`, `en`, `hi` or `ta`; overrides `Accept-Language`"), ("v" = Option<u8>, Query, description = "Response schema version, `1` for the original shape (`PanchangComparisonV1`) or `2` (default) for the full response; overrides a `application/vnd.panchang.v1+json` `Accept` header")),
+    request_body = crate::panchang::PanchangCompareRequest,
+    responses(
+        (status = 200, description = "Panchang in each Ayanamsa system, with the limbs that differ", body = ApiResponse<crate::panchang::PanchangComparison>),
+        (status = 400, description = "Invalid input, with the reason in `message`", body = ApiResponse<serde_json::Value>),
+        (status = 406, description = "The requested response version is not 1 or 2", body = ApiResponse<serde_json::Value>),
+        (status = 409, description = "The `Idempotency-Key` was already used for a different request", body = ApiResponse<serde_json::Value>),
+        (status = 413, description = "The body is larger than `MAX_BODY_BYTES`", body = ApiResponse<serde_json::Value>),
+        (status = 422, description = "The date does not exist or is outside `SUPPORTED_YEARS`", body = ApiResponse<serde_json::Value>),
+        (status = 429, description = "Rate limit exceeded, see the `Retry-After` header", body = ApiResponse<serde_json::Value>),
+        (status = 504, description = "The calculation took longer than `CALCULATION_TIMEOUT_MS`", body = ApiResponse<serde_json::Value>),
+    ),
+))]
+#[post("/panchang/compare")]
+async fn panchang_compare_handler(req: HttpRequest, request_id: RequestId, state: web::Data<AppState>, data: web::Json<crate::panchang::PanchangCompareRequest>) -> impl Responder {
+    let version = match requested_version(&req) {
+        Ok(version) => version,
+        Err(error) => return error_response(request_id, error.status_code(), error.code(), error.to_string()),
+    };
+    let data = data.into_inner().with_supported_years(state.supported_years.clone()).with_default_location(&state.default_location);
+    let mut result = within_budget(&state, async move { crate::panchang::calculate_panchang_comparison(&data).await }).await;
+    if let Some(language) = requested_language(&req) {
+        result = result.map(|comparison| comparison.with_labels(language));
+    }
+    envelope(request_id, version, result.map(|comparison| Versioned::new(comparison, version)), "Ayanamsa comparison calculated successfully")
+}
+
+/// Fetches the Panchang for a request from the in-memory cache, or else through the
+/// database cache when a database is configured, labels it in the requested language
+/// and wraps it in the API envelope
+async fn panchang_response(request_id: RequestId, state: &AppState, data: crate::panchang::PanchangRequest, language: Option<Language>, version: ApiVersion) -> HttpResponse {
+    // Validated first, so the requests the caches answer alike share a key
+    let validated = match crate::panchang::ValidatedRequest::try_from(&data) {
+        Ok(validated) => validated,
+        Err(error) => return versioned_error_response(request_id, version, error.status_code(), error.code(), error.to_string()),
+    };
+    let (pool, fields, key) = (state.pool.clone(), data.options().fields().cloned(), crate::cache::cache_key(&validated));
+    let cached = state.memory_cache.get(&key);
+    state.metrics.observe_memory_cache(cached.is_some());
+    let mut result = match cached {
+        Some(panchang) => Ok(panchang),
+        None => within_budget(state, async move {
+            Ok(match &pool {
+                Some(pool) => crate::cache::get_or_compute(pool, &validated).await,
+                None => crate::panchang::calculate_panchang_validated(&validated),
+            })
+        })
+        .await
+        .inspect(|panchang| state.memory_cache.insert(key, panchang.clone())),
+    };
+    if let Some(language) = language {
+        result = result.map(|panchang| panchang.with_labels(language));
+    }
+    envelope(request_id, version, result.map(|panchang| Selected::new(Versioned::new(panchang, version), fields)), "Panchang data fetched successfully")
+}
+
+/// Wraps a calculation result in the API envelope, using the error's status on failure
+fn envelope<T: Serialize>(request_id: RequestId, version: ApiVersion, result: Result<T, PanchangError>, success_message: &str) -> HttpResponse {
+    match result {
+        Ok(data) => {
+            let response: ApiResponse<T> = ApiResponse {
                 status: "success".to_string(),
                 status_code: 200,
-                message: Some("Panchang data fetched successfully".into()),
-                data: Some(panchang_data),
+                api_version: version.number(),
+                message: Some(success_message.into()),
+                code: None,
+                data: Some(data),
                 timestamp: Utc::now(),
-                request_id: "12345".to_string(),
+                request_id: request_id.0,
             };
             HttpResponse::Ok().json(response)
         },
-        Err(error) => {
-            let response: ApiResponse<crate::panchang::PanchangResponse> = ApiResponse {
-                status: "error".to_string(),
-                status_code: 400,
-                message: Some(error.into()),
-                data: None,
-                timestamp: Utc::now(),
-                request_id: "12345".to_string(),
-            };
-            HttpResponse::BadRequest().json(response)
+        Err(error) => versioned_error_response(request_id, version, error.status_code(), error.code(), error.to_string()),
+    }
+}
+
+/// Builds an error envelope without data
+pub(crate) fn error_response(request_id: RequestId, status_code: StatusCode, code: &str, message: String) -> HttpResponse {
+    versioned_error_response(request_id, ApiVersion::default(), status_code, code, message)
+}
+
+/// Builds an error envelope without data for a request that asked for `version`
+fn versioned_error_response(request_id: RequestId, version: ApiVersion, status_code: StatusCode, code: &str, message: String) -> HttpResponse {
+    let response: ApiResponse<()> = ApiResponse {
+        status: "error".to_string(),
+        status_code: status_code.as_u16(),
+        api_version: version.number(),
+        message: Some(message),
+        code: Some(code.to_string()),
+        data: None,
+        timestamp: Utc::now(),
+        request_id: request_id.0,
+    };
+    HttpResponse::build(status_code).json(response)
+}
+
+/// Methods served on each path, for the `Allow` header of a 405
+const ALLOWED_METHODS: [(&str, &str); 10] = [
+    ("/health", "GET"),
+    ("/healthz", "GET"),
+    ("/readyz", "GET"),
+    ("/metrics", "GET"),
+    ("/panchang", "GET, POST"),
+    ("/panchang/now", "GET"),
+    ("/panchang/range", "POST"),
+    ("/panchang/range.ndjson", "GET"),
+    ("/panchang/next-tithi", "POST"),
+    ("/panchang/compare", "POST"),
+];
+
+/// Answers a path no route matches with a 404 in the API envelope
+pub(crate) async fn not_found(req: HttpRequest) -> HttpResponse {
+    error_response(request_id_of(&req), StatusCode::NOT_FOUND, "NOT_FOUND", "Not Found".to_string())
+}
+
+/// Answers a known path requested with a method it does not serve with a 405 in
+/// the API envelope, naming the methods it does serve
+fn method_not_allowed(req: &HttpRequest, allowed: &'static str) -> HttpResponse {
+    let message = format!("Method Not Allowed, {} accepts {}", req.path(), allowed);
+    let mut response = error_response(request_id_of(req), StatusCode::METHOD_NOT_ALLOWED, "METHOD_NOT_ALLOWED", message);
+    response.headers_mut().insert(header::ALLOW, HeaderValue::from_static(allowed));
+    response
+}
+
+/// Reads the request id assigned by the middleware to a request
+fn request_id_of(req: &HttpRequest) -> RequestId {
+    req.extensions().get::<RequestId>().cloned().unwrap_or_default()
+}
+
+/// Wraps a JSON body that cannot be read in the API envelope, with a 413 when it
+/// is larger than allowed and a 400 otherwise
+pub fn json_error_handler(err: JsonPayloadError, req: &HttpRequest) -> actix_web::Error {
+    let too_large = match &err {
+        JsonPayloadError::OverflowKnownLength { length, limit } => {
+            Some(format!("Request body is {} bytes, at most {} are allowed", length, limit))
         }
+        JsonPayloadError::Overflow { limit } => Some(format!("Request body is over the {} bytes allowed", limit)),
+        _ => None,
+    };
+    if let Some(message) = too_large {
+        let response = error_response(request_id_of(req), StatusCode::PAYLOAD_TOO_LARGE, "PAYLOAD_TOO_LARGE", message);
+        return InternalError::from_response(err, response).into();
     }
+
+    let message = match &err {
+        JsonPayloadError::ContentType => "Content-Type must be application/json".to_string(),
+        JsonPayloadError::Deserialize(reason) => format!("Invalid request body: {}", reason),
+        other => format!("Invalid request body: {}", other),
+    };
+    let response = error_response(request_id_of(req), StatusCode::BAD_REQUEST, "INVALID_BODY", message);
+    InternalError::from_response(err, response).into()
 }
 
+/// Wraps a query string that cannot be read in the API envelope with a 400
+pub fn query_error_handler(err: QueryPayloadError, req: &HttpRequest) -> actix_web::Error {
+    let message = match &err {
+        QueryPayloadError::Deserialize(reason) => format!("Invalid query string: {}", reason),
+        other => format!("Invalid query string: {}", other),
+    };
+    let response = error_response(request_id_of(req), StatusCode::BAD_REQUEST, "INVALID_QUERY", message);
+    InternalError::from_response(err, response).into()
+}
 
 pub fn init(cfg: &mut web::ServiceConfig) {
-    cfg.service(health_chcek);
+    cfg.service(health_check);
+    cfg.service(liveness);
+    cfg.service(readiness);
+    cfg.service(metrics);
     cfg.service(panchang_handler);
+    cfg.service(panchang_now_handler);
+    cfg.service(panchang_query_handler);
+    cfg.service(panchang_range_ndjson_handler);
+    cfg.service(panchang_range_handler);
+    cfg.service(panchang_next_tithi_handler);
+    cfg.service(panchang_compare_handler);
+
+    #[cfg(feature = "openapi")]
+    crate::openapi::init(cfg);
+
+    // Registered last, so only requests the routes above turn down reach them
+    for (path, allowed) in ALLOWED_METHODS {
+        cfg.service(web::resource(path).to(move |req: HttpRequest| async move { method_not_allowed(&req, allowed) }));
+    }
 }
@@ -1,34 +1,216 @@
-use actix_web::{web,App, HttpServer};
+use actix_cors::Cors;
+use actix_web::{web,App, HttpServer, http};
+use actix_web::http::KeepAlive;
+use actix_web::middleware::{Condition, from_fn};
 use crate::routes;
 use std::error::Error;
-use dotenv::dotenv;
-use std::env;
+use std::ops::RangeInclusive;
+use std::time::Duration;
+use tokio::signal;
+use tokio::sync::watch;
 
+use crate::cache::MemoryCache;
+use crate::config::Config;
 use crate::db;
+use crate::idempotency::{IDEMPOTENCY_KEY_HEADER, REPLAYED_HEADER, idempotency};
+use crate::metrics::Metrics;
+use crate::warm;
+use crate::panchang::{Ayanamsa, Location, PanchangError};
+use crate::middleware::{REQUEST_ID_HEADER, RateLimiter, log_request, rate_limit, request_id, track_metrics};
+use sqlx::PgPool;
 
-/// Starts the HTTP server on the given port.
-pub async fn http_server() -> Result<(), Box<dyn Error>> {
-    dotenv().ok();
-    
-    let host = env::var("SERVER_HOST").unwrap_or_else(|_| String::from("127.0.0.1"));
-    let port = env::var("SERVER_PORT")
-        .unwrap_or_else(|_| String::from("8080"))
-        .parse::<u16>()
-        .expect("PORT must be a valid number");
-
-    // db conection
-    let pool = db::init_db().await;
-
-    println!("🚀 Starting server at http://{}:{}", host, port);
-
-    HttpServer::new(move || {
-        App::new()
-        .app_data(web::Data::new(pool.clone()))
-            .configure(routes::init)
+/// State shared by all workers
+pub struct AppState {
+    /// Database pool, absent when the server runs without a database
+    pub pool: Option<PgPool>,
+    /// Whether the service should report itself unavailable when the database is down
+    pub db_required: bool,
+    /// Location filling in the parts a `/panchang` or `/panchang/now` request leaves out
+    pub default_location: Location,
+    /// Ayanamsa used when a request does not name one
+    pub default_ayanamsa: Ayanamsa,
+    /// Years the Panchang endpoints calculate
+    pub supported_years: RangeInclusive<i32>,
+    /// Longest date range, in days, served by `/panchang/range`
+    pub max_range_days: u32,
+    /// Seconds an `Idempotency-Key` is remembered for
+    pub idempotency_ttl: u64,
+    /// Time a request's calculation may take before it is answered with a 504
+    pub calculation_timeout: Duration,
+    /// Responses of recent requests, checked before the database cache
+    pub memory_cache: MemoryCache,
+    /// Request, cache and database pool metrics served at `/metrics`
+    pub metrics: Metrics,
+}
+
+impl AppState {
+    /// Builds the state of a server running with `config` and the database pool, if any
+    pub fn new(config: &Config, pool: Option<PgPool>) -> Self {
+        AppState {
+            pool,
+            db_required: config.db_required,
+            default_location: config.default_location.clone(),
+            default_ayanamsa: config.default_ayanamsa,
+            supported_years: config.supported_years.clone(),
+            max_range_days: config.max_range_days,
+            idempotency_ttl: config.idempotency_ttl,
+            calculation_timeout: Duration::from_millis(config.calculation_timeout_ms),
+            memory_cache: MemoryCache::new(config.mem_cache_size),
+            metrics: Metrics::new(),
+        }
+    }
+}
+
+/// Runs a calculation on the blocking thread pool, so that it holds no worker, and
+/// gives up on it with a `CALCULATION_TIMEOUT` once `state.calculation_timeout` passes
+///
+/// The calculations never yield, so a timeout can only interrupt them from another
+/// thread. One given up on still runs to completion on its thread, but its worker is
+/// free to serve other requests meanwhile. A calculation that panics is logged and
+/// reported as a `CalculationFailed` error, leaving the worker serving.
+pub async fn within_budget<T: Send + 'static>(state: &AppState, calculation: impl Future<Output = Result<T, PanchangError>> + Send + 'static) -> Result<T, PanchangError> {
+    let runtime = tokio::runtime::Handle::current();
+    match actix_web::rt::time::timeout(state.calculation_timeout, web::block(move || runtime.block_on(calculation))).await {
+        Ok(Ok(result)) => result,
+        // The pool only fails a task that panicked
+        Ok(Err(err)) => {
+            tracing::error!(error = %err, "Panchang calculation panicked");
+            Err(PanchangError::CalculationFailed(err.to_string()))
+        }
+        Err(_) => Err(PanchangError::CalculationTimeout(state.calculation_timeout.as_millis())),
+    }
+}
+
+/// Reads JSON bodies of up to `limit` bytes, answering any other in the API envelope
+pub fn json_config(limit: usize) -> web::JsonConfig {
+    web::JsonConfig::default().limit(limit).error_handler(routes::json_error_handler)
+}
+
+/// Builds the CORS middleware for the comma-separated `CORS_ALLOWED_ORIGINS` list,
+/// where `*` allows any origin
+pub fn cors(allowed_origins: &[String]) -> Cors {
+    let cors = Cors::default()
+        .allowed_methods(vec!["GET", "POST"])
+        .allowed_headers(vec![
+            http::header::CONTENT_TYPE,
+            http::header::HeaderName::from_static(REQUEST_ID_HEADER),
+            http::header::HeaderName::from_static(IDEMPOTENCY_KEY_HEADER),
+        ])
+        .expose_headers(vec![REQUEST_ID_HEADER, REPLAYED_HEADER])
+        .max_age(3600);
+
+    if allowed_origins.iter().any(|origin| origin == "*") {
+        return cors.allow_any_origin();
+    }
+    allowed_origins.iter().fold(cors, |cors, origin| cors.allowed_origin(origin))
+}
+
+/// Registers the API routes, and the demo page at `/` when built with the `ui`
+/// feature, answering paths no route matches with a 404 in the API envelope
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    routes::init(cfg);
+    #[cfg(feature = "ui")]
+    crate::ui::init(cfg);
+    cfg.default_service(web::to(routes::not_found));
+}
+
+/// Waits for Ctrl-C (SIGINT) or, on Unix, SIGTERM
+async fn shutdown_signal() {
+    let interrupt = async {
+        signal::ctrl_c().await.expect("failed to listen for SIGINT");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        signal::unix::signal(signal::unix::SignalKind::terminate())
+            .expect("failed to listen for SIGTERM")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = interrupt => tracing::info!("SIGINT received"),
+        _ = terminate => tracing::info!("SIGTERM received"),
+    }
+}
+
+/// Starts the HTTP server with the given configuration.
+pub async fn http_server(config: Config) -> Result<(), Box<dyn Error>> {
+    // db conection, optional so the calculations can be served on their own
+    let pool = db::init_db(config.db.as_ref()).await?;
+    let state = web::Data::new(AppState::new(&config, pool.clone()));
+
+    let Config {
+        host, port, shutdown_timeout, workers, keep_alive, cors_origins, default_ayanamsa, rate_limit_per_min, max_body_bytes, warm, ..
+    } = config;
+
+    // Keep the popular locations in the cache, which needs the database
+    let (stop_warming, shutdown) = watch::channel(false);
+    let warmer = match (warm, &pool) {
+        (Some(settings), Some(pool)) => {
+            tracing::info!(locations = settings.locations.len(), interval = settings.interval, days = settings.days, "Warming the Panchang cache");
+            Some(actix_web::rt::spawn(warm::run(pool.clone(), settings, default_ayanamsa, shutdown)))
+        }
+        (Some(_), None) => {
+            tracing::warn!("WARM_LOCATIONS is set without a database, not warming the cache");
+            None
+        }
+        (None, _) => None,
+    };
+
+    // Shared by all workers so each client has a single bucket
+    let rate_limiter = rate_limit_per_min.map(|limit| web::Data::new(RateLimiter::new(limit)));
+
+    tracing::info!(workers, keep_alive, "Starting server at http://{}:{}", host, port);
+
+    let server = HttpServer::new(move || {
+        let mut app = App::new();
+        if let Some(rate_limiter) = &rate_limiter {
+            app = app.app_data(rate_limiter.clone());
+        }
+        app
+        .wrap(from_fn(idempotency))
+        .wrap(from_fn(track_metrics))
+        .wrap(from_fn(rate_limit))
+        .wrap(Condition::new(!cors_origins.is_empty(), cors(&cors_origins)))
+        .wrap(from_fn(log_request))
+        .wrap(from_fn(request_id))
+        .app_data(state.clone())
+        .app_data(json_config(max_body_bytes))
+        .app_data(web::PayloadConfig::new(max_body_bytes))
+        .app_data(web::QueryConfig::default().error_handler(routes::query_error_handler))
+            .configure(configure)
     })
+    .workers(workers)
+    .keep_alive(if keep_alive == 0 { KeepAlive::Disabled } else { KeepAlive::Timeout(Duration::from_secs(keep_alive)) })
+    .shutdown_timeout(shutdown_timeout)
+    .disable_signals()
     .bind((host, port))?
-    .run()
-    .await?;
+    .run();
+
+    // Stop accepting connections on a signal and let in-flight requests drain
+    let handle = server.handle();
+    actix_web::rt::spawn(async move {
+        shutdown_signal().await;
+        tracing::info!(shutdown_timeout, "Stopping server, waiting for in-flight requests");
+        handle.stop(true).await;
+    });
+
+    server.await?;
+
+    // Let a pass in progress finish before the pool closes
+    if let Some(warmer) = warmer {
+        let _ = stop_warming.send(true);
+        let _ = warmer.await;
+    }
+
+    if let Some(pool) = pool {
+        pool.close().await;
+        tracing::info!("Database pool closed");
+    }
+    tracing::info!("Server stopped");
 
     Ok(())
 }
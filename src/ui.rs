@@ -0,0 +1,16 @@
+use actix_web::http::header::ContentType;
+use actix_web::{HttpResponse, get, web};
+
+/// The demo page, its style and script inline so that it loads no other asset
+static INDEX_HTML: &str = include_str!("ui/index.html");
+
+/// Serves the demo page, whose form POSTs to `/panchang` and shows the result
+#[get("/")]
+async fn index() -> HttpResponse {
+    HttpResponse::Ok().content_type(ContentType::html()).body(INDEX_HTML)
+}
+
+/// Serves the demo page at `/`
+pub fn init(cfg: &mut web::ServiceConfig) {
+    cfg.service(index);
+}
@@ -0,0 +1,66 @@
+//! Background job keeping the Panchang of popular locations in the cache
+//!
+//! Each pass calculates the sunrise Panchang (see [`PanchangRequest::at_sunrise`])
+//! of the current local date and the next few at every configured location, so the
+//! requests a Panchang site makes for "today" are answered from the cache. The cache
+//! is keyed on the validated request, so a client's sunrise request hits the warmed
+//! entry whatever `time` it sends and however it spells the date and zone.
+
+use chrono::{DateTime, Days, Utc};
+use chrono_tz::Tz;
+use sqlx::PgPool;
+use std::time::Duration;
+use tokio::sync::watch;
+
+use crate::cache;
+use crate::config::WarmConfig;
+use crate::panchang::{Ayanamsa, Location, PanchangRequest, ValidatedRequest};
+
+/// Caches the sunrise Panchang in `ayanamsa`, the server's default, of `days` dates at
+/// each location, starting with the local date of `now` there
+///
+/// Returns how many Panchangs are now in the cache. A location whose timezone is
+/// unknown, or a date that cannot be calculated, is logged and skipped.
+pub async fn warm_cache(pool: &PgPool, locations: &[Location], ayanamsa: Ayanamsa, now: DateTime<Utc>, days: u32) -> usize {
+    let mut warmed = 0;
+    for location in locations {
+        let timezone = location.timezone.as_deref().unwrap_or_default();
+        let Ok(tz) = timezone.parse::<Tz>() else {
+            tracing::warn!(timezone, "Unknown timezone, not warming the location");
+            continue;
+        };
+        let today = now.with_timezone(&tz).date_naive();
+
+        for offset in 0..days {
+            let Some(date) = today.checked_add_days(Days::new(offset.into())) else {
+                break;
+            };
+            let request = PanchangRequest::at_sunrise(&date.format("%d/%m/%Y").to_string(), location)
+                .map_options(|options| options.with_default_ayanamsa(ayanamsa));
+            match ValidatedRequest::try_from(&request) {
+                Ok(validated) => {
+                    cache::get_or_compute(pool, &validated).await;
+                    warmed += 1;
+                }
+                Err(err) => tracing::warn!(error = %err, %date, timezone, "Failed to warm the cache"),
+            }
+        }
+    }
+    warmed
+}
+
+/// Warms the cache every `config.interval` seconds, starting right away, until
+/// `shutdown` changes
+pub async fn run(pool: PgPool, config: WarmConfig, ayanamsa: Ayanamsa, mut shutdown: watch::Receiver<bool>) {
+    let mut interval = tokio::time::interval(Duration::from_secs(config.interval));
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {}
+            _ = shutdown.changed() => break,
+        }
+
+        let warmed = warm_cache(&pool, &config.locations, ayanamsa, Utc::now(), config.days).await;
+        tracing::info!(warmed, locations = config.locations.len(), "Panchang cache warmed");
+    }
+    tracing::info!("Cache warming stopped");
+}
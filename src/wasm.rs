@@ -0,0 +1,34 @@
+//! JavaScript bindings, so a static frontend can calculate the Panchang in the
+//! browser without a round-trip to the server
+//!
+//! Build with `wasm-pack build --target web --no-default-features --features wasm`.
+
+use std::future::Future;
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::panchang::{PanchangRequest, calculate_panchang};
+
+/// Calculates the Panchang for a local date and time at a location
+///
+/// Takes the date in DD/MM/YYYY format, the time in HH:MM 24-hour format, the
+/// timezone offset in [+/-]HH:MM, [+/-]HH or Z format, and the observer's latitude
+/// and longitude in degrees. Returns an object with the fields of the `/panchang`
+/// response, or throws an `Error` with the same message the API would send.
+#[wasm_bindgen(js_name = calculatePanchanga)]
+pub fn calculate_panchanga(date: &str, time: &str, zone: &str, latitude: f64, longitude: f64) -> Result<JsValue, JsError> {
+    let request = PanchangRequest::new(date, time, zone, latitude, longitude);
+
+    // calculate_panchang never awaits, so its first poll completes it
+    let response = match pin!(calculate_panchang(&request)).poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(response) => response.map_err(|error| JsError::new(&error.to_string()))?,
+        Poll::Pending => unreachable!("calculate_panchang does not await"),
+    };
+
+    // Maps become plain objects, as they are in the JSON response
+    let serializer = serde_wasm_bindgen::Serializer::json_compatible();
+    response.serialize(&serializer).map_err(|error| JsError::new(&error.to_string()))
+}
@@ -0,0 +1,98 @@
+//! Checks Abhijit, carved out of Uttara Ashada and Sravana from 276°40' to 280°53'20"
+//! in the 28-Nakshatra system, by longitude and in the Panchang of 1 August 2023,
+//! when the Moon was in Uttara Ashada's last pada.
+
+use panchang_rs::panchang::{NakshatraSystem, nakshatra_name};
+use serde_json::{Value, json};
+
+mod common;
+
+/// Start and end of Abhijit's arc, in degrees
+const ABHIJIT_START: f64 = 276.0 + 40.0 / 60.0;
+const ABHIJIT_END: f64 = 280.0 + 53.0 / 60.0 + 20.0 / 3600.0;
+
+/// The Panchang at noon IST in New Delhi on 1 August 2023 in a Nakshatra system
+fn panchang(nakshatra_system: u8) -> Value {
+    common::panchang_json(&common::delhi_with(
+        "01/08/2023",
+        "12:00",
+        json!({ "nakshatra_system": nakshatra_system, "longitudes": true }),
+    ))
+}
+
+#[test]
+fn arc_is_abhijit_only_with_28_nakshatras() {
+    // Uttara Ashada's last pada, then the first fifteenth of Sravana
+    for (longitude, in_27) in [(278.0, "Uttara Ashada"), (280.5, "Sravana")] {
+        assert_eq!(
+            nakshatra_name(longitude, NakshatraSystem::TwentySeven),
+            in_27
+        );
+        assert_eq!(
+            nakshatra_name(longitude, NakshatraSystem::TwentyEight),
+            "Abhijit"
+        );
+    }
+}
+
+#[test]
+fn arc_bounds() {
+    let (before, after) = (ABHIJIT_START - 1e-6, ABHIJIT_END + 1e-6);
+    assert_eq!(
+        nakshatra_name(before, NakshatraSystem::TwentyEight),
+        "Uttara Ashada"
+    );
+    assert_eq!(
+        nakshatra_name(ABHIJIT_START, NakshatraSystem::TwentyEight),
+        "Abhijit"
+    );
+    assert_eq!(
+        nakshatra_name(ABHIJIT_END - 1e-6, NakshatraSystem::TwentyEight),
+        "Abhijit"
+    );
+    assert_eq!(
+        nakshatra_name(after, NakshatraSystem::TwentyEight),
+        "Sravana"
+    );
+    // The rest of the zodiac is the same in both systems
+    for longitude in (0..360)
+        .map(f64::from)
+        .filter(|longitude| !(ABHIJIT_START..ABHIJIT_END).contains(longitude))
+    {
+        assert_eq!(
+            nakshatra_name(longitude, NakshatraSystem::TwentyEight),
+            nakshatra_name(longitude, NakshatraSystem::TwentySeven),
+            "{}°",
+            longitude
+        );
+    }
+}
+
+#[test]
+fn response_follows_the_system() {
+    let twenty_seven = panchang(27);
+    let twenty_eight = panchang(28);
+    let longitude = twenty_seven["moon_longitude"].as_f64().unwrap();
+    assert!(
+        (ABHIJIT_START..ABHIJIT_END).contains(&longitude),
+        "{}",
+        longitude
+    );
+
+    assert_eq!(twenty_seven["nakshatra"], "Uttara Ashada");
+    assert_eq!(twenty_eight["nakshatra"], "Abhijit");
+    assert_eq!(twenty_eight["tithi"], twenty_seven["tithi"]);
+}
+
+#[test]
+fn only_27_and_28_are_systems() {
+    assert_eq!(
+        serde_json::from_value::<NakshatraSystem>(json!(27)).unwrap(),
+        NakshatraSystem::TwentySeven
+    );
+    assert_eq!(
+        serde_json::from_value::<NakshatraSystem>(json!(28)).unwrap(),
+        NakshatraSystem::TwentyEight
+    );
+    assert!(serde_json::from_value::<NakshatraSystem>(json!(26)).is_err());
+}
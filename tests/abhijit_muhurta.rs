@@ -0,0 +1,33 @@
+//! Checks `abhijit_muhurta`, the eighth of the fifteen muhurtas of the daytime,
+//! centred on local midday and void on Wednesdays.
+
+use panchang_rs::panchang::{abhijit_muhurta, sunrise_sunset};
+
+#[test]
+fn centred_on_midday_except_on_wednesday() {
+    // A day from 06:00 to 18:00 has muhurtas of 48 minutes
+    let (start, end) = abhijit_muhurta(6.0, 18.0, 0).unwrap();
+    assert!((start - 11.6).abs() < 1e-9, "{start}");
+    assert!((end - 12.4).abs() < 1e-9, "{end}");
+
+    assert!(abhijit_muhurta(6.0, 18.0, 3).is_none());
+    assert!(abhijit_muhurta(6.0, 18.0, 10).is_none());
+}
+
+#[test]
+fn delhi_on_independence_day_2023() {
+    // Tuesday 15 August, when NOAA's sunrise at 05:50 and sunset at 19:01 put local
+    // midday at 12:25 and make each muhurta about 53 minutes
+    let (sunrise, sunset) = sunrise_sunset(15, 8, 2023, 28.6139, 77.2090, 5.5).unwrap();
+    let (start, end) = abhijit_muhurta(sunrise, sunset, 2).unwrap();
+    let midday = 12.0 + 25.5 / 60.0;
+    assert!(
+        ((start + end) / 2.0 - midday).abs() <= 1.0 / 60.0,
+        "{start}-{end}"
+    );
+    assert!(((end - start) * 60.0 - 52.7).abs() < 0.5, "{start}-{end}");
+
+    // The next day is a Wednesday
+    let (sunrise, sunset) = sunrise_sunset(16, 8, 2023, 28.6139, 77.2090, 5.5).unwrap();
+    assert!(abhijit_muhurta(sunrise, sunset, 3).is_none());
+}
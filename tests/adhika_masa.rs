@@ -0,0 +1,39 @@
+//! Checks the Adhika Shravana of 2023, which ran from the new moon of 17 July to
+//! that of 16 August, between Ashadha and the Nija (regular) Shravana.
+
+use panchang_rs::{Ayanamsa, Panchanga, calculate_panchanga};
+
+/// The Panchanga at noon in New Delhi
+fn at_noon(day: i32, month: i32, year: i32) -> Panchanga {
+    calculate_panchanga(day, month, year, 12.0, 5.5, Ayanamsa::Lahiri)
+}
+
+#[test]
+fn adhika_shravana_2023() {
+    let ashadha = at_noon(10, 7, 2023);
+    assert_eq!(ashadha.current_masa, "Ashadha");
+    assert!(!ashadha.is_adhika_masa);
+
+    for day in [19, 25, 31] {
+        let adhika = at_noon(day, 7, 2023);
+        assert_eq!(adhika.current_masa, "Shravana", "{day} July");
+        assert!(adhika.is_adhika_masa, "{day} July");
+    }
+    let adhika = at_noon(15, 8, 2023);
+    assert_eq!(adhika.current_masa, "Shravana");
+    assert!(adhika.is_adhika_masa);
+
+    // An Adhika month has the same name in the Purnimanta reckoning
+    assert_eq!(adhika.current_purnimanta_masa, "Shravana");
+
+    let nija = at_noon(25, 8, 2023);
+    assert_eq!(nija.current_masa, "Shravana");
+    assert!(!nija.is_adhika_masa);
+}
+
+#[test]
+fn no_adhika_masa_in_2022() {
+    for month in 1..=12 {
+        assert!(!at_noon(15, month, 2022).is_adhika_masa, "month {month}");
+    }
+}
@@ -0,0 +1,29 @@
+//! Checks that `at_sunrise` calculates the limbs at the local sunrise of the date
+//! rather than at `time`. In New Delhi on 11 August 2023, Krishna Ekadashi began at
+//! about 05:07 IST, between midnight and the sunrise at 05:48.
+
+use serde_json::{Value, json};
+
+mod common;
+
+/// The Panchang of 11 August 2023 in New Delhi at `time`, or at sunrise
+fn panchang(time: &str, at_sunrise: bool) -> Value {
+    common::panchang_json(&common::delhi_with(
+        "11/08/2023",
+        time,
+        json!({ "at_sunrise": at_sunrise }),
+    ))
+}
+
+#[test]
+fn sunrise_tithi_differs_from_midnight() {
+    let midnight = panchang("00:00", false);
+    let sunrise = panchang("00:00", true);
+
+    assert_eq!(midnight["tithi"], "Dashami");
+    assert_eq!(sunrise["tithi"], "Ekadashi");
+    assert_eq!(sunrise["paksha"]["name"], "Krishna");
+
+    // The requested time makes no difference at sunrise
+    assert_eq!(panchang("23:00", true), sunrise);
+}
@@ -0,0 +1,36 @@
+//! Checks `ayana_ritu` on either side of the sidereal solstices, Makara (270°) and
+//! Karka (90°) Sankranti, and the equinoxes, Mesha (0°) and Tula (180°) Sankranti.
+
+use panchang_rs::panchang::ayana_ritu;
+
+#[test]
+fn solstices_turn_the_ayana() {
+    let table = [
+        (269.99, ("Dakshinayana", "Hemanta")),
+        (270.0, ("Uttarayana", "Shishira")),
+        (89.99, ("Uttarayana", "Grishma")),
+        (90.0, ("Dakshinayana", "Varsha")),
+    ];
+    for (longitude, expected) in table {
+        assert_eq!(ayana_ritu(longitude), expected, "{longitude}°");
+    }
+}
+
+#[test]
+fn equinoxes_fall_within_a_ritu() {
+    let table = [
+        (359.99, ("Uttarayana", "Vasanta")),
+        (0.0, ("Uttarayana", "Vasanta")),
+        (360.0, ("Uttarayana", "Vasanta")),
+        (-0.01, ("Uttarayana", "Vasanta")),
+        (179.99, ("Dakshinayana", "Sharad")),
+        (180.0, ("Dakshinayana", "Sharad")),
+    ];
+    for (longitude, expected) in table {
+        assert_eq!(ayana_ritu(longitude), expected, "{longitude}°");
+    }
+
+    // Vasanta begins at Meena Sankranti (330°)
+    assert_eq!(ayana_ritu(329.99).1, "Shishira");
+    assert_eq!(ayana_ritu(330.0).1, "Vasanta");
+}
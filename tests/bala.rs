@@ -0,0 +1,47 @@
+//! Checks `tarabala` and `chandrabala`: the janma Nakshatra and Rashi themselves
+//! count as 1, and the counts wrap from Revati and Meena to the start.
+
+use panchang_rs::panchang::{chandrabala, tarabala};
+
+#[test]
+fn janma_nakshatra_is_janma_tara() {
+    for nakshatra in 0..27 {
+        let bala = tarabala(nakshatra, nakshatra);
+        assert_eq!(bala.count, 1);
+        assert_eq!(bala.tara, "Janma");
+        assert!(!bala.favourable);
+    }
+}
+
+#[test]
+fn taras_repeat_every_nine_nakshatras() {
+    // From a Rohini janma, Mrigashira is Sampat and Ardra is Vipat
+    assert_eq!(tarabala(4, 3).tara, "Sampat");
+    assert_eq!(tarabala(5, 3).tara, "Vipat");
+
+    // Ashwini, counted from Revati, is the second Nakshatra
+    let bala = tarabala(0, 26);
+    assert_eq!((bala.count, bala.tara), (2, "Sampat"));
+
+    // Krittika, counted from Bharani, is the second, and again the eleventh and twentieth
+    assert_eq!(tarabala(2, 1).tara, tarabala(11, 1).tara);
+    assert_eq!(tarabala(2, 1).tara, tarabala(20, 1).tara);
+    assert_eq!(tarabala(9, 1).tara, "Parama Mitra");
+}
+
+#[test]
+fn janma_rashi_is_favourable() {
+    for rashi in 0..12 {
+        let bala = chandrabala(rashi, rashi);
+        assert_eq!(bala.position, 1);
+        assert!(bala.favourable);
+    }
+
+    // Mesha is the second Rashi from Meena and gives no Chandrabala
+    let bala = chandrabala(0, 11);
+    assert_eq!(bala.position, 2);
+    assert!(!bala.favourable);
+
+    // The eighth, Ashtama Chandra, is unfavourable
+    assert!(!chandrabala(7, 0).favourable);
+}
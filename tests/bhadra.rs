@@ -0,0 +1,55 @@
+//! Checks the Bhadra (Visti Karana) window against Raksha Bandhan 2023 in New Delhi,
+//! whose Bhadra, face and tail are published, and that a day without it has none.
+
+use chrono::DateTime;
+use serde_json::Value;
+
+mod common;
+
+/// The Panchang at noon IST in New Delhi on `date`
+fn panchang(date: &str) -> Value {
+    common::panchang_json(&common::delhi(date, "12:00"))
+}
+
+/// Asserts that the instant `actual` is within five minutes of `expected`
+fn assert_near(actual: &Value, expected: &str) {
+    let actual = DateTime::parse_from_rfc3339(actual.as_str().unwrap()).unwrap();
+    let expected = DateTime::parse_from_rfc3339(expected).unwrap();
+    let minutes = (actual - expected).num_minutes().abs();
+    assert!(
+        minutes <= 5,
+        "{} is {} minutes from {}",
+        actual,
+        minutes,
+        expected
+    );
+}
+
+#[test]
+fn raksha_bandhan_bhadra_matches_published_times() {
+    let bhadra = &panchang("30/08/2023")["bhadra"];
+
+    // The first half of Shukla Purnima, from 10:58 to 21:01 IST
+    assert_near(&bhadra["start"], "2023-08-30T10:58:00+05:30");
+    assert_near(&bhadra["end"], "2023-08-30T21:01:00+05:30");
+    assert_eq!(bhadra["paksha"], "Shukla");
+    assert_eq!(bhadra["form"], "Vrishchiki");
+    assert_eq!(bhadra["avoid"], "tail");
+    assert_eq!(bhadra["kind"], "night");
+
+    // Its tail falls before its face on a Purnima
+    assert_near(&bhadra["tail"]["start"], "2023-08-30T17:30:00+05:30");
+    assert_near(&bhadra["tail"]["end"], "2023-08-30T18:31:00+05:30");
+    assert_near(&bhadra["face"]["start"], "2023-08-30T18:31:00+05:30");
+    assert_near(&bhadra["face"]["end"], "2023-08-30T20:11:00+05:30");
+
+    // A night Bhadra whose middle falls by day is held harmless
+    assert_eq!(bhadra["by_day"], true);
+    assert_eq!(bhadra["harmless"], true);
+}
+
+#[test]
+fn day_without_visti_has_no_bhadra() {
+    // Shukla Pratipada to Dvitiya, far from any Visti
+    assert_eq!(panchang("17/08/2023")["bhadra"], Value::Null);
+}
@@ -0,0 +1,90 @@
+//! Checks that request bodies over the configured size are refused in the API
+//! envelope, and that `/panchang/range` caps its days whatever the body size.
+
+#![cfg(feature = "server")]
+
+use actix_web::http::StatusCode;
+use actix_web::{App, test, web};
+use panchang_rs::config::Config;
+use panchang_rs::server::{self, AppState};
+use serde_json::{Value, json};
+
+/// Configures a server without a database from `vars` as the environment
+fn config(vars: &[(&str, &str)]) -> Config {
+    Config::load(None, |name| {
+        vars.iter()
+            .find(|(var, _)| *var == name)
+            .map(|(_, value)| value.to_string())
+    })
+    .unwrap()
+}
+
+#[actix_web::test]
+async fn oversized_body_is_enveloped_413() {
+    let config = config(&[("MAX_BODY_BYTES", "256")]);
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::new(&config, None)))
+            .app_data(server::json_config(config.max_body_bytes))
+            .configure(server::configure),
+    )
+    .await;
+
+    let body = json!({
+        "date": "15/08/2023",
+        "time": "12:00",
+        "zone": "+05:30",
+        "latitude": 28.6139,
+        "longitude": 77.2090,
+        "janma_nakshatra": "x".repeat(1024),
+    });
+    let response = test::call_service(
+        &app,
+        test::TestRequest::post()
+            .uri("/panchang")
+            .set_json(body)
+            .to_request(),
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    let body: Value = test::read_body_json(response).await;
+    assert_eq!(body["status"], "error");
+    assert_eq!(body["statusCode"], 413);
+    assert_eq!(body["code"], "PAYLOAD_TOO_LARGE");
+    assert!(body["message"].as_str().unwrap().contains("at most 256"));
+}
+
+#[actix_web::test]
+async fn range_is_capped_in_days() {
+    let config = config(&[("MAX_RANGE_DAYS", "7")]);
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::new(&config, None)))
+            .app_data(server::json_config(config.max_body_bytes))
+            .configure(server::configure),
+    )
+    .await;
+
+    let request = |end_date: &str| {
+        test::TestRequest::post()
+            .uri("/panchang/range")
+            .set_json(json!({
+                "start_date": "01/08/2023",
+                "end_date": end_date,
+                "time": "06:00",
+                "zone": "+05:30",
+                "latitude": 28.6139,
+                "longitude": 77.2090,
+            }))
+            .to_request()
+    };
+
+    let response = test::call_service(&app, request("07/08/2023")).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = test::call_service(&app, request("08/08/2023")).await;
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let body: Value = test::read_body_json(response).await;
+    assert_eq!(body["code"], "INVALID_DATE_RANGE");
+}
@@ -0,0 +1,52 @@
+//! Checks that the database cache answers a repeated request with the stored response
+//! instead of calculating it again.
+//!
+//! Needs a PostgreSQL database, configured through the same `DB_HOST`, `DB_PORT`,
+//! `DB_USER`, `DB_PASSWORD` and `DB_NAME` variables as the server, so it is ignored
+//! by default; run it with `cargo test --test cache -- --ignored`.
+
+#![cfg(feature = "server")]
+
+use panchang_rs::cache::{cache_key, get_or_compute};
+use panchang_rs::config::Config;
+use panchang_rs::panchang::{PanchangRequest, ValidatedRequest};
+use serde_json::{Value, json};
+
+mod common;
+
+#[actix_web::test]
+#[ignore = "needs a PostgreSQL database, configured through DB_HOST"]
+async fn repeated_request_is_served_from_the_database() {
+    let pool = common::connect(&Config::from_env().expect("valid configuration")).await;
+    let request: PanchangRequest = serde_json::from_value(json!({
+        "date": "15/08/2023",
+        "time": "12:00",
+        "zone": "+05:30",
+        "latitude": 28.6139,
+        "longitude": 77.2090,
+    }))
+    .unwrap();
+    let request = ValidatedRequest::try_from(&request).unwrap();
+    let key = cache_key(&request);
+    sqlx::query("DELETE FROM panchang_cache WHERE cache_key = $1")
+        .bind(&key)
+        .execute(&pool)
+        .await
+        .expect("cache table is writable");
+
+    let first = get_or_compute(&pool, &request).await;
+    assert_eq!(
+        serde_json::to_value(&first).unwrap()["tithi"],
+        "Chaturdashi"
+    );
+
+    // Mark the stored response, which a recalculation would not reproduce
+    sqlx::query("UPDATE panchang_cache SET response = jsonb_set(response, '{tithi}', '\"Cached\"') WHERE cache_key = $1")
+        .bind(&key)
+        .execute(&pool)
+        .await
+        .expect("cache table is writable");
+
+    let second: Value = serde_json::to_value(get_or_compute(&pool, &request).await).unwrap();
+    assert_eq!(second["tithi"], "Cached");
+}
@@ -0,0 +1,163 @@
+//! Checks the reading of dates in the Julian calendar before the Gregorian cutover.
+//!
+//! Julian Thursday 4 October 1582 was followed by Gregorian Friday 15 October 1582,
+//! and in Britain Julian Wednesday 2 September 1752 by Gregorian Thursday
+//! 14 September 1752.
+
+use panchang_rs::panchang::{
+    PanchangRangeRequest, PanchangRequest, calculate_panchang, calculate_panchang_range,
+    gregorian_to_julian, julian_to_gregorian,
+};
+use serde_json::{Value, json};
+
+mod common;
+
+/// Calculates the Panchang at noon UTC in Greenwich with the calendar options in
+/// `options`, as JSON with the `meta` of the calculation
+fn panchang(date: &str, options: Value) -> Result<Value, String> {
+    let mut request = json!({
+        "date": date,
+        "time": "12:00",
+        "zone": "Z",
+        "latitude": 51.4779,
+        "longitude": 0.0,
+        "verbose": true,
+    });
+    request
+        .as_object_mut()
+        .unwrap()
+        .extend(options.as_object().unwrap().clone());
+    let request: PanchangRequest = serde_json::from_value(request).unwrap();
+    common::block_on(calculate_panchang(&request))
+        .map(|response| serde_json::to_value(response).unwrap())
+        .map_err(|err| err.code().to_string())
+}
+
+/// Day number of the calculated instant
+fn day_number(response: &Value) -> f64 {
+    response["meta"]["days_since_j2000"].as_f64().unwrap()
+}
+
+#[test]
+fn converts_julian_dates() {
+    assert_eq!(julian_to_gregorian(4, 10, 1582), (14, 10, 1582));
+    assert_eq!(julian_to_gregorian(2, 9, 1752), (13, 9, 1752));
+    assert_eq!(julian_to_gregorian(29, 2, 1500), (10, 3, 1500));
+    assert_eq!(julian_to_gregorian(1, 1, 2000), (14, 1, 2000));
+}
+
+#[test]
+fn converts_gregorian_dates_back() {
+    assert_eq!(gregorian_to_julian(14, 10, 1582), (4, 10, 1582));
+    assert_eq!(gregorian_to_julian(13, 9, 1752), (2, 9, 1752));
+    assert_eq!(gregorian_to_julian(10, 3, 1500), (29, 2, 1500));
+    assert_eq!(gregorian_to_julian(14, 1, 2000), (1, 1, 2000));
+}
+
+#[test]
+fn cutover_days_are_consecutive() {
+    let julian = json!({ "calendar": "julian" });
+    let before = panchang("04/10/1582", julian.clone()).unwrap();
+    let after = panchang("15/10/1582", julian).unwrap();
+
+    assert_eq!(before["vaara"]["english"], "Thursday");
+    assert_eq!(after["vaara"]["english"], "Friday");
+    assert_eq!(day_number(&after) - day_number(&before), 1.0);
+    assert_eq!(before["meta"]["day"], 14);
+    assert_eq!(after["meta"]["day"], 15);
+}
+
+#[test]
+fn gregorian_is_proleptic_by_default() {
+    let gregorian = panchang("04/10/1582", json!({})).unwrap();
+    let julian = panchang("04/10/1582", json!({ "calendar": "julian" })).unwrap();
+
+    assert_eq!(gregorian["vaara"]["english"], "Monday");
+    assert_eq!(day_number(&julian) - day_number(&gregorian), 10.0);
+    // From the cutover on both calendars read a date alike
+    assert_eq!(
+        panchang("15/10/1582", json!({})).unwrap()["meta"],
+        panchang("15/10/1582", json!({ "calendar": "julian" })).unwrap()["meta"]
+    );
+}
+
+#[test]
+fn rejects_the_skipped_days() {
+    let julian = json!({ "calendar": "julian" });
+    assert_eq!(
+        panchang("10/10/1582", julian.clone()).unwrap_err(),
+        "DATE_OUT_OF_RANGE"
+    );
+    assert_eq!(
+        panchang("14/10/1582", julian).unwrap_err(),
+        "DATE_OUT_OF_RANGE"
+    );
+    assert!(panchang("10/10/1582", json!({})).is_ok());
+}
+
+#[test]
+fn julian_leap_days() {
+    assert!(panchang("29/02/1500", json!({ "calendar": "julian" })).is_ok());
+    assert_eq!(
+        panchang("29/02/1500", json!({})).unwrap_err(),
+        "DATE_OUT_OF_RANGE"
+    );
+}
+
+#[test]
+fn cutover_is_configurable() {
+    let british = json!({ "calendar": "julian", "gregorian_cutover": "14/09/1752" });
+    let before = panchang("02/09/1752", british.clone()).unwrap();
+    let after = panchang("14/09/1752", british.clone()).unwrap();
+
+    assert_eq!(before["vaara"]["english"], "Wednesday");
+    assert_eq!(after["vaara"]["english"], "Thursday");
+    assert_eq!(day_number(&after) - day_number(&before), 1.0);
+    // The 1582 reform does not apply under the later cutover
+    assert!(panchang("10/10/1582", british).is_ok());
+}
+
+#[test]
+fn range_crosses_the_cutover() {
+    let request: PanchangRangeRequest = serde_json::from_value(json!({
+        "start_date": "01/10/1582",
+        "end_date": "20/10/1582",
+        "time": "12:00",
+        "zone": "Z",
+        "latitude": 51.4779,
+        "longitude": 0.0,
+        "calendar": "julian",
+    }))
+    .unwrap();
+    let days = serde_json::to_value(common::block_on(calculate_panchang_range(&request)).unwrap())
+        .unwrap();
+    let days = days.as_array().unwrap();
+
+    // Julian 1-4 October are followed by Gregorian 15-20 October
+    let dates: Vec<&str> = days
+        .iter()
+        .map(|day| day["date"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        dates,
+        [
+            "01/10/1582",
+            "02/10/1582",
+            "03/10/1582",
+            "04/10/1582",
+            "15/10/1582",
+            "16/10/1582",
+            "17/10/1582",
+            "18/10/1582",
+            "19/10/1582",
+            "20/10/1582",
+        ]
+    );
+    assert_eq!(days[3]["vaara"]["english"], "Thursday");
+    assert_eq!(days[4]["vaara"]["english"], "Friday");
+    // Each day is the Panchang of the Julian date it names
+    assert_eq!(
+        days[0]["tithi"],
+        panchang("01/10/1582", json!({ "calendar": "julian" })).unwrap()["tithi"]
+    );
+}
@@ -0,0 +1,46 @@
+//! Checks `choghadiya` against the weekday tables of the Choghadiya: the first
+//! Choghadiya of the daytime and of the night for each weekday, and the eighths
+//! that the slots divide the daytime and the night into.
+
+use panchang_rs::panchang::choghadiya;
+
+/// The first daytime and the first nighttime Choghadiya, from Sunday
+const FIRST: [(&str, &str); 7] = [
+    ("Udveg", "Shubh"),
+    ("Amrit", "Char"),
+    ("Rog", "Kaal"),
+    ("Labh", "Udveg"),
+    ("Shubh", "Amrit"),
+    ("Char", "Rog"),
+    ("Kaal", "Labh"),
+];
+
+#[test]
+fn first_choghadiyas_follow_the_weekday() {
+    for (weekday, (day, night)) in FIRST.iter().enumerate() {
+        let slots = choghadiya(6.0, 18.0, 6.0, weekday);
+        assert_eq!(slots.len(), 16);
+        assert_eq!(slots[0].name, *day, "weekday {weekday}");
+        assert_eq!(slots[8].name, *night, "weekday {weekday}");
+    }
+}
+
+#[test]
+fn sunday_begins_with_udveg_until_the_first_eighth() {
+    // Sunrise at 06:00 and sunset at 19:00 make each daytime slot 1h37½m, and the
+    // night to 05:48 makes each night slot 1h21m
+    let slots = choghadiya(6.0, 19.0, 5.8, 0);
+
+    assert_eq!(slots[0].name, "Udveg");
+    assert!(!slots[0].auspicious);
+    assert!((slots[0].start - 6.0).abs() < 1e-9);
+    assert!((slots[0].end - 7.625).abs() < 1e-9);
+
+    // Char follows Udveg
+    assert_eq!(slots[1].name, "Char");
+    assert!(slots[1].auspicious);
+
+    assert!((slots[7].end - 19.0).abs() < 1e-9);
+    assert!((slots[8].end - 20.35).abs() < 1e-9);
+    assert!((slots[15].end - 29.8).abs() < 1e-9);
+}
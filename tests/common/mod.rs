@@ -0,0 +1,84 @@
+//! Helpers shared by the integration tests.
+//!
+//! Each test crate uses only some of them.
+#![allow(dead_code)]
+
+use std::future::Future;
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+
+#[cfg(feature = "server")]
+use panchang_rs::config::Config;
+use panchang_rs::panchang::{PanchangRequest, calculate_panchang};
+use serde_json::{Value, json};
+#[cfg(feature = "server")]
+use sqlx::PgPool;
+#[cfg(feature = "server")]
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+
+/// Connects to the database configured by the `DB_*` variables and applies the
+/// migrations
+///
+/// Panics when `DB_HOST` is not set, so the tests calling it are `#[ignore]`d and run
+/// with `cargo test -- --ignored` against a database.
+#[cfg(feature = "server")]
+pub async fn connect(config: &Config) -> PgPool {
+    let db = config.db.as_ref().expect("DB_HOST is set");
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .connect_with(
+            PgConnectOptions::new()
+                .host(&db.host)
+                .port(db.port)
+                .username(&db.user)
+                .password(&db.password)
+                .database(&db.name),
+        )
+        .await
+        .expect("database is reachable");
+    sqlx::migrate!().run(&pool).await.expect("migrations apply");
+    pool
+}
+
+/// Runs one of the `calculate_*` functions to completion
+///
+/// They are `async` for the server's sake but never await, so their first poll
+/// completes them.
+pub fn block_on<F: Future>(calculation: F) -> F::Output {
+    match pin!(calculation).poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(output) => output,
+        Poll::Pending => unreachable!("the calculations do not await"),
+    }
+}
+
+/// A request for a local date and time in New Delhi, in IST
+pub fn delhi(date: &str, time: &str) -> PanchangRequest {
+    PanchangRequest::new(date, time, "+05:30", 28.6139, 77.2090)
+}
+
+/// A request for a local date and time in New Delhi, in IST, with the fields of
+/// `options` added to it or, where null, taken out
+pub fn delhi_with(date: &str, time: &str, options: Value) -> PanchangRequest {
+    let mut request = json!({
+        "date": date,
+        "time": time,
+        "zone": "+05:30",
+        "latitude": 28.6139,
+        "longitude": 77.2090,
+    });
+    let fields = request.as_object_mut().unwrap();
+    for (key, value) in options.as_object().unwrap() {
+        match value {
+            Value::Null => fields.remove(key),
+            value => fields.insert(key.clone(), value.clone()),
+        };
+    }
+    serde_json::from_value(request).unwrap()
+}
+
+/// Calculates the Panchang of a request, as JSON
+///
+/// Panics if the request is rejected.
+pub fn panchang_json(request: &PanchangRequest) -> Value {
+    serde_json::to_value(block_on(calculate_panchang(request)).unwrap()).unwrap()
+}
@@ -0,0 +1,68 @@
+//! Checks `/panchang/compare` near a Nakshatra boundary.
+//!
+//! At 12:45 IST on 15/08/2023 the sidereal Moon is just short of the end of Pushya
+//! in the Lahiri Ayanamsa. Raman's Ayanamsa is about 1.4° smaller, which puts the
+//! Moon in Ashlesa and the Yoga past Vyatipata, while the Rashi stays Karka.
+
+use panchang_rs::PanchangError;
+use panchang_rs::panchang::{
+    PanchangCompareRequest, PanchangComparison, calculate_panchang_comparison,
+};
+use serde_json::{Value, json};
+
+mod common;
+
+/// Compares the Panchang at 12:45 IST on 15/08/2023 in New Delhi across `ayanamsas`
+fn compare(ayanamsas: Value) -> Result<PanchangComparison, PanchangError> {
+    let request: PanchangCompareRequest = serde_json::from_value(json!({
+        "date": "15/08/2023",
+        "time": "12:45",
+        "zone": "+05:30",
+        "latitude": 28.6139,
+        "longitude": 77.2090,
+        "ayanamsas": ayanamsas,
+    }))
+    .unwrap();
+    common::block_on(calculate_panchang_comparison(&request))
+}
+
+#[test]
+fn flags_nakshatra_across_boundary() {
+    let comparison = serde_json::to_value(compare(json!(["lahiri", "raman"])).unwrap()).unwrap();
+
+    let systems = comparison["systems"].as_array().unwrap();
+    assert_eq!(systems[0]["ayanamsa"], "lahiri");
+    assert_eq!(systems[0]["nakshatra"], "Pushya");
+    assert_eq!(systems[1]["ayanamsa"], "raman");
+    assert_eq!(systems[1]["nakshatra"], "Ashlesa");
+
+    let lahiri = systems[0]["ayanamsa_degrees"].as_f64().unwrap();
+    let raman = systems[1]["ayanamsa_degrees"].as_f64().unwrap();
+    assert!((23.0..25.0).contains(&lahiri), "Lahiri Ayanamsa {}", lahiri);
+    assert!(
+        (lahiri - raman - 1.4).abs() < 0.1,
+        "Raman Ayanamsa {}",
+        raman
+    );
+
+    let differences = comparison["differences"].as_array().unwrap();
+    let limbs: Vec<&str> = differences
+        .iter()
+        .map(|difference| difference["limb"].as_str().unwrap())
+        .collect();
+    assert!(
+        limbs.contains(&"nakshatra") && limbs.contains(&"yoga"),
+        "{:?}",
+        limbs
+    );
+    assert!(!limbs.contains(&"rashi"), "{:?}", limbs);
+    assert_eq!(differences[0]["values"], json!(["Pushya", "Ashlesa"]));
+}
+
+#[test]
+fn rejects_too_few_or_repeated_systems() {
+    for ayanamsas in [json!(["lahiri"]), json!(["lahiri", "kp", "lahiri"])] {
+        let error = compare(ayanamsas).unwrap_err();
+        assert_eq!(error.code(), "INVALID_COMPARISON", "{}", error);
+    }
+}
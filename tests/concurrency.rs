@@ -0,0 +1,58 @@
+//! Checks that `calculate_panchanga` is reentrant: 100 threads calculating the same
+//! instant at once all get the Panchanga a single call gets.
+
+use std::sync::Barrier;
+use std::thread;
+
+use panchang_rs::{Ayanamsa, calculate_panchanga};
+
+const CALLS: usize = 100;
+
+#[test]
+fn concurrent_calls_agree() {
+    let expected = calculate_panchanga(15, 8, 2023, 12.5, 5.5, Ayanamsa::Lahiri);
+
+    // Release every thread at once so that the calculations overlap
+    let barrier = Barrier::new(CALLS);
+    let results: Vec<_> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..CALLS)
+            .map(|_| {
+                scope.spawn(|| {
+                    barrier.wait();
+                    calculate_panchanga(15, 8, 2023, 12.5, 5.5, Ayanamsa::Lahiri)
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect()
+    });
+
+    assert_eq!(results.len(), CALLS);
+    for (call, result) in results.iter().enumerate() {
+        assert_eq!(*result, expected, "call {}", call);
+    }
+}
+
+#[test]
+fn interleaved_dates_do_not_disturb_each_other() {
+    // Threads alternating between two dates each get their own date's Panchanga
+    let dates = [(15, 8, 2023), (1, 1, 1950)];
+    let expected = dates.map(|(day, month, year)| {
+        calculate_panchanga(day, month, year, 6.0, 5.5, Ayanamsa::Lahiri)
+    });
+
+    let barrier = Barrier::new(CALLS);
+    thread::scope(|scope| {
+        for call in 0..CALLS {
+            let (barrier, expected) = (&barrier, &expected);
+            scope.spawn(move || {
+                let (day, month, year) = dates[call % 2];
+                barrier.wait();
+                let result = calculate_panchanga(day, month, year, 6.0, 5.5, Ayanamsa::Lahiri);
+                assert_eq!(result, expected[call % 2], "call {}", call);
+            });
+        }
+    });
+}
@@ -0,0 +1,110 @@
+//! Checks that settings are read from a TOML file, that the environment overrides
+//! them, and that invalid settings are rejected.
+
+#![cfg(feature = "server")]
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use panchang_rs::Ayanamsa;
+use panchang_rs::config::Config;
+
+/// Loads `config.sample.toml` with `vars` as the environment
+fn load(vars: &[(&str, &str)]) -> Config {
+    let vars: HashMap<String, String> = vars
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+    Config::load(Some(Path::new("config.sample.toml")), |name| {
+        vars.get(name).cloned()
+    })
+    .unwrap()
+}
+
+#[test]
+fn reads_the_file() {
+    let config = load(&[]);
+    assert_eq!(config.host, "127.0.0.1");
+    assert_eq!(config.port, 8080);
+    assert_eq!(config.keep_alive, 5);
+    assert_eq!(config.default_ayanamsa, Ayanamsa::Lahiri);
+    assert_eq!(
+        config.default_location.timezone.as_deref(),
+        Some("Asia/Kolkata")
+    );
+
+    let db = config.db.expect("the sample file configures a database");
+    assert_eq!(db.host, "localhost");
+    assert_eq!(db.port, 5432);
+    assert_eq!(db.max_connections, 5);
+}
+
+#[test]
+fn environment_overrides_the_file() {
+    let config = load(&[
+        ("SERVER_PORT", "9090"),
+        ("DEFAULT_AYANAMSA", "raman"),
+        ("DB_NAME", "panchang"),
+    ]);
+    assert_eq!(config.port, 9090);
+    assert_eq!(config.default_ayanamsa, Ayanamsa::Raman);
+    assert_eq!(config.db.unwrap().name, "panchang");
+    // Settings the environment leaves out still come from the file
+    assert_eq!(config.host, "127.0.0.1");
+}
+
+#[test]
+fn rejects_unknown_keys() {
+    let path = std::env::temp_dir().join("panchang-rs-unknown-key.toml");
+    std::fs::write(&path, "[server]\nprot = 9090\n").unwrap();
+    let result = Config::load(Some(&path), |_| None);
+    std::fs::remove_file(&path).unwrap();
+    assert!(result.is_err());
+}
+
+#[test]
+fn rejects_invalid_default_location() {
+    for (name, value) in [
+        ("DEFAULT_TIMEZONE", "Mars/Olympus_Mons"),
+        ("DEFAULT_LATITUDE", "91"),
+        ("DEFAULT_LONGITUDE", "east"),
+    ] {
+        let error = Config::load(None, |var| (var == name).then(|| value.to_string())).unwrap_err();
+        assert!(error.to_string().starts_with(name), "{}", error);
+    }
+}
+
+#[test]
+fn reads_the_short_coordinate_names() {
+    let config = load(&[("DEFAULT_LAT", "40.7128"), ("DEFAULT_LON", "-74.0060")]);
+    assert_eq!(config.default_location.latitude, 40.7128);
+    assert_eq!(config.default_location.longitude, -74.0060);
+
+    // The full names take precedence
+    let config = load(&[("DEFAULT_LATITUDE", "12.9716"), ("DEFAULT_LAT", "40.7128")]);
+    assert_eq!(config.default_location.latitude, 12.9716);
+}
+
+#[test]
+fn reads_workers_and_keep_alive() {
+    let config = load(&[("WORKERS", "3"), ("KEEP_ALIVE", "75")]);
+    assert_eq!(config.workers, 3);
+    assert_eq!(config.keep_alive, 75);
+
+    // 0 turns keep-alive off
+    assert_eq!(load(&[("KEEP_ALIVE", "0")]).keep_alive, 0);
+}
+
+#[test]
+fn rejects_invalid_workers_and_keep_alive() {
+    for (name, value) in [
+        ("WORKERS", "0"),
+        ("WORKERS", "four"),
+        ("WORKERS", "-2"),
+        ("KEEP_ALIVE", "5s"),
+        ("KEEP_ALIVE", "-1"),
+    ] {
+        let error = Config::load(None, |var| (var == name).then(|| value.to_string())).unwrap_err();
+        assert!(error.to_string().starts_with(name), "{}", error);
+    }
+}
@@ -0,0 +1,72 @@
+//! Checks that a browser's preflight from an origin in `CORS_ALLOWED_ORIGINS` is
+//! allowed, and one from any other origin is not.
+
+#![cfg(feature = "server")]
+
+use actix_web::http::{StatusCode, header};
+use actix_web::{App, test, web};
+use panchang_rs::config::Config;
+use panchang_rs::server::{self, AppState, cors};
+
+/// A preflight of a JSON POST to `/panchang` from `origin`
+fn preflight(origin: &str) -> test::TestRequest {
+    test::TestRequest::default()
+        .method(actix_web::http::Method::OPTIONS)
+        .uri("/panchang")
+        .insert_header((header::ORIGIN, origin))
+        .insert_header((header::ACCESS_CONTROL_REQUEST_METHOD, "POST"))
+        .insert_header((header::ACCESS_CONTROL_REQUEST_HEADERS, "content-type"))
+}
+
+#[actix_web::test]
+async fn preflight_from_an_allowed_origin() {
+    let config = Config::load(None, |_| None).unwrap();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::new(&config, None)))
+            .wrap(cors(&[String::from("https://panchang.example")]))
+            .configure(server::configure),
+    )
+    .await;
+
+    let allowed =
+        test::call_service(&app, preflight("https://panchang.example").to_request()).await;
+    assert_eq!(allowed.status(), StatusCode::OK);
+    assert_eq!(
+        allowed
+            .headers()
+            .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+            .unwrap(),
+        "https://panchang.example"
+    );
+
+    let refused = test::call_service(&app, preflight("https://other.example").to_request()).await;
+    assert!(
+        refused
+            .headers()
+            .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+            .is_none()
+    );
+}
+
+#[actix_web::test]
+async fn wildcard_allows_any_origin() {
+    let config = Config::load(None, |_| None).unwrap();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::new(&config, None)))
+            .wrap(cors(&[String::from("*")]))
+            .configure(server::configure),
+    )
+    .await;
+
+    let response = test::call_service(&app, preflight("https://other.example").to_request()).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response
+            .headers()
+            .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+            .unwrap(),
+        "https://other.example"
+    );
+}
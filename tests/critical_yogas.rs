@@ -0,0 +1,57 @@
+//! Checks the windows of Vyatipata and Vaidhruthi against August 2023 in New Delhi:
+//! each window holds its Yoga to within a second of its bounds, and dates without
+//! either have none.
+
+use chrono::{DateTime, Duration, Utc};
+use panchang_rs::panchang::{Ayanamsa, calculate_panchanga_at, critical_yogas_on};
+
+/// The Yoga in effect at an instant, in Lahiri
+fn yoga_at(instant: DateTime<Utc>) -> String {
+    calculate_panchanga_at(instant, Ayanamsa::Lahiri).current_yoga
+}
+
+/// Asserts that `yoga` holds just inside `start` and `end`, and not just outside
+fn assert_bounded(yoga: &str, start: DateTime<Utc>, end: DateTime<Utc>) {
+    let second = Duration::seconds(1);
+    assert_eq!(yoga_at(start + second), yoga);
+    assert_eq!(yoga_at(end - second), yoga);
+    assert_ne!(yoga_at(start - second), yoga);
+    assert_ne!(yoga_at(end + second), yoga);
+}
+
+#[test]
+fn vyatipata_window_is_detected_and_bounded() {
+    let windows = critical_yogas_on(15, 8, 2023, 5.5, Ayanamsa::Lahiri);
+    assert_eq!(windows.len(), 1);
+    let (yoga, start, end) = &windows[0];
+    assert_eq!(yoga, "Vyatipata");
+
+    // From 16:38 IST on the 14th to 17:31 IST on the 15th
+    assert_eq!(start.format("%d %H:%M").to_string(), "14 11:08");
+    assert_eq!(end.format("%d %H:%M").to_string(), "15 12:01");
+    assert_bounded(yoga, *start, *end);
+
+    // The day before finds the same window, begun during it, to within the second
+    // the boundary search resolves
+    let before = critical_yogas_on(14, 8, 2023, 5.5, Ayanamsa::Lahiri);
+    assert_eq!(before.len(), 1);
+    assert_eq!(&before[0].0, yoga);
+    assert!((before[0].1 - *start).abs() < Duration::seconds(1));
+    assert!((before[0].2 - *end).abs() < Duration::seconds(1));
+}
+
+#[test]
+fn vaidhruthi_window_is_detected_and_bounded() {
+    let windows = critical_yogas_on(24, 8, 2023, 5.5, Ayanamsa::Lahiri);
+    assert_eq!(windows.len(), 1);
+    let (yoga, start, end) = &windows[0];
+    assert_eq!(yoga, "Vaidhruthi");
+    assert_bounded(yoga, *start, *end);
+}
+
+#[test]
+fn other_dates_have_no_critical_yoga() {
+    for day in [10, 18, 20, 30] {
+        assert!(critical_yogas_on(day, 8, 2023, 5.5, Ayanamsa::Lahiri).is_empty());
+    }
+}
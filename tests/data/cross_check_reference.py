@@ -0,0 +1,251 @@
+#!/usr/bin/env python3
+"""Cross-checks the published limbs of tests/data/reference.json.
+
+The dataset is transcribed by hand from a published Panchang, and this script
+only looks for transcription slips: it recomputes the limbs each row states and
+reports the rows where they differ. It never writes the dataset, and a row it
+disagrees with is to be checked against its `source`, not changed to fit. Run it
+from the repository root:
+
+    python3 tests/data/cross_check_reference.py
+
+Only the standard library is used, and nothing is shared with the crate. The
+method follows Meeus, Astronomical Algorithms (2nd ed.):
+
+* ΔT from the observed yearly values, interpolated (chapter 10)
+* The Sun's apparent longitude, with the equation of centre, nutation and
+  aberration (chapter 25)
+* The Moon's longitude from the ELP-2000/82 terms of table 47.A (chapter 47),
+  made apparent with the nutation in longitude (chapter 22)
+* The Lahiri Ayanamsa as defined by the Indian Astronomical Ephemeris,
+  23°15'00.658" at 1956 March 21.0, carried by the IAU 1976 general precession
+  and made true with the nutation in longitude
+"""
+
+import json
+import math
+import sys
+from datetime import datetime, timedelta, timezone
+from pathlib import Path
+
+DATASET = Path(__file__).with_name("reference.json")
+
+TITHI = [
+    "Prathame", "Dwithiya", "Thrithiya", "Chathurthi", "Panchami", "Shrashti",
+    "Saptami", "Ashtami", "Navami", "Dashami", "Ekadashi", "Dwadashi",
+    "Thrayodashi", "Chaturdashi", "Poornima",
+    "Prathame", "Dwithiya", "Thrithiya", "Chathurthi", "Panchami", "Shrashti",
+    "Saptami", "Ashtami", "Navami", "Dashami", "Ekadashi", "Dwadashi",
+    "Thrayodashi", "Chaturdashi", "Amavasya",
+]
+NAKSHATRA = [
+    "Ashwini", "Bharani", "Krittika", "Rohini", "Mrigashira", "Ardhra",
+    "Punarvasu", "Pushya", "Ashlesa", "Magha", "Poorva Phalguni",
+    "Uttara Phalguni", "Hasta", "Chitra", "Swathi", "Vishaka", "Anuradha",
+    "Jyeshta", "Mula", "Poorva Ashada", "Uttara Ashada", "Sravana", "Dhanishta",
+    "Shatabisha", "Poorva Bhadra", "Uttara Bhadra", "Revathi",
+]
+YOGA = [
+    "Vishkambha", "Prithi", "Ayushman", "Saubhagya", "Shobhana", "Atiganda",
+    "Sukarman", "Dhrithi", "Shoola", "Ganda", "Vridhi", "Dhruva", "Vyaghata",
+    "Harshana", "Vajra", "Siddhi", "Vyatipata", "Variyan", "Parigha", "Shiva",
+    "Siddha", "Sadhya", "Shubha", "Shukla", "Bramha", "Indra", "Vaidhruthi",
+]
+KARANA = [
+    "Bava", "Balava", "Kaulava", "Taitula", "Garija", "Vanija", "Visti",
+    "Sakuni", "Chatuspada", "Naga", "Kimstughna",
+]
+RASHI = [
+    "Mesha", "Vrishabha", "Mithuna", "Karka", "Simha", "Kanya", "Tula",
+    "Vrischika", "Dhanu", "Makara", "Kumbha", "Meena",
+]
+
+# ΔT in seconds on January 1 of each fifth year (Astronomical Almanac, IERS)
+DELTA_T = [
+    (1950, 29.07), (1955, 31.07), (1960, 33.15), (1965, 35.73), (1970, 40.18),
+    (1975, 45.48), (1980, 50.54), (1985, 54.34), (1990, 56.86), (1995, 60.78),
+    (2000, 63.83), (2005, 64.69), (2010, 66.07), (2015, 67.64), (2020, 69.36),
+    (2025, 69.20),
+]
+
+# Table 47.A: multiples of D, M, M' and F, and the coefficient of the sine in
+# millionths of a degree
+MOON_LONGITUDE = [
+    (0, 0, 1, 0, 6288774), (2, 0, -1, 0, 1274027), (2, 0, 0, 0, 658314),
+    (0, 0, 2, 0, 213618), (0, 1, 0, 0, -185116), (0, 0, 0, 2, -114332),
+    (2, 0, -2, 0, 58793), (2, -1, -1, 0, 57066), (2, 0, 1, 0, 53322),
+    (2, -1, 0, 0, 45758), (0, 1, -1, 0, -40923), (1, 0, 0, 0, -34720),
+    (0, 1, 1, 0, -30383), (2, 0, 0, -2, 15327), (0, 0, 1, 2, -12528),
+    (0, 0, 1, -2, 10980), (4, 0, -1, 0, 10675), (0, 0, 3, 0, 10034),
+    (4, 0, -2, 0, 8548), (2, 1, -1, 0, -7888), (2, 1, 0, 0, -6766),
+    (1, 0, -1, 0, -5163), (1, 1, 0, 0, 4987), (2, -1, 1, 0, 4036),
+    (2, 0, 2, 0, 3994), (4, 0, 0, 0, 3861), (2, 0, -3, 0, 3665),
+    (0, 1, -2, 0, -2689), (2, 0, -1, 2, -2602), (2, -1, -2, 0, 2390),
+    (1, 0, 1, 0, -2348), (2, -2, 0, 0, 2236), (0, 1, 2, 0, -2120),
+    (0, 2, 0, 0, -2069), (2, -2, -1, 0, 2048), (2, 0, 1, -2, -1773),
+    (2, 0, 0, 2, -1595), (4, -1, -1, 0, 1215), (0, 0, 2, 2, -1110),
+    (3, 0, -1, 0, -892), (2, 1, 1, 0, -810), (4, -1, -2, 0, 759),
+    (0, 2, -1, 0, -713), (2, 2, -1, 0, -700), (2, 1, -2, 0, 691),
+    (2, -1, 0, -2, 596), (4, 0, 1, 0, 549), (0, 0, 4, 0, 537),
+    (4, -1, 0, 0, 520), (1, 0, -2, 0, -487), (2, 1, 0, -2, -399),
+    (0, 0, 2, -2, -381), (1, 1, 1, 0, 351), (3, 0, -2, 0, -340),
+    (4, 0, -3, 0, 330), (2, -1, 2, 0, 327), (0, 2, 1, 0, -323),
+    (1, 1, -1, 0, 299), (2, 0, 3, 0, 294), (2, -1, -1, 2, 0),
+]
+
+# Julian Ephemeris Day of the Lahiri epoch, 1956 March 21.0, and its Ayanamsa
+LAHIRI_EPOCH = 2435553.5
+LAHIRI_AT_EPOCH = 23 + 15 / 60 + 0.658 / 3600
+
+
+def sin(degrees):
+    return math.sin(math.radians(degrees))
+
+
+def julian_day(instant):
+    """Julian Day of an aware datetime, on the UT scale"""
+    return 2440587.5 + instant.timestamp() / 86400
+
+
+def delta_t(jd):
+    year = 2000 + (jd - 2451544.5) / 365.25
+    if year >= DELTA_T[-1][0]:
+        # About a third of a second a year since 2000
+        return DELTA_T[-1][1] + 0.3 * (year - DELTA_T[-1][0])
+    for (y0, t0), (y1, t1) in zip(DELTA_T, DELTA_T[1:]):
+        if year < y1:
+            return t0 + (t1 - t0) * (year - y0) / (y1 - y0)
+    raise AssertionError("unreachable")
+
+
+def nutation_in_longitude(t):
+    """Δψ in degrees, to about half an arcsecond (chapter 22)"""
+    node = 125.04452 - 1934.136261 * t
+    sun = 280.4665 + 36000.7698 * t
+    moon = 218.3165 + 481267.8813 * t
+    arcseconds = (
+        -17.20 * sin(node) - 1.32 * sin(2 * sun) - 0.23 * sin(2 * moon) + 0.21 * sin(2 * node)
+    )
+    return arcseconds / 3600
+
+
+def apparent_sun(t):
+    """The Sun's apparent longitude in degrees (chapter 25)"""
+    mean_longitude = 280.46646 + 36000.76983 * t + 0.0003032 * t * t
+    anomaly = 357.52911 + 35999.05029 * t - 0.0001537 * t * t
+    centre = (
+        (1.914602 - 0.004817 * t - 0.000014 * t * t) * sin(anomaly)
+        + (0.019993 - 0.000101 * t) * sin(2 * anomaly)
+        + 0.000289 * sin(3 * anomaly)
+    )
+    aberration = -20.4898 / 3600
+    return mean_longitude + centre + aberration + nutation_in_longitude(t)
+
+
+def apparent_moon(t):
+    """The Moon's apparent longitude in degrees (chapter 47)"""
+    mean_longitude = (
+        218.3164477 + 481267.88123421 * t - 0.0015786 * t**2 + t**3 / 538841 - t**4 / 65194000
+    )
+    elongation = (
+        297.8501921 + 445267.1114034 * t - 0.0018819 * t**2 + t**3 / 545868 - t**4 / 113065000
+    )
+    sun_anomaly = 357.5291092 + 35999.0502909 * t - 0.0001536 * t**2 + t**3 / 24490000
+    anomaly = (
+        134.9633964 + 477198.8675055 * t + 0.0087414 * t**2 + t**3 / 69699 - t**4 / 14712000
+    )
+    latitude = (
+        93.2720950 + 483202.0175233 * t - 0.0036539 * t**2 - t**3 / 3526000 + t**4 / 863310000
+    )
+    eccentricity = 1 - 0.002516 * t - 0.0000074 * t**2
+
+    total = sum(
+        coefficient
+        * eccentricity ** abs(m)
+        * sin(d * elongation + m * sun_anomaly + mp * anomaly + f * latitude)
+        for d, m, mp, f, coefficient in MOON_LONGITUDE
+    )
+    total += 3958 * sin(119.75 + 131.849 * t)
+    total += 1962 * sin(mean_longitude - latitude)
+    total += 318 * sin(53.09 + 479264.290 * t)
+    return mean_longitude + total / 1e6 + nutation_in_longitude(t)
+
+
+def lahiri(jde, t):
+    """The true Lahiri Ayanamsa in degrees"""
+
+    def precession(jde):
+        # General precession in longitude since J2000, in arcseconds (IAU 1976)
+        c = (jde - 2451545.0) / 36525
+        return 5029.0966 * c + 1.11113 * c * c - 0.000006 * c**3
+
+    mean = LAHIRI_AT_EPOCH + (precession(jde) - precession(LAHIRI_EPOCH)) / 3600
+    return mean + nutation_in_longitude(t)
+
+
+def positions(instant):
+    """Sidereal longitudes of the Sun and the Moon, and their elongation"""
+    jd = julian_day(instant)
+    jde = jd + delta_t(jd) / 86400
+    t = (jde - 2451545.0) / 36525
+    sun = apparent_sun(t)
+    moon = apparent_moon(t)
+    ayanamsa = lahiri(jde, t)
+    return (sun - ayanamsa) % 360, (moon - ayanamsa) % 360, (moon - sun) % 360
+
+
+def elements(instant):
+    sun, moon, elongation = positions(instant)
+    half_tithi = int(elongation // 6)
+    if half_tithi == 0:
+        karana = 10
+    elif half_tithi >= 57:
+        karana = 7 + half_tithi - 57
+    else:
+        karana = (half_tithi - 1) % 7
+    return {
+        "tithi_number": int(elongation // 12) + 1,
+        "nakshatra": NAKSHATRA[int(moon // (360 / 27))],
+        "yoga": YOGA[int((sun + moon) % 360 // (360 / 27))],
+        "karana": KARANA[karana],
+        "rashi": RASHI[int(moon // 30)],
+    }
+
+
+def parse_instant(row):
+    day, month, year = (int(part) for part in row["date"].split("/"))
+    hour, minute = (int(part) for part in row["time"].split(":"))
+    sign = -1 if row["zone"].startswith("-") else 1
+    zone_hours, zone_minutes = (int(part) for part in row["zone"][1:].split(":"))
+    offset = sign * timedelta(hours=zone_hours, minutes=zone_minutes)
+    return datetime(year, month, day, hour, minute, tzinfo=timezone(offset))
+
+
+def disagreements(row):
+    """The limbs a row states that differ from the computed ones"""
+    at = elements(parse_instant(row))
+    computed = {**at, "tithi": TITHI[at["tithi_number"] - 1]}
+    stated = ("tithi_number", "tithi", "nakshatra", "yoga", "karana", "rashi")
+    return [
+        f"{key} {row[key]}, computed {computed[key]}"
+        for key in stated
+        if key in row and row[key] != computed[key]
+    ]
+
+
+def main():
+    rows = json.loads(DATASET.read_text(encoding="utf-8"))
+    failed = 0
+    for row in rows:
+        differences = disagreements(row)
+        if differences:
+            failed += 1
+            print(f"{row['date']} {row['time']} {row['zone']}: {'; '.join(differences)}")
+            print(f"  source: {row['source']}")
+    if failed:
+        sys.exit(f"{failed} of {len(rows)} rows differ, check them against their source")
+    print(f"{DATASET.name} agrees with the computation ({len(rows)} rows)")
+
+
+if __name__ == "__main__":
+    main()
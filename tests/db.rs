@@ -0,0 +1,74 @@
+//! Checks the retries of the startup connection: a connector that fails a few times
+//! before the database comes up is retried until it succeeds, with the wait doubling
+//! between attempts, and one that never succeeds gives up with its last error.
+
+#![cfg(feature = "server")]
+
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+use panchang_rs::config::Config;
+use panchang_rs::db::{init_db, retry_with_backoff};
+
+/// A connector standing in for a database that accepts connections from the
+/// `available_from`th attempt on
+async fn flaky_connect(
+    calls: &Cell<u32>,
+    attempt: u32,
+    available_from: u32,
+) -> Result<&'static str, String> {
+    calls.set(calls.get() + 1);
+    assert_eq!(attempt, calls.get(), "attempts are numbered from 1");
+    if attempt >= available_from {
+        Ok("pool")
+    } else {
+        Err(format!("connection refused on attempt {}", attempt))
+    }
+}
+
+#[actix_web::test]
+async fn retries_until_the_database_is_up() {
+    let calls = Cell::new(0);
+    let started = Instant::now();
+    let result = retry_with_backoff(5, Duration::from_millis(20), |attempt| {
+        flaky_connect(&calls, attempt, 3)
+    })
+    .await;
+
+    assert_eq!(result, Ok("pool"));
+    assert_eq!(calls.get(), 3);
+    // 20 ms after the first failure and 40 ms after the second
+    assert!(started.elapsed() >= Duration::from_millis(60));
+}
+
+#[actix_web::test]
+async fn gives_up_with_the_last_error() {
+    let calls = Cell::new(0);
+    let result = retry_with_backoff(4, Duration::from_millis(1), |attempt| {
+        flaky_connect(&calls, attempt, 10)
+    })
+    .await;
+
+    assert_eq!(result, Err(String::from("connection refused on attempt 4")));
+    assert_eq!(calls.get(), 4);
+}
+
+#[actix_web::test]
+async fn unreachable_database_is_an_error() {
+    // Nothing listens on port 1, so every attempt is refused at once
+    let config = Config::load(None, |name| {
+        let value = match name {
+            "DB_HOST" => "127.0.0.1",
+            "DB_PORT" => "1",
+            "DB_USER" | "DB_PASSWORD" | "DB_NAME" => "panchang",
+            "DB_CONNECT_ATTEMPTS" => "2",
+            "DB_CONNECT_BASE_DELAY_MS" => "1",
+            _ => return None,
+        };
+        Some(value.to_string())
+    })
+    .unwrap();
+
+    let error = init_db(config.db.as_ref()).await.unwrap_err();
+    assert!(error.to_string().starts_with("Database connection error"));
+}
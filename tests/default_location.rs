@@ -0,0 +1,202 @@
+//! Checks that `/panchang` and the range, next-Tithi and compare endpoints fill in
+//! the location a request leaves out from the configured default, and that they
+//! name the parts they filled in, `/panchang` in the `meta`.
+
+#![cfg(feature = "server")]
+
+use actix_web::{App, test, web};
+use panchang_rs::config::Config;
+use panchang_rs::server::{self, AppState};
+use serde_json::{Value, json};
+
+/// Configures a server without a database, with the default location at New York
+fn new_york() -> Config {
+    Config::load(None, |name| match name {
+        "DEFAULT_LATITUDE" => Some(String::from("40.7128")),
+        "DEFAULT_LONGITUDE" => Some(String::from("-74.0060")),
+        "DEFAULT_TIMEZONE" => Some(String::from("America/New_York")),
+        _ => None,
+    })
+    .unwrap()
+}
+
+/// Posts `body` extended with `location` to `uri` on a server configured by `config`,
+/// returning the `data` of the successful response
+async fn post(config: &Config, uri: &str, mut body: Value, location: Value) -> Value {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::new(config, None)))
+            .app_data(server::json_config(config.max_body_bytes))
+            .configure(server::configure),
+    )
+    .await;
+    body.as_object_mut()
+        .unwrap()
+        .extend(location.as_object().unwrap().clone());
+
+    let request = test::TestRequest::post()
+        .uri(uri)
+        .set_json(body)
+        .to_request();
+    let response: Value = test::call_and_read_body_json(&app, request).await;
+    assert_eq!(response["status"], "success", "{}", response);
+    response["data"].clone()
+}
+
+/// Sends a verbose `/panchang` request for noon on 15 August 2023 with `location`
+async fn panchang(config: &Config, location: Value) -> Value {
+    let body = json!({ "date": "15/08/2023", "time": "12:00", "verbose": true });
+    post(config, "/panchang", body, location).await
+}
+
+/// New York, the configured default, given in full
+fn given_new_york() -> Value {
+    json!({ "latitude": 40.7128, "longitude": -74.0060, "timezone": "America/New_York" })
+}
+
+#[actix_web::test]
+async fn request_without_a_zone_uses_the_default_timezone() {
+    let panchang = panchang(
+        &new_york(),
+        json!({ "latitude": 28.6139, "longitude": 77.2090 }),
+    )
+    .await;
+
+    // Eastern Daylight Time in August
+    assert_eq!(panchang["meta"]["zone_offset"], -4.0);
+    assert_eq!(panchang["meta"]["defaulted"], json!(["timezone"]));
+}
+
+#[actix_web::test]
+async fn request_without_a_location_uses_the_default_location() {
+    let defaulted = panchang(&new_york(), json!({})).await;
+    let given = panchang(&new_york(), given_new_york()).await;
+
+    assert_eq!(
+        defaulted["meta"]["defaulted"],
+        json!(["latitude", "longitude", "timezone"])
+    );
+    assert_eq!(given["meta"]["defaulted"], json!([]));
+    assert_eq!(defaulted["sunrise"], given["sunrise"]);
+    assert_eq!(defaulted["lagna"], given["lagna"]);
+}
+
+#[actix_web::test]
+async fn given_zone_is_kept() {
+    let panchang = panchang(
+        &new_york(),
+        json!({ "zone": "+05:30", "latitude": 28.6139, "longitude": 77.2090 }),
+    )
+    .await;
+
+    assert_eq!(panchang["meta"]["zone_offset"], 5.5);
+    assert_eq!(panchang["meta"]["defaulted"], json!([]));
+}
+
+#[actix_web::test]
+async fn range_without_a_location_uses_the_default_location() {
+    let body = json!({
+        "start_date": "15/08/2023",
+        "end_date": "16/08/2023",
+        "time": "12:00",
+        "verbose": true,
+    });
+    let defaulted = post(&new_york(), "/panchang/range", body.clone(), json!({})).await;
+    let given = post(&new_york(), "/panchang/range", body, given_new_york()).await;
+
+    assert_eq!(defaulted.as_array().unwrap().len(), 2);
+    for (defaulted, given) in defaulted
+        .as_array()
+        .unwrap()
+        .iter()
+        .zip(given.as_array().unwrap())
+    {
+        assert_eq!(
+            defaulted["meta"]["defaulted"],
+            json!(["latitude", "longitude", "timezone"])
+        );
+        assert_eq!(defaulted["sunrise"], given["sunrise"]);
+        assert_eq!(defaulted["lagna"], given["lagna"]);
+    }
+}
+
+#[actix_web::test]
+async fn ndjson_range_without_a_location_uses_the_default_location() {
+    let config = new_york();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::new(&config, None)))
+            .app_data(server::json_config(config.max_body_bytes))
+            .configure(server::configure),
+    )
+    .await;
+    let request = test::TestRequest::get()
+        .uri("/panchang/range.ndjson?start_date=15/08/2023&end_date=16/08/2023&time=12:00")
+        .to_request();
+    let body = test::call_and_read_body(&app, request).await;
+    let days: Vec<Value> = std::str::from_utf8(&body)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+
+    let given = post(
+        &config,
+        "/panchang",
+        json!({ "date": "15/08/2023", "time": "12:00" }),
+        given_new_york(),
+    )
+    .await;
+    assert_eq!(days.len(), 2, "{:?}", days);
+    assert_eq!(days[0]["sunrise"], given["sunrise"]);
+}
+
+#[actix_web::test]
+async fn next_tithi_without_a_location_uses_the_default_location() {
+    let body = json!({ "from_date": "20/08/2023", "tithi": "Ekadashi", "time": "06:00" });
+    let mut defaulted = post(&new_york(), "/panchang/next-tithi", body.clone(), json!({})).await;
+    let mut given = post(&new_york(), "/panchang/next-tithi", body, given_new_york()).await;
+
+    assert_eq!(
+        defaulted["defaulted"].take(),
+        json!(["latitude", "longitude", "timezone"])
+    );
+    assert_eq!(given["defaulted"].take(), json!([]));
+    assert_eq!(defaulted, given);
+}
+
+#[actix_web::test]
+async fn compare_without_a_location_uses_the_default_location() {
+    let body = json!({ "date": "15/08/2023", "time": "12:00", "ayanamsas": ["lahiri", "raman"] });
+    let mut defaulted = post(&new_york(), "/panchang/compare", body.clone(), json!({})).await;
+    let mut given = post(&new_york(), "/panchang/compare", body, given_new_york()).await;
+
+    assert_eq!(
+        defaulted["defaulted"].take(),
+        json!(["latitude", "longitude", "timezone"])
+    );
+    assert_eq!(given["defaulted"].take(), json!([]));
+    assert_eq!(defaulted, given);
+}
+
+#[actix_web::test]
+async fn next_tithi_and_compare_name_only_the_parts_defaulted() {
+    let location = json!({ "latitude": 28.6139, "longitude": 77.2090 });
+    let next_tithi = post(
+        &new_york(),
+        "/panchang/next-tithi",
+        json!({ "from_date": "20/08/2023", "tithi": "Ekadashi", "time": "06:00" }),
+        location.clone(),
+    )
+    .await;
+    let compare = post(
+        &new_york(),
+        "/panchang/compare",
+        json!({ "date": "15/08/2023", "time": "12:00", "ayanamsas": ["lahiri", "raman"] }),
+        location,
+    )
+    .await;
+
+    assert_eq!(next_tithi["defaulted"], json!(["timezone"]));
+    assert_eq!(compare["defaulted"], json!(["timezone"]));
+}
@@ -0,0 +1,95 @@
+//! Checks `panchanga_diff` over pairs of Panchangas built from one calculation:
+//! identical, with a single field changed, and with every field changed.
+
+use panchang_rs::panchang::panchanga_diff;
+use panchang_rs::{Ayanamsa, Panchanga, calculate_panchanga};
+
+/// Panchanga of Independence Day 2023 at noon in IST
+fn panchanga() -> Panchanga {
+    calculate_panchanga(15, 8, 2023, 12.0, 5.5, Ayanamsa::Lahiri)
+}
+
+#[test]
+fn identical_panchangas_do_not_differ() {
+    let a = panchanga();
+    assert!(panchanga_diff(&a, &a.clone()).is_empty());
+}
+
+#[test]
+fn single_changed_field_is_named() {
+    let a = panchanga();
+    let mut b = a.clone();
+    b.current_nakshatra = String::from("Mrigashira");
+
+    assert_eq!(panchanga_diff(&a, &b), ["nakshatra"]);
+    assert_eq!(panchanga_diff(&b, &a), ["nakshatra"]);
+    assert_eq!(b.field("nakshatra").as_deref(), Some("Mrigashira"));
+}
+
+#[test]
+fn every_changed_field_is_named_in_order() {
+    let a = panchanga();
+    let b = Panchanga {
+        current_yoga: String::from("-"),
+        current_nakshatra: String::from("-"),
+        current_nakshatra_number: a.current_nakshatra_number % 27 + 1,
+        current_nakshatra_28: String::from("-"),
+        current_nakshatra_pada: a.current_nakshatra_pada % 4 + 1,
+        current_nakshatra_lord: String::from("-"),
+        current_tithi: String::from("-"),
+        current_tithi_number: a.current_tithi_number % 30 + 1,
+        current_karana: String::from("-"),
+        current_paksha: String::from("-"),
+        current_chandra_rashi: String::from("-"),
+        current_chandra_rashi_number: a.current_chandra_rashi_number % 12 + 1,
+        current_rashi_lord: String::from("-"),
+        current_navamsa_rashi: String::from("-"),
+        current_surya_rashi: String::from("-"),
+        current_surya_nakshatra: String::from("-"),
+        current_surya_nakshatra_pada: a.current_surya_nakshatra_pada % 4 + 1,
+        moon_illumination: a.moon_illumination + 0.5,
+        moon_phase_angle: a.moon_phase_angle + 1.0,
+        moon_phase: String::from("-"),
+        current_masa: String::from("-"),
+        current_purnimanta_masa: String::from("-"),
+        is_adhika_masa: !a.is_adhika_masa,
+        current_samvatsara: String::from("-"),
+        current_kali_year: a.current_kali_year + 1,
+        current_ayana: String::from("-"),
+        current_ritu: String::from("-"),
+    };
+
+    assert_eq!(
+        panchanga_diff(&a, &b),
+        [
+            "tithi",
+            "tithi_number",
+            "paksha",
+            "nakshatra",
+            "nakshatra_number",
+            "nakshatra_28",
+            "nakshatra_pada",
+            "nakshatra_lord",
+            "yoga",
+            "karana",
+            "chandra_rashi",
+            "chandra_rashi_number",
+            "rashi_lord",
+            "navamsa_rashi",
+            "surya_rashi",
+            "surya_nakshatra",
+            "surya_nakshatra_pada",
+            "moon_illumination",
+            "moon_phase_angle",
+            "moon_phase",
+            "masa",
+            "purnimanta_masa",
+            "is_adhika_masa",
+            "samvatsara",
+            "kali_year",
+            "ayana",
+            "ritu",
+        ]
+    );
+    assert_eq!(a.field("lagna"), None);
+}
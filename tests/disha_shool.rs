@@ -0,0 +1,18 @@
+//! Checks `disha_shool` against the traditional rule "Shani Soma purab na chalu,
+//! Mangal Budh uttar disi kalu": East on Saturday and Monday, North on Tuesday and
+//! Wednesday, South on Thursday, and West on Friday and Sunday.
+
+use panchang_rs::panchang::disha_shool;
+
+#[test]
+fn direction_of_each_weekday() {
+    let expected = ["West", "East", "North", "North", "South", "West", "East"];
+    for (weekday, direction) in expected.iter().enumerate() {
+        let shool = disha_shool(weekday);
+        assert_eq!(shool.direction, *direction, "weekday {weekday}");
+        assert!(!shool.remedy.is_empty());
+    }
+
+    // The weekday wraps like the other weekday tables
+    assert_eq!(disha_shool(8).direction, "East");
+}
@@ -0,0 +1,52 @@
+//! Snapshots of the text renderings of a Panchanga, for noon IST on 15/08/2023.
+
+use panchang_rs::{Ayanamsa, Panchanga, calculate_panchanga};
+
+fn independence_day() -> Panchanga {
+    calculate_panchanga(15, 8, 2023, 12.0, 5.5, Ayanamsa::Lahiri)
+}
+
+#[test]
+fn display_aligns_values() {
+    let expected = "\
+Tithi            Chaturdashi (29 of 30)
+Paksha           Krishna
+Nakshatra        Pushya, pada 4, lord Shani
+Yoga             Vyatipata
+Karana           Sakuni
+Rashi            Karka, lord Chandra
+Surya Rashi      Karka
+Masa             Shravana (Adhika)
+Purnimanta Masa  Shravana (Adhika)
+Samvatsara       Shobhakrit
+Ayana            Dakshinayana
+Ritu             Varsha
+Moon             New Moon, 1.2% illuminated";
+
+    assert_eq!(independence_day().to_string(), expected);
+}
+
+#[test]
+fn table_is_boxed() {
+    let expected = "\
++-----------------+----------------------------+
+| Element         | Value                      |
++-----------------+----------------------------+
+| Tithi           | Chaturdashi (29 of 30)     |
+| Paksha          | Krishna                    |
+| Nakshatra       | Pushya, pada 4, lord Shani |
+| Yoga            | Vyatipata                  |
+| Karana          | Sakuni                     |
+| Rashi           | Karka, lord Chandra        |
+| Surya Rashi     | Karka                      |
+| Masa            | Shravana (Adhika)          |
+| Purnimanta Masa | Shravana (Adhika)          |
+| Samvatsara      | Shobhakrit                 |
+| Ayana           | Dakshinayana               |
+| Ritu            | Varsha                     |
+| Moon            | New Moon, 1.2% illuminated |
++-----------------+----------------------------+
+";
+
+    assert_eq!(independence_day().to_table(), expected);
+}
@@ -0,0 +1,40 @@
+//! Checks how a named timezone resolves the local times that US daylight saving
+//! skips or repeats. In New York, 02:00-03:00 on 10 March 2024 never happened and
+//! 01:00-02:00 on 3 November 2024 happened twice.
+
+use chrono::FixedOffset;
+use panchang_rs::panchang::{PanchangRequest, ValidatedRequest};
+use serde_json::json;
+
+/// The UTC offset a request in New York resolves to
+fn offset(date: &str, time: &str) -> FixedOffset {
+    let request: PanchangRequest = serde_json::from_value(json!({
+        "date": date,
+        "time": time,
+        "timezone": "America/New_York",
+        "latitude": 40.7128,
+        "longitude": -74.0060,
+    }))
+    .unwrap();
+    ValidatedRequest::try_from(&request).unwrap().offset()
+}
+
+#[test]
+fn spring_forward_gap_uses_the_offset_before_it() {
+    let est = FixedOffset::west_opt(5 * 3600).unwrap();
+    let edt = FixedOffset::west_opt(4 * 3600).unwrap();
+
+    assert_eq!(offset("10/03/2024", "01:59"), est);
+    assert_eq!(offset("10/03/2024", "02:30"), est);
+    assert_eq!(offset("10/03/2024", "03:00"), edt);
+}
+
+#[test]
+fn fall_back_overlap_uses_the_earlier_offset() {
+    let est = FixedOffset::west_opt(5 * 3600).unwrap();
+    let edt = FixedOffset::west_opt(4 * 3600).unwrap();
+
+    assert_eq!(offset("03/11/2024", "00:59"), edt);
+    assert_eq!(offset("03/11/2024", "01:30"), edt);
+    assert_eq!(offset("03/11/2024", "02:00"), est);
+}
@@ -0,0 +1,60 @@
+//! Checks that a request by `epoch` calculates the same Panchang as the request by
+//! the equivalent date, time and zone, and that an epoch outside the years 1 to
+//! 9999 is rejected.
+
+use panchang_rs::panchang::{PanchangError, calculate_panchang};
+use serde_json::{Value, json};
+
+mod common;
+
+/// The verbose Panchang in New Delhi of a request with the fields of `when`, in
+/// place of a date and time
+fn panchang(when: Value) -> Value {
+    let mut options = json!({ "date": null, "time": null, "verbose": true });
+    options
+        .as_object_mut()
+        .unwrap()
+        .extend(when.as_object().unwrap().clone());
+    common::panchang_json(&common::delhi_with("", "", options))
+}
+
+#[test]
+fn epoch_matches_date_time_and_zone() {
+    // 09:30 UT on 15 August 2023, which is 15:00 IST
+    let by_epoch = panchang(json!({ "epoch": 1692091800 }));
+    let by_date = panchang(json!({ "date": "15/08/2023", "time": "15:00" }));
+
+    assert_eq!(by_epoch["meta"]["hour"], 15.0);
+    assert_eq!(by_epoch, by_date);
+}
+
+#[test]
+fn epoch_resolves_to_the_local_date() {
+    // 20:00 UT on 14 August 2023 is already 01:30 on the 15th in IST
+    let by_epoch = panchang(json!({ "epoch": 1692043200 }));
+    let by_date = panchang(json!({ "date": "15/08/2023", "time": "01:30" }));
+
+    assert_eq!(by_epoch["meta"]["day"], 15);
+    assert_eq!(by_epoch, by_date);
+}
+
+#[test]
+fn epoch_outside_the_supported_years_is_rejected() {
+    // 1 January of year 0, year 255 479, and a year chrono still represents but whose
+    // day number overflows the calculation
+    for epoch in [-62167219200_i64, 8000000000000, -8334601228800] {
+        let request = common::delhi_with(
+            "",
+            "",
+            json!({ "date": null, "time": null, "epoch": epoch }),
+        );
+        let result = common::block_on(calculate_panchang(&request));
+
+        assert!(
+            matches!(result, Err(PanchangError::DateOutOfRange(_))),
+            "epoch {}: {:?}",
+            epoch,
+            result.map(|_| ())
+        );
+    }
+}
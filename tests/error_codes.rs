@@ -0,0 +1,109 @@
+//! Checks the code and HTTP status of every `PanchangError` variant against the
+//! error table of the README.
+
+#![cfg(feature = "server")]
+
+use actix_web::ResponseError;
+use panchang_rs::PanchangError;
+
+#[test]
+fn every_variant_has_its_documented_code_and_status() {
+    let reason = || String::from("reason");
+    let table = [
+        (
+            PanchangError::InvalidDateFormat(reason()),
+            "INVALID_DATE",
+            400,
+        ),
+        (
+            PanchangError::DateOutOfRange(reason()),
+            "DATE_OUT_OF_RANGE",
+            422,
+        ),
+        (
+            PanchangError::InvalidDateRange(reason()),
+            "INVALID_DATE_RANGE",
+            422,
+        ),
+        (
+            PanchangError::InvalidTimeFormat(reason()),
+            "INVALID_TIME",
+            400,
+        ),
+        (
+            PanchangError::InvalidInstant(reason()),
+            "INVALID_INSTANT",
+            400,
+        ),
+        (
+            PanchangError::InvalidTimezone(reason()),
+            "INVALID_TIMEZONE",
+            400,
+        ),
+        (
+            PanchangError::InvalidLocation(reason()),
+            "INVALID_LOCATION",
+            400,
+        ),
+        (
+            PanchangError::InvalidBirthDetails(reason()),
+            "INVALID_BIRTH_DETAILS",
+            400,
+        ),
+        (PanchangError::InvalidTithi(reason()), "INVALID_TITHI", 400),
+        (
+            PanchangError::TithiNotFound(reason()),
+            "TITHI_NOT_FOUND",
+            422,
+        ),
+        (
+            PanchangError::InvalidComparison(reason()),
+            "INVALID_COMPARISON",
+            400,
+        ),
+        (
+            PanchangError::UnsupportedApiVersion(reason()),
+            "UNSUPPORTED_API_VERSION",
+            406,
+        ),
+        (
+            PanchangError::CalculationTimeout(1000),
+            "CALCULATION_TIMEOUT",
+            504,
+        ),
+        (
+            PanchangError::CalculationFailed(reason()),
+            "INTERNAL_ERROR",
+            500,
+        ),
+    ];
+    let readme = include_str!("../README.md");
+
+    for (error, code, status) in table {
+        assert_eq!(error.code(), code, "{:?}", error);
+        assert_eq!(error.status_code().as_u16(), status, "{:?}", error);
+        let row = format!("| `{}` | {} |", code, status);
+        assert!(readme.contains(&row), "README lacks the row {}", row);
+    }
+}
+
+/// Fails to compile when a variant is added, until it is added to the table above
+#[allow(dead_code)]
+fn table_is_exhaustive(error: PanchangError) {
+    match error {
+        PanchangError::InvalidDateFormat(_)
+        | PanchangError::DateOutOfRange(_)
+        | PanchangError::InvalidDateRange(_)
+        | PanchangError::InvalidTimeFormat(_)
+        | PanchangError::InvalidInstant(_)
+        | PanchangError::InvalidTimezone(_)
+        | PanchangError::InvalidLocation(_)
+        | PanchangError::InvalidBirthDetails(_)
+        | PanchangError::InvalidTithi(_)
+        | PanchangError::TithiNotFound(_)
+        | PanchangError::InvalidComparison(_)
+        | PanchangError::UnsupportedApiVersion(_)
+        | PanchangError::CalculationTimeout(_)
+        | PanchangError::CalculationFailed(_) => {}
+    }
+}
@@ -0,0 +1,26 @@
+//! Checks `special_days`: Ekadashi in both Pakshas, and the Krishna Chaturthi that
+//! becomes Angaraki Sankashti on a Tuesday.
+
+use panchang_rs::panchang::special_days;
+
+#[test]
+fn ekadashi_in_both_pakshas() {
+    for paksha in ["Shukla", "Krishna"] {
+        for weekday in 0..7 {
+            assert_eq!(special_days(11, paksha, weekday), ["Ekadashi"]);
+        }
+    }
+    assert!(special_days(10, "Shukla", 0).is_empty());
+}
+
+#[test]
+fn sankashti_on_a_tuesday_is_angaraki() {
+    assert_eq!(
+        special_days(4, "Krishna", 2),
+        ["Angaraki Sankashti Chaturthi"]
+    );
+    assert_eq!(special_days(4, "Krishna", 3), ["Sankashti Chaturthi"]);
+
+    // The Shukla Chaturthi is Vinayaka Chaturthi whatever the weekday
+    assert_eq!(special_days(4, "Shukla", 2), ["Vinayaka Chaturthi"]);
+}
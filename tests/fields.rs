@@ -0,0 +1,146 @@
+//! Checks that `fields` restricts a response to the named fields, in the query of a
+//! GET and the body of a POST, and that an unknown name is refused before anything
+//! is calculated.
+
+#![cfg(feature = "server")]
+
+use actix_web::http::StatusCode;
+use actix_web::{App, test, web};
+use panchang_rs::config::Config;
+use panchang_rs::panchang::{
+    Language, PanchangRequest, RESPONSE_FIELDS, ResponseFields, calculate_panchang,
+};
+use panchang_rs::server::{self, AppState};
+use serde_json::{Value, json};
+
+/// Keys of a JSON object, sorted
+fn keys(value: &Value) -> Vec<&str> {
+    let mut keys: Vec<&str> = value
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(String::as_str)
+        .collect();
+    keys.sort();
+    keys
+}
+
+#[actix_web::test]
+async fn response_fields_name_every_field() {
+    let request: PanchangRequest = serde_json::from_value(json!({
+        "date": "15/08/2023",
+        "time": "12:00",
+        "zone": "+05:30",
+        "latitude": 28.6139,
+        "longitude": 77.2090,
+        "verbose": true,
+        "longitudes": true,
+        "samples": 2,
+    }))
+    .unwrap();
+
+    let response = calculate_panchang(&request)
+        .await
+        .unwrap()
+        .with_labels(Language::English);
+
+    let mut fields = RESPONSE_FIELDS.to_vec();
+    fields.sort();
+    assert_eq!(keys(&serde_json::to_value(response).unwrap()), fields);
+}
+
+#[actix_web::test]
+async fn unknown_and_empty_selections_are_rejected() {
+    assert!(ResponseFields::parse(" tithi , nakshatra ").is_ok());
+    assert_eq!(
+        ResponseFields::parse("tithi,weekday").unwrap_err(),
+        "unknown field `weekday` in `fields`"
+    );
+    assert!(ResponseFields::parse(" , ").is_err());
+}
+
+#[actix_web::test]
+async fn selected_fields_are_the_only_ones_sent() {
+    let config = Config::load(None, |_| None).unwrap();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::new(&config, None)))
+            .app_data(server::json_config(config.max_body_bytes))
+            .configure(server::configure),
+    )
+    .await;
+    let query =
+        "/panchang?date=15/08/2023&time=12:00&zone=%2B05:30&latitude=28.6139&longitude=77.2090";
+
+    let response: Value = test::call_and_read_body_json(
+        &app,
+        test::TestRequest::get()
+            .uri(&format!("{}&fields=tithi,nakshatra", query))
+            .to_request(),
+    )
+    .await;
+    assert_eq!(keys(&response["data"]), ["nakshatra", "tithi"]);
+    assert_eq!(response["data"]["nakshatra"], "Pushya");
+
+    let response: Value =
+        test::call_and_read_body_json(&app, test::TestRequest::get().uri(query).to_request()).await;
+    assert_eq!(keys(&response["data"]).len(), RESPONSE_FIELDS.len() - 5);
+
+    let response: Value = test::call_and_read_body_json(
+        &app,
+        test::TestRequest::post()
+            .uri("/panchang/range")
+            .set_json(json!({
+                "start_date": "15/08/2023",
+                "end_date": "16/08/2023",
+                "time": "12:00",
+                "zone": "+05:30",
+                "latitude": 28.6139,
+                "longitude": 77.2090,
+                "fields": "tithi",
+            }))
+            .to_request(),
+    )
+    .await;
+    for day in response["data"].as_array().unwrap() {
+        assert_eq!(keys(day), ["date", "tithi"]);
+    }
+}
+
+#[actix_web::test]
+async fn unknown_field_is_a_bad_request() {
+    let config = Config::load(None, |_| None).unwrap();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::new(&config, None)))
+            .app_data(server::json_config(config.max_body_bytes))
+            .configure(server::configure),
+    )
+    .await;
+
+    let response = test::call_service(
+        &app,
+        test::TestRequest::post()
+            .uri("/panchang")
+            .set_json(json!({
+                "date": "15/08/2023",
+                "time": "12:00",
+                "zone": "+05:30",
+                "latitude": 28.6139,
+                "longitude": 77.2090,
+                "fields": "tithi,weekday",
+            }))
+            .to_request(),
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body: Value = test::read_body_json(response).await;
+    assert_eq!(body["code"], "INVALID_BODY");
+    assert!(
+        body["message"]
+            .as_str()
+            .unwrap()
+            .contains("unknown field `weekday` in `fields`")
+    );
+}
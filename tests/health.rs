@@ -0,0 +1,147 @@
+//! Checks that the server starts and serves `/health` without any database
+//! configured, and `/health` against a database that is down: the service reports itself
+//! degraded but healthy enough to serve, unless `DB_REQUIRED` makes it unavailable.
+//! Checks as well that `/healthz` only reports the process alive, while `/readyz`
+//! waits for a configured database.
+//!
+//! The working-database case needs PostgreSQL, configured through the `DB_*`
+//! variables, so it is ignored by default; run it with `cargo test --test health -- --ignored`.
+
+#![cfg(feature = "server")]
+
+use std::time::Duration;
+
+use actix_web::http::StatusCode;
+use actix_web::{App, test, web};
+use panchang_rs::config::Config;
+use panchang_rs::db::init_db;
+use panchang_rs::server::{self, AppState};
+use serde_json::Value;
+use sqlx::PgPool;
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+
+mod common;
+
+/// A pool for a database nobody listens for, on port 1, which fails every query
+fn broken_pool() -> PgPool {
+    PgPoolOptions::new()
+        .acquire_timeout(Duration::from_millis(500))
+        .connect_lazy_with(
+            PgConnectOptions::new()
+                .host("127.0.0.1")
+                .port(1)
+                .username("panchang")
+                .database("panchang"),
+        )
+}
+
+/// Configures a server with `DB_REQUIRED` set to `required`
+fn config(required: bool) -> Config {
+    Config::load(None, |name| {
+        (name == "DB_REQUIRED").then(|| required.to_string())
+    })
+    .unwrap()
+}
+
+/// Fetches `path` from an app with the state, returning the status and the body
+async fn get(state: AppState, path: &str) -> (StatusCode, Value) {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(state))
+            .configure(server::configure),
+    )
+    .await;
+    let response = test::call_service(&app, test::TestRequest::get().uri(path).to_request()).await;
+    (response.status(), test::read_body_json(response).await)
+}
+
+#[actix_web::test]
+async fn starts_without_a_database() {
+    // No DB_* variable at all
+    let config = Config::load(None, |_| None).unwrap();
+    assert!(config.db.is_none());
+    let pool = init_db(config.db.as_ref()).await.unwrap();
+    assert!(pool.is_none());
+
+    let (status, body) = get(AppState::new(&config, pool), "/health").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "healthy");
+    assert_eq!(body["data"]["engine"]["status"], "up");
+    assert_eq!(body["data"]["db"]["status"], "disabled");
+}
+
+#[actix_web::test]
+async fn broken_database_degrades_the_service() {
+    let (status, body) = get(
+        AppState::new(&config(false), Some(broken_pool())),
+        "/health",
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "degraded");
+    assert_eq!(body["statusCode"], 200);
+    assert_eq!(body["data"]["engine"]["status"], "up");
+    assert_eq!(body["data"]["db"]["status"], "down");
+    assert!(body["data"]["db"]["message"].is_string());
+}
+
+#[actix_web::test]
+async fn broken_required_database_is_unavailable() {
+    let (status, body) = get(AppState::new(&config(true), Some(broken_pool())), "/health").await;
+
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["status"], "degraded");
+    assert_eq!(body["statusCode"], 503);
+    assert_eq!(body["data"]["db"]["status"], "down");
+}
+
+#[actix_web::test]
+async fn probes_without_a_database() {
+    let (status, body) = get(AppState::new(&config(false), None), "/healthz").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "alive");
+
+    let (status, body) = get(AppState::new(&config(false), None), "/readyz").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "ready");
+    assert_eq!(body["data"]["db"]["status"], "disabled");
+}
+
+#[actix_web::test]
+async fn probes_with_a_broken_database() {
+    // Alive, so the process is not restarted, but not ready for traffic
+    let (status, body) = get(
+        AppState::new(&config(false), Some(broken_pool())),
+        "/healthz",
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "alive");
+
+    let (status, body) = get(
+        AppState::new(&config(false), Some(broken_pool())),
+        "/readyz",
+    )
+    .await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["status"], "unready");
+    assert_eq!(body["statusCode"], 503);
+    assert_eq!(body["data"]["db"]["status"], "down");
+}
+
+#[actix_web::test]
+#[ignore = "needs a PostgreSQL database, configured through DB_HOST"]
+async fn probes_with_a_working_database() {
+    let config = Config::from_env().expect("valid configuration");
+    let pool = common::connect(&config).await;
+
+    let (status, body) = get(AppState::new(&config, Some(pool.clone())), "/healthz").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "alive");
+
+    let (status, body) = get(AppState::new(&config, Some(pool)), "/readyz").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "ready");
+    assert_eq!(body["data"]["db"]["status"], "up");
+}
@@ -0,0 +1,50 @@
+//! Checks `current_hora`: the first Hora at sunrise is ruled by the weekday's lord,
+//! and day and night Horas are twelfths of the daytime and of the night.
+
+use panchang_rs::panchang::current_hora;
+
+/// The lord of each weekday, from Sunday
+const WEEKDAY_LORD: [&str; 7] = [
+    "Surya", "Chandra", "Mangala", "Budha", "Guru", "Shukra", "Shani",
+];
+
+#[test]
+fn first_hora_is_ruled_by_the_weekday_lord() {
+    for (weekday, lord) in WEEKDAY_LORD.iter().enumerate() {
+        let hora = current_hora(6.0, 18.0, 6.0, weekday, 6.0).unwrap();
+        assert_eq!(hora.lord, *lord, "weekday {weekday}");
+        assert_eq!((hora.start, hora.end), (6.0, 7.0));
+    }
+}
+
+#[test]
+fn horas_follow_the_chaldean_order() {
+    // Sunday: Surya, Shukra, Budha, Chandra, Shani, Guru, Mangala, then Surya again
+    let lords: Vec<_> = (0..8)
+        .map(|hora| {
+            current_hora(6.0, 18.0, 6.0, 0, 6.5 + hora as f64)
+                .unwrap()
+                .lord
+        })
+        .collect();
+    assert_eq!(
+        lords,
+        [
+            "Surya", "Shukra", "Budha", "Chandra", "Shani", "Guru", "Mangala", "Surya"
+        ]
+    );
+
+    // The last night Hora of Sunday precedes Monday's first, ruled by Chandra
+    let last = current_hora(6.0, 18.0, 6.0, 0, 29.5).unwrap();
+    assert_eq!(last.lord, "Budha");
+}
+
+#[test]
+fn night_horas_are_twelfths_of_the_night() {
+    // A 14-hour daytime leaves a 10-hour night of 50-minute Horas
+    let hora = current_hora(5.0, 19.0, 5.0, 3, 19.0).unwrap();
+    assert!((hora.end - hora.start - 50.0 / 60.0).abs() < 1e-9);
+
+    assert!(current_hora(5.0, 19.0, 5.0, 3, 4.9).is_none());
+    assert!(current_hora(5.0, 19.0, 5.0, 3, 29.0).is_none());
+}
@@ -0,0 +1,105 @@
+//! Checks that a POST retried with the same `Idempotency-Key` gets the first
+//! response back, and that reusing a key for another request is refused.
+//!
+//! Needs a PostgreSQL database, configured through the same `DB_HOST`, `DB_PORT`,
+//! `DB_USER`, `DB_PASSWORD` and `DB_NAME` variables as the server, so they are ignored
+//! by default; run them with `cargo test --test idempotency -- --ignored`.
+
+#![cfg(feature = "server")]
+
+use actix_web::middleware::from_fn;
+use actix_web::{App, test, web};
+use actix_web::{body::MessageBody, dev::ServiceResponse, http::StatusCode};
+use panchang_rs::config::Config;
+use panchang_rs::idempotency::idempotency;
+use panchang_rs::server::{self, AppState};
+use serde_json::Value;
+use uuid::Uuid;
+
+mod common;
+
+/// Body of a request for the Panchang at noon in New Delhi on a date
+fn body(date: &str) -> String {
+    format!(
+        r#"{{"date":"{}","time":"12:00","zone":"+05:30","latitude":28.6139,"longitude":77.2090}}"#,
+        date
+    )
+}
+
+/// Reads a response body as bytes
+async fn bytes(response: ServiceResponse<impl MessageBody>) -> Vec<u8> {
+    test::read_body(response).await.to_vec()
+}
+
+#[actix_web::test]
+#[ignore = "needs a PostgreSQL database, configured through DB_HOST"]
+async fn replays_the_first_response() {
+    let config = Config::from_env().expect("valid configuration");
+    let pool = common::connect(&config).await;
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::new(&config, Some(pool))))
+            .app_data(server::json_config(config.max_body_bytes))
+            .wrap(from_fn(idempotency))
+            .configure(server::configure),
+    )
+    .await;
+
+    let key = Uuid::new_v4().to_string();
+    let request = |body: String| {
+        test::TestRequest::post()
+            .uri("/panchang")
+            .insert_header(("Idempotency-Key", key.as_str()))
+            .insert_header(("Content-Type", "application/json"))
+            .set_payload(body)
+            .to_request()
+    };
+
+    let first = test::call_service(&app, request(body("15/08/2023"))).await;
+    assert_eq!(first.status(), StatusCode::OK);
+    assert!(first.headers().get("Idempotent-Replayed").is_none());
+    let first = bytes(first).await;
+
+    // The same fields in another order make the same request
+    let reordered = r#"{"longitude":77.2090,"latitude":28.6139,"zone":"+05:30","time":"12:00","date":"15/08/2023"}"#;
+    let replay = test::call_service(&app, request(reordered.to_string())).await;
+    assert_eq!(replay.status(), StatusCode::OK);
+    assert_eq!(replay.headers().get("Idempotent-Replayed").unwrap(), "true");
+    // Down to the timestamp and the request id of the first response
+    assert_eq!(bytes(replay).await, first);
+}
+
+#[actix_web::test]
+#[ignore = "needs a PostgreSQL database, configured through DB_HOST"]
+async fn refuses_a_reused_key() {
+    let config = Config::from_env().expect("valid configuration");
+    let pool = common::connect(&config).await;
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::new(&config, Some(pool))))
+            .app_data(server::json_config(config.max_body_bytes))
+            .wrap(from_fn(idempotency))
+            .configure(server::configure),
+    )
+    .await;
+
+    let key = Uuid::new_v4().to_string();
+    let request = |body: String| {
+        test::TestRequest::post()
+            .uri("/panchang")
+            .insert_header(("Idempotency-Key", key.as_str()))
+            .insert_header(("Content-Type", "application/json"))
+            .set_payload(body)
+            .to_request()
+    };
+
+    let first = test::call_service(&app, request(body("15/08/2023"))).await;
+    assert_eq!(first.status(), StatusCode::OK);
+
+    let conflict = test::call_service(&app, request(body("16/08/2023"))).await;
+    assert_eq!(conflict.status(), StatusCode::CONFLICT);
+    let body: Value = test::read_body_json(conflict).await;
+    assert_eq!(body["status"], "error");
+    assert_eq!(body["statusCode"], 409);
+    assert_eq!(body["code"], "IDEMPOTENCY_KEY_REUSED");
+}
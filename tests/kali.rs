@@ -0,0 +1,69 @@
+//! Checks the Kali Ahargana against the Julian Day Number of the Kali Yuga epoch,
+//! 588465.5 at midnight of 17/18 February 3102 BCE, and the Kali year against the
+//! Saka year it runs alongside.
+
+use chrono::{Datelike, NaiveDate};
+use panchang_rs::panchang::kali_ahargana;
+use panchang_rs::{Ayanamsa, calculate_panchanga};
+use serde_json::Value;
+
+mod common;
+
+/// Julian Day Number of the Kali Yuga's first day, 18 February 3102 BCE
+const KALI_EPOCH_JDN: i32 = 588_466;
+
+/// Days from the proleptic Gregorian 1 January of year 1 to its Julian Day Number
+const CE_TO_JDN: i32 = 1_721_425;
+
+/// Calculates the Panchang for an instant in New Delhi, as JSON
+fn panchang(date: &str, time: &str) -> Value {
+    common::panchang_json(&common::delhi(date, time))
+}
+
+#[test]
+fn ahargana_of_reference_dates() {
+    assert_eq!(kali_ahargana(1, 1, 2000), 1_863_079);
+    // 15 August 1947 was a Friday, so a whole number of weeks from the epoch
+    assert_eq!(kali_ahargana(15, 8, 1947), 1_843_947);
+    assert_eq!(kali_ahargana(15, 8, 1947) % 7, 0);
+}
+
+#[test]
+fn ahargana_counts_julian_days_from_the_epoch() {
+    for (day, month, year) in [
+        (1, 1, 1),
+        (4, 10, 1582),
+        (15, 10, 1582),
+        (29, 2, 2024),
+        (31, 12, 9999),
+    ] {
+        let date = NaiveDate::from_ymd_opt(year, month, day).unwrap();
+        assert_eq!(
+            kali_ahargana(day as i32, month as i32, year),
+            date.num_days_from_ce() + CE_TO_JDN - KALI_EPOCH_JDN,
+            "{}",
+            date
+        );
+    }
+}
+
+#[test]
+fn kali_year_turns_at_chaitra() {
+    // Saka 1945 until the Chaitra Shukla Pratipada of 9 April 2024, then 1946
+    let before = calculate_panchanga(1, 2, 2024, 12.0, 5.5, Ayanamsa::Lahiri);
+    let after = calculate_panchanga(15, 8, 2024, 12.0, 5.5, Ayanamsa::Lahiri);
+    assert_eq!(before.current_kali_year, 5124);
+    assert_eq!(after.current_kali_year, 5125);
+}
+
+#[test]
+fn response_counts_the_ahargana_of_the_vaara() {
+    let noon = panchang("15/08/2023", "12:00");
+    assert_eq!(noon["kali_year"], 5124);
+    assert_eq!(noon["kali_ahargana"], kali_ahargana(15, 8, 2023));
+
+    // Before sunrise it is still the previous day's Vaara, and Ahargana
+    let dawn = panchang("15/08/2023", "04:00");
+    assert_eq!(dawn["vaara"]["english"], "Monday");
+    assert_eq!(dawn["kali_ahargana"], kali_ahargana(14, 8, 2023));
+}
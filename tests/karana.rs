@@ -0,0 +1,91 @@
+//! Walks the 60 half-Tithis of a month through `karana`, and the Karanas of the
+//! synodic month from the new moon of 8 May 2024 (03:22 UT) to that of 6 June 2024
+//! (12:37 UT), across both Amavasya to Prathama transitions.
+
+use chrono::{DateTime, Duration, Utc};
+use panchang_rs::panchang::karana;
+use panchang_rs::{Ayanamsa, calculate_panchanga_at};
+
+const MOVABLE: [&str; 7] = [
+    "Bava", "Balava", "Kaulava", "Taitula", "Garija", "Vanija", "Visti",
+];
+
+const CLOSING: [&str; 3] = ["Sakuni", "Chatuspada", "Naga"];
+
+#[test]
+fn every_half_tithi_has_its_karana() {
+    // Kimstughna opens the month, the movable seven repeat eight times, and the
+    // three closing Karanas end it
+    let mut expected = vec!["Kimstughna"];
+    for _ in 0..8 {
+        expected.extend(MOVABLE);
+    }
+    expected.extend(CLOSING);
+    assert_eq!(expected.len(), 60);
+
+    for (half_tithi, name) in expected.iter().enumerate() {
+        let start = half_tithi as f64 * 6.0;
+        // The Sun's longitude drops out, only the elongation counts
+        for sun in [0.0, 120.0, 300.5] {
+            for elongation in [start, start + 3.0, start + 5.999] {
+                assert_eq!(
+                    karana(sun + elongation, sun),
+                    *name,
+                    "half-Tithi {} at {}° from the Sun",
+                    half_tithi + 1,
+                    elongation
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn fixed_karanas_occur_once_a_month() {
+    let karanas: Vec<&str> = (0..60)
+        .map(|half_tithi| karana(half_tithi as f64 * 6.0 + 3.0, 0.0))
+        .collect();
+    for fixed in ["Kimstughna", "Sakuni", "Chatuspada", "Naga"] {
+        assert_eq!(
+            karanas.iter().filter(|karana| **karana == fixed).count(),
+            1,
+            "{}",
+            fixed
+        );
+    }
+    for movable in MOVABLE {
+        assert_eq!(
+            karanas.iter().filter(|karana| **karana == movable).count(),
+            8,
+            "{}",
+            movable
+        );
+    }
+}
+
+#[test]
+fn synodic_month_runs_through_the_karanas_in_order() {
+    // From the last Visti before the first new moon to the Kimstughna after the second
+    let mut instant: DateTime<Utc> = "2024-05-06T12:00:00Z".parse().unwrap();
+    let end: DateTime<Utc> = "2024-06-06T18:00:00Z".parse().unwrap();
+
+    // Each Karana lasts over 9 hours, so an hourly step sees all of them
+    let mut karanas: Vec<String> = Vec::new();
+    while instant <= end {
+        let karana = calculate_panchanga_at(instant, Ayanamsa::Lahiri).current_karana;
+        if karanas.last() != Some(&karana) {
+            karanas.push(karana);
+        }
+        instant += Duration::hours(1);
+    }
+
+    let mut expected = vec!["Visti"];
+    expected.extend(CLOSING);
+    expected.push("Kimstughna");
+    for _ in 0..8 {
+        expected.extend(MOVABLE);
+    }
+    expected.extend(CLOSING);
+    expected.push("Kimstughna");
+    assert_eq!(karanas, expected);
+}
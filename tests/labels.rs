@@ -0,0 +1,57 @@
+//! Checks that `Accept-Language: hi` labels the response fields in Hindi, and that
+//! every field of a Panchang has a label.
+
+#![cfg(feature = "server")]
+
+use actix_web::http::{StatusCode, header};
+use actix_web::{App, test, web};
+use panchang_rs::config::Config;
+use panchang_rs::panchang::{Language, RESPONSE_FIELDS, field_labels};
+use panchang_rs::server::{self, AppState};
+use serde_json::{Value, json};
+
+#[actix_web::test]
+async fn accept_language_hi_gives_hindi_labels() {
+    let config = Config::load(None, |_| None).unwrap();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::new(&config, None)))
+            .app_data(server::json_config(config.max_body_bytes))
+            .configure(server::configure),
+    )
+    .await;
+    let request = test::TestRequest::post()
+        .uri("/panchang")
+        .insert_header((header::ACCEPT_LANGUAGE, "hi-IN,hi;q=0.9,en;q=0.8"))
+        .set_json(json!({
+            "date": "15/08/2023",
+            "time": "12:00",
+            "zone": "+05:30",
+            "latitude": 28.6139,
+            "longitude": 77.2090,
+        }))
+        .to_request();
+    let response = test::call_service(&app, request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: Value = test::read_body_json(response).await;
+
+    let labels = &body["data"]["labels"];
+    assert_eq!(labels["tithi"], "तिथि");
+    assert_eq!(labels["nakshatra"], "नक्षत्र");
+    assert_eq!(labels["sun_longitude"], "सूर्य भोगांश");
+
+    // The keys stay in English
+    assert!(body["data"]["tithi"].is_string());
+}
+
+#[actix_web::test]
+async fn every_field_is_labelled() {
+    for language in [Language::English, Language::Hindi, Language::Tamil] {
+        let labels = field_labels(language);
+        for field in RESPONSE_FIELDS {
+            if !["samples", "labels", "meta"].contains(&field) {
+                assert!(labels.contains_key(field), "{field} in {language:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,22 @@
+//! Checks the Lagna against the ascendant of a published chart: Albert Einstein, born
+//! 14 March 1879 at 11:30 local mean time in Ulm (48°24′N, 10°00′E), whose chart in
+//! the Astro-Databank (Rodden rating AA) has the ascendant at 11°38′ Cancer, tropical.
+
+use chrono::{TimeZone, Utc};
+use panchang_rs::Ayanamsa;
+use panchang_rs::panchang::{ayanamsa_degrees, lagna};
+
+#[test]
+fn matches_a_published_ascendant() {
+    // Local mean time at 10°E is 40 minutes ahead of UT
+    let utc = Utc.with_ymd_and_hms(1879, 3, 14, 10, 50, 0).unwrap();
+    let (rashi, sidereal) = lagna(utc, 48.4, 10.0, Ayanamsa::Lahiri);
+
+    let ayanamsa = ayanamsa_degrees(14, 3, 1879, 10.0 + 50.0 / 60.0, 0.0, Ayanamsa::Lahiri);
+    let tropical = sidereal + ayanamsa;
+    let published = 90.0 + 11.0 + 38.0 / 60.0;
+    assert!((tropical - published).abs() < 1.0, "{}", tropical);
+
+    // With Lahiri's 22°, the ascendant falls back into Mithuna (Gemini)
+    assert_eq!(rashi, "Mithuna");
+}
@@ -0,0 +1,85 @@
+//! Checks that the coordinates of a request are validated before anything is
+//! calculated: the poles and the antimeridian are accepted, and a latitude or
+//! longitude beyond them is refused with `INVALID_LOCATION`.
+
+use panchang_rs::panchang::{PanchangError, PanchangRequest, calculate_panchang};
+
+mod common;
+
+/// Calculates the Panchang of 15 August 2023 at noon UTC at a location
+fn panchang_at(latitude: f64, longitude: f64) -> Result<(), PanchangError> {
+    let request = PanchangRequest::new("15/08/2023", "12:00", "Z", latitude, longitude);
+    common::block_on(calculate_panchang(&request)).map(|_| ())
+}
+
+#[test]
+fn boundary_coordinates_are_accepted() {
+    for (latitude, longitude) in [(90.0, 180.0), (-90.0, -180.0), (0.0, 0.0)] {
+        assert!(
+            panchang_at(latitude, longitude).is_ok(),
+            "latitude {} longitude {}",
+            latitude,
+            longitude
+        );
+    }
+}
+
+#[test]
+fn out_of_range_coordinates_are_rejected() {
+    for (latitude, longitude, field) in [
+        (100.0, 77.2090, "latitude"),
+        (-90.5, 77.2090, "latitude"),
+        (28.6139, 181.0, "longitude"),
+        (28.6139, -200.0, "longitude"),
+    ] {
+        let err = panchang_at(latitude, longitude).unwrap_err();
+        assert_eq!(err.code(), "INVALID_LOCATION");
+        assert!(
+            err.to_string().contains(field),
+            "latitude {} longitude {}: {}",
+            latitude,
+            longitude,
+            err
+        );
+    }
+}
+
+#[cfg(feature = "server")]
+mod server {
+    use actix_web::http::StatusCode;
+    use actix_web::{App, test, web};
+    use panchang_rs::config::Config;
+    use panchang_rs::server::{self, AppState};
+    use serde_json::Value;
+
+    #[actix_web::test]
+    async fn query_coordinates_are_validated() {
+        let config = Config::load(None, |_| None).unwrap();
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(AppState::new(&config, None)))
+                .configure(server::configure),
+        )
+        .await;
+
+        let uri = |latitude: &str| {
+            format!(
+                "/panchang?date=15/08/2023&time=12:00&zone=%2B05:30&latitude={}&longitude=77.2090",
+                latitude
+            )
+        };
+
+        let response = test::call_service(
+            &app,
+            test::TestRequest::get().uri(&uri("28.6139")).to_request(),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response =
+            test::call_service(&app, test::TestRequest::get().uri(&uri("100")).to_request()).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body: Value = test::read_body_json(response).await;
+        assert_eq!(body["code"], "INVALID_LOCATION");
+    }
+}
@@ -0,0 +1,53 @@
+//! Checks that the sidereal longitudes `longitudes` adds are the ones the elements
+//! come from: the Moon's lies in the arc of the returned Rashi and Nakshatra, and the
+//! Sun's in that of the Sun's Rashi.
+
+use panchang_rs::panchang::{nakshatra_index, rashi_index};
+use serde_json::{Value, json};
+
+mod common;
+
+/// The Panchang with longitudes in New Delhi at noon IST on a day of August 2023
+fn panchang(day: u32) -> Value {
+    let date = format!("{:02}/08/2023", day);
+    common::panchang_json(&common::delhi_with(
+        &date,
+        "12:00",
+        json!({ "longitudes": true }),
+    ))
+}
+
+/// Whether a longitude lies in arc `index` of arcs of `width` degrees
+fn in_arc(longitude: f64, index: usize, width: f64) -> bool {
+    let start = index as f64 * width;
+    (start..start + width).contains(&longitude)
+}
+
+#[test]
+fn moon_lies_in_the_returned_rashi() {
+    // A month takes the Moon through every Rashi
+    for day in 1..=31 {
+        let panchang = panchang(day);
+        let moon = panchang["moon_longitude"].as_f64().unwrap();
+        let sun = panchang["sun_longitude"].as_f64().unwrap();
+        assert!((0.0..360.0).contains(&moon), "{moon}");
+
+        let rashi = rashi_index(panchang["rashi"].as_str().unwrap()).unwrap();
+        assert!(
+            in_arc(moon, rashi, 30.0),
+            "{day} August: {moon}° in {}",
+            panchang["rashi"]
+        );
+        let nakshatra = nakshatra_index(panchang["nakshatra"].as_str().unwrap()).unwrap();
+        assert!(in_arc(moon, nakshatra, 40.0 / 3.0), "{day} August: {moon}°");
+        let surya_rashi = rashi_index(panchang["surya_rashi"].as_str().unwrap()).unwrap();
+        assert!(in_arc(sun, surya_rashi, 30.0), "{day} August: {sun}°");
+    }
+}
+
+#[test]
+fn longitudes_only_when_asked() {
+    let panchang = common::panchang_json(&common::delhi("15/08/2023", "12:00"));
+    assert!(panchang["moon_longitude"].is_null());
+    assert!(panchang["sun_longitude"].is_null());
+}
@@ -0,0 +1,51 @@
+//! Checks the Nakshatra and Rashi lords over a sidereal month of the Moon, during
+//! which it passes through every Nakshatra and Rashi: Ashwini is ruled by Ketu and
+//! Mesha by Mangala, and the lords follow the Vimshottari and classical orders.
+
+use std::collections::BTreeMap;
+
+use chrono::{Duration, TimeZone, Utc};
+use panchang_rs::panchang::{Ayanamsa, calculate_panchanga_at, nakshatra_index};
+
+/// The Vimshottari lords from Ashwini, repeating every 9 Nakshatras
+const NAKSHATRA_LORD: [&str; 9] = [
+    "Ketu", "Shukra", "Surya", "Chandra", "Mangala", "Rahu", "Guru", "Shani", "Budha",
+];
+
+/// The classical lords from Mesha
+const RASHI_LORD: [&str; 12] = [
+    "Mangala", "Shukra", "Budha", "Chandra", "Surya", "Budha", "Shukra", "Mangala", "Guru",
+    "Shani", "Shani", "Guru",
+];
+
+#[test]
+fn lords_over_a_sidereal_month() {
+    let mut nakshatra_lords = BTreeMap::new();
+    let mut rashi_lords = BTreeMap::new();
+
+    // Every three hours over 28 days, less than the 20 hours a Nakshatra lasts
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    for step in 0..28 * 8 {
+        let panchanga = calculate_panchanga_at(start + Duration::hours(3 * step), Ayanamsa::Lahiri);
+        let nakshatra = nakshatra_index(&panchanga.current_nakshatra).unwrap();
+        let rashi = panchanga.current_chandra_rashi_number as usize - 1;
+        assert_eq!(
+            panchanga.current_nakshatra_lord,
+            NAKSHATRA_LORD[nakshatra % 9]
+        );
+        assert_eq!(panchanga.current_rashi_lord, RASHI_LORD[rashi]);
+        nakshatra_lords.insert(
+            panchanga.current_nakshatra,
+            panchanga.current_nakshatra_lord,
+        );
+        rashi_lords.insert(
+            panchanga.current_chandra_rashi,
+            panchanga.current_rashi_lord,
+        );
+    }
+
+    assert_eq!(nakshatra_lords.len(), 27);
+    assert_eq!(rashi_lords.len(), 12);
+    assert_eq!(nakshatra_lords["Ashwini"], "Ketu");
+    assert_eq!(rashi_lords["Mesha"], "Mangala");
+}
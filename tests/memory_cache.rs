@@ -0,0 +1,166 @@
+//! Checks the in-memory cache: a repeated request is answered from it, requests
+//! spelling the same inputs differently share its key, and a full cache evicts the
+//! least recently used response.
+
+#![cfg(feature = "server")]
+
+use actix_web::{App, test, web};
+use panchang_rs::cache::{MemoryCache, cache_key};
+use panchang_rs::config::Config;
+use panchang_rs::panchang::{
+    Location, PanchangRequest, PanchangResponse, ValidatedRequest, calculate_panchang,
+};
+use panchang_rs::server::{self, AppState};
+use serde_json::{Value, json};
+
+/// Configures a server without a database, with `MEM_CACHE_SIZE` at `size`
+fn config(size: &str) -> Config {
+    Config::load(None, |name| {
+        (name == "MEM_CACHE_SIZE").then(|| size.to_string())
+    })
+    .unwrap()
+}
+
+/// A sunrise request for a date of August 2023 in New Delhi
+fn request(day: u32) -> PanchangRequest {
+    serde_json::from_value(json!({
+        "date": format!("{:02}/08/2023", day),
+        "time": "06:00",
+        "zone": "+05:30",
+        "latitude": 28.6139,
+        "longitude": 77.2090,
+    }))
+    .unwrap()
+}
+
+/// Cache key of a request given as JSON
+fn key_of(request: Value) -> String {
+    let request: PanchangRequest = serde_json::from_value(request).unwrap();
+    cache_key(&ValidatedRequest::try_from(&request).unwrap())
+}
+
+/// Cache key of `request(day)`
+fn key(day: u32) -> String {
+    cache_key(&ValidatedRequest::try_from(&request(day)).unwrap())
+}
+
+/// The calculated Panchang of `request(day)`
+async fn response(day: u32) -> PanchangResponse {
+    calculate_panchang(&request(day)).await.unwrap()
+}
+
+/// Reads the count of `result` lookups from the Prometheus text
+fn lookups(metrics: &str, result: &str) -> u64 {
+    let series = format!(
+        "panchang_memory_cache_lookups_total{{result=\"{}\"}} ",
+        result
+    );
+    metrics
+        .lines()
+        .find_map(|line| line.strip_prefix(&series))
+        .map_or(0, |count| count.parse().unwrap())
+}
+
+#[actix_web::test]
+async fn repeated_request_is_served_from_memory() {
+    let config = config("16");
+    let state = web::Data::new(AppState::new(&config, None));
+    let app = test::init_service(
+        App::new()
+            .app_data(state.clone())
+            .app_data(server::json_config(config.max_body_bytes))
+            .configure(server::configure),
+    )
+    .await;
+    let uri =
+        "/panchang?date=15/08/2023&time=06:00&zone=%2B05:30&latitude=28.6139&longitude=77.2090";
+
+    let first: Value =
+        test::call_and_read_body_json(&app, test::TestRequest::get().uri(uri).to_request()).await;
+    assert_eq!(state.memory_cache.len(), 1);
+    let second: Value =
+        test::call_and_read_body_json(&app, test::TestRequest::get().uri(uri).to_request()).await;
+    assert_eq!(state.memory_cache.len(), 1);
+    assert_eq!(second["data"], first["data"]);
+
+    let metrics =
+        test::call_and_read_body(&app, test::TestRequest::get().uri("/metrics").to_request()).await;
+    let metrics = String::from_utf8(metrics.to_vec()).unwrap();
+    assert_eq!(lookups(&metrics, "miss"), 1);
+    assert_eq!(lookups(&metrics, "hit"), 1);
+}
+
+#[actix_web::test]
+async fn full_cache_evicts_least_recently_used() {
+    let cache = MemoryCache::new(2);
+    let keys: Vec<String> = (14..=16).map(key).collect();
+
+    cache.insert(keys[0].clone(), response(14).await);
+    cache.insert(keys[1].clone(), response(15).await);
+    // Using the 14th leaves the 15th the least recently used
+    assert!(cache.get(&keys[0]).is_some());
+    cache.insert(keys[2].clone(), response(16).await);
+
+    assert_eq!(cache.len(), 2);
+    assert!(cache.get(&keys[0]).is_some());
+    assert!(cache.get(&keys[1]).is_none());
+    assert!(cache.get(&keys[2]).is_some());
+}
+
+#[actix_web::test]
+async fn zero_size_caches_nothing() {
+    let cache = MemoryCache::new(0);
+    let key = key(15);
+
+    cache.insert(key.clone(), response(15).await);
+
+    assert!(cache.is_empty());
+    assert!(cache.get(&key).is_none());
+}
+
+#[actix_web::test]
+async fn equivalent_requests_share_a_key() {
+    let base = json!({
+        "date": "05/08/2023",
+        "time": "09:00",
+        "zone": "+05:30",
+        "latitude": 28.6139,
+        "longitude": 77.2090,
+    });
+    let with = |changes: Value| {
+        let mut request = base.clone();
+        for (name, value) in changes.as_object().unwrap() {
+            request[name] = value.clone();
+        }
+        key_of(request)
+    };
+    let key = key_of(base.clone());
+
+    assert_eq!(with(json!({ "date": "5/8/2023" })), key);
+    assert_eq!(with(json!({ "zone": "+5:30" })), key);
+    assert_eq!(with(json!({ "time": "9:00" })), key);
+    assert_ne!(with(json!({ "time": "10:00" })), key);
+    assert_ne!(with(json!({ "date": "06/08/2023" })), key);
+
+    // The time does not matter when the Panchang is taken at sunrise
+    assert_eq!(
+        with(json!({ "at_sunrise": true, "time": "06:00" })),
+        with(json!({ "at_sunrise": true, "time": "18:30" }))
+    );
+
+    // Nor does which parts of the location came from the default, unless reported
+    let delhi = Location {
+        latitude: 28.6139,
+        longitude: 77.2090,
+        timezone: None,
+    };
+    let defaulted = |verbose: bool| {
+        let request: PanchangRequest = serde_json::from_value(
+            json!({ "date": "05/08/2023", "time": "09:00", "zone": "+05:30", "verbose": verbose }),
+        )
+        .unwrap();
+        cache_key(&ValidatedRequest::try_from(&request.with_default_location(&delhi)).unwrap())
+    };
+    assert_eq!(defaulted(false), key);
+    assert_ne!(defaulted(true), with(json!({ "verbose": true })));
+}
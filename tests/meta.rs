@@ -0,0 +1,56 @@
+//! Checks the `meta` of a verbose request: the parsed inputs as given, and
+//! intermediates consistent with the date and with the elements of the response.
+
+use serde_json::{Value, json};
+
+mod common;
+
+/// The verbose Panchang of 12:30 IST in New Delhi on 15 August 2023
+fn panchang() -> Value {
+    common::panchang_json(&common::delhi_with(
+        "15/08/2023",
+        "12:30",
+        json!({ "verbose": true, "longitudes": true }),
+    ))
+}
+
+#[test]
+fn meta_holds_the_parsed_inputs() {
+    let panchang = panchang();
+    let meta = &panchang["meta"];
+    assert_eq!(
+        (&meta["day"], &meta["month"], &meta["year"]),
+        (&json!(15), &json!(8), &json!(2023))
+    );
+    assert_eq!(meta["hour"], 12.5);
+    assert_eq!(meta["zone_offset"], 5.5);
+    assert_eq!(meta["defaulted"], json!([]));
+}
+
+#[test]
+fn meta_intermediates_are_sane() {
+    let panchang = panchang();
+    let meta = &panchang["meta"];
+    let number = |key: &str| meta[key].as_f64().unwrap();
+
+    // 07:00 UT on 15 August 2023 is 8628 days and 7 hours from 1999-12-31 0h UT
+    assert!((number("days_since_j2000") - (8628.0 + 7.0 / 24.0)).abs() < 1e-6);
+
+    // Lahiri was about 24°10' in 2023, and the Sun near 22° of Simha, tropical
+    let ayanamsa = number("ayanamsa");
+    assert!((24.0..24.3).contains(&ayanamsa), "{ayanamsa}");
+    let sun = number("sun_longitude");
+    assert!((141.0..144.0).contains(&sun), "{sun}");
+
+    // A day before the new moon, the Moon is within a Tithi or two behind the Sun
+    let moon = number("moon_longitude");
+    let elongation = (moon - sun).rem_euclid(360.0);
+    assert!((336.0..348.0).contains(&elongation), "{elongation}");
+    assert_eq!(panchang["tithi"], "Chaturdashi");
+
+    // The sidereal longitudes of the response are the tropical ones less the Ayanamsa
+    for (tropical, sidereal) in [(sun, "sun_longitude"), (moon, "moon_longitude")] {
+        let sidereal = panchang[sidereal].as_f64().unwrap();
+        assert!(((tropical - ayanamsa).rem_euclid(360.0) - sidereal).abs() < 1e-5);
+    }
+}
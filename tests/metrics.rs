@@ -0,0 +1,64 @@
+//! Checks that `/metrics` counts the Panchang requests by endpoint and status, and
+//! records their latency, while requests to other paths are not counted.
+
+#![cfg(feature = "server")]
+
+use actix_web::middleware::from_fn;
+use actix_web::{App, test, web};
+use panchang_rs::config::Config;
+use panchang_rs::middleware::track_metrics;
+use panchang_rs::server::{self, AppState};
+
+/// Reads the value of the series of `metric` whose labels include all of `labels`
+fn sample(metrics: &str, metric: &str, labels: &[&str]) -> Option<f64> {
+    metrics.lines().find_map(|line| {
+        let (series, value) = line.rsplit_once(' ')?;
+        let labelled = series.strip_prefix(metric)?.starts_with('{');
+        (labelled && labels.iter().all(|label| series.contains(label)))
+            .then(|| value.parse().unwrap())
+    })
+}
+
+#[actix_web::test]
+async fn panchang_requests_are_counted() {
+    let config = Config::load(None, |_| None).unwrap();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::new(&config, None)))
+            .wrap(from_fn(track_metrics))
+            .configure(server::configure),
+    )
+    .await;
+    let get = |uri: &str| test::TestRequest::get().uri(uri).to_request();
+
+    let ok =
+        "/panchang?date=15/08/2023&time=12:00&zone=%2B05:30&latitude=28.6139&longitude=77.2090";
+    let invalid =
+        "/panchang?date=31/04/2023&time=12:00&zone=%2B05:30&latitude=28.6139&longitude=77.2090";
+    test::call_service(&app, get(ok)).await;
+    test::call_service(&app, get(ok)).await;
+    test::call_service(&app, get(invalid)).await;
+    test::call_service(&app, get("/healthz")).await;
+
+    let metrics = test::call_and_read_body(&app, get("/metrics")).await;
+    let metrics = String::from_utf8(metrics.to_vec()).unwrap();
+    let requests = |status: &str| {
+        let status = format!("status=\"{}\"", status);
+        sample(
+            &metrics,
+            "panchang_requests_total",
+            &["endpoint=\"/panchang\"", &status],
+        )
+    };
+    assert_eq!(requests("200"), Some(2.0));
+    assert_eq!(requests("422"), Some(1.0));
+    assert_eq!(
+        sample(
+            &metrics,
+            "panchang_request_duration_seconds_count",
+            &["endpoint=\"/panchang\"", "status=\"200\""]
+        ),
+        Some(2.0)
+    );
+    assert!(!metrics.contains("endpoint=\"/healthz\""));
+}
@@ -0,0 +1,80 @@
+//! Checks the Moon's illumination and phase angle at the new and full moons of
+//! August 2023, and the phase angle against the elongation it is derived from.
+
+use chrono::{DateTime, TimeZone, Utc};
+use panchang_rs::panchang::{
+    Ayanamsa, Panchanga, calculate_panchanga_at, moon_elongation, moon_phase_angle,
+};
+
+/// The Panchanga at a UTC instant, in Lahiri
+fn at(instant: DateTime<Utc>) -> Panchanga {
+    calculate_panchanga_at(instant, Ayanamsa::Lahiri)
+}
+
+#[test]
+fn dark_at_amavasya() {
+    // New moon at 09:38 UTC on 16 August 2023
+    let panchanga = at(Utc.with_ymd_and_hms(2023, 8, 16, 9, 38, 0).unwrap());
+    assert_eq!(panchanga.current_tithi, "Amavasya");
+    assert!(
+        panchanga.moon_illumination < 0.001,
+        "{}",
+        panchanga.moon_illumination
+    );
+    assert!(
+        panchanga.moon_phase_angle > 179.0,
+        "{}",
+        panchanga.moon_phase_angle
+    );
+    assert_eq!(panchanga.moon_phase, "New Moon");
+}
+
+#[test]
+fn full_at_poornima() {
+    // Full moon at 01:36 UTC on 31 August 2023
+    let panchanga = at(Utc.with_ymd_and_hms(2023, 8, 31, 1, 36, 0).unwrap());
+    assert_eq!(panchanga.current_tithi, "Poornima");
+    assert!(
+        panchanga.moon_illumination > 0.999,
+        "{}",
+        panchanga.moon_illumination
+    );
+    assert!(
+        panchanga.moon_phase_angle < 1.0,
+        "{}",
+        panchanga.moon_phase_angle
+    );
+    assert_eq!(panchanga.moon_phase, "Full Moon");
+}
+
+#[test]
+fn half_lit_at_the_quarters() {
+    // First quarter at 09:47 UTC on 24 August 2023, last quarter at 10:28 UTC on 8 August
+    for instant in [
+        Utc.with_ymd_and_hms(2023, 8, 24, 9, 47, 0).unwrap(),
+        Utc.with_ymd_and_hms(2023, 8, 8, 10, 28, 0).unwrap(),
+    ] {
+        let panchanga = at(instant);
+        assert!(
+            (panchanga.moon_illumination - 0.5).abs() < 0.01,
+            "{}: {}",
+            instant,
+            panchanga.moon_illumination
+        );
+        assert!(
+            (panchanga.moon_phase_angle - 90.0).abs() < 1.0,
+            "{}: {}",
+            instant,
+            panchanga.moon_phase_angle
+        );
+    }
+}
+
+#[test]
+fn phase_angle_is_alike_waxing_and_waning() {
+    assert_eq!(moon_elongation(100.0, 40.0), 300.0);
+    assert_eq!(moon_phase_angle(100.0, 40.0), 120.0);
+    assert_eq!(moon_phase_angle(100.0, 160.0), 120.0);
+    assert_eq!(moon_phase_angle(0.0, 0.0), 180.0);
+    assert_eq!(moon_phase_angle(10.0, 190.0), 0.0);
+}
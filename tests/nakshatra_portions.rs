@@ -0,0 +1,36 @@
+//! Checks `nakshatra_portions` against the Tyajya (Varjyam) and Amrita ghatis that
+//! Muhurta Chintamani tabulates for Ashwini and Rohini.
+//!
+//! Ashwini's Varjyam begins at ghati 50 and its Amrita Kaal at ghati 42; Rohini's at
+//! ghatis 40 and 52. Each lasts four ghatis, and a ghati is a sixtieth of the
+//! Nakshatra, so 24 minutes of a Nakshatra lasting exactly a day.
+
+use panchang_rs::panchang::nakshatra_portions;
+
+/// Asserts that two (start, end) pairs of hours agree to within a second
+fn assert_window(actual: (f64, f64), expected: (f64, f64)) {
+    let second = 1.0 / 3600.0;
+    assert!(
+        (actual.0 - expected.0).abs() < second && (actual.1 - expected.1).abs() < second,
+        "{actual:?} against {expected:?}"
+    );
+}
+
+#[test]
+fn ashwini_of_a_day() {
+    let portions = nakshatra_portions(0.0, 24.0, 0);
+
+    // Ghatis 50-54 and 42-46
+    assert_window(portions.varjyam, (20.0, 21.6));
+    assert_window(portions.amrita_kaal, (16.8, 18.4));
+}
+
+#[test]
+fn rohini_scales_with_its_length() {
+    // A Rohini of 22½ hours, from 06:00 to 04:30 the next day, makes a ghati 22½ minutes
+    let portions = nakshatra_portions(6.0, 28.5, 3);
+
+    // Ghatis 40-44 and 52-56
+    assert_window(portions.varjyam, (21.0, 22.5));
+    assert_window(portions.amrita_kaal, (25.5, 27.0));
+}
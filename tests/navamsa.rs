@@ -0,0 +1,89 @@
+//! Checks the Navamsa (D9) Rashi against the traditional counting rules: from the
+//! Rashi itself in a movable Rashi, from the ninth from it in a fixed Rashi and from
+//! the fifth in a dual Rashi.
+
+use panchang_rs::panchang::navamsa;
+use panchang_rs::{Ayanamsa, calculate_panchanga_at};
+
+/// Rashis in order from Mesha
+const RASHIS: [&str; 12] = [
+    "Mesha",
+    "Vrishabha",
+    "Mithuna",
+    "Karka",
+    "Simha",
+    "Kanya",
+    "Tula",
+    "Vrischika",
+    "Dhanu",
+    "Makara",
+    "Kumbha",
+    "Meena",
+];
+
+#[test]
+fn navamsas_follow_the_counting_rules() {
+    for (longitude, expected) in [
+        // Mesha, movable: its Navamsas start from Mesha
+        (0.0, "Mesha"),
+        (3.5, "Vrishabha"),
+        (29.9, "Dhanu"),
+        // Vrishabha, fixed: from Makara, the ninth from it
+        (30.0, "Makara"),
+        (45.0, "Vrishabha"),
+        // Mithuna, dual: from Tula, the fifth from it
+        (60.0, "Tula"),
+        (89.9, "Mithuna"),
+        // Karka, movable: from Karka
+        (90.0, "Karka"),
+        // Simha, fixed: from Mesha
+        (125.0, "Vrishabha"),
+        // Dhanu, dual: from Mesha
+        (240.0, "Mesha"),
+        // Meena, dual: from Karka, ending in Meena
+        (330.0, "Karka"),
+        (359.99, "Meena"),
+    ] {
+        assert_eq!(navamsa(longitude), expected, "{}°", longitude);
+    }
+}
+
+#[test]
+fn navamsa_agrees_with_the_rule_for_every_pada() {
+    for pada in 0..108 {
+        let rashi = pada / 9;
+        let first = match rashi % 3 {
+            0 => rashi,
+            1 => rashi + 8,
+            _ => rashi + 4,
+        };
+        let longitude = pada as f64 * 10.0 / 3.0 + 1.0;
+        assert_eq!(
+            navamsa(longitude),
+            RASHIS[(first + pada % 9) % 12],
+            "pada {} at {}°",
+            pada,
+            longitude
+        );
+    }
+}
+
+#[test]
+fn navamsa_matches_the_nakshatra_pada() {
+    // The first pada of a Nakshatra starting a Rashi is that Rashi's first Navamsa:
+    // Ashwini starts Mesha's, Magha Simha's and Mula Dhanu's, all in Mesha
+    let mut instant = "2024-01-01T00:00:00Z".parse().unwrap();
+    for _ in 0..120 {
+        let panchanga = calculate_panchanga_at(instant, Ayanamsa::Lahiri);
+        let nakshatra = panchanga.current_nakshatra.as_str();
+        if ["Ashwini", "Magha", "Mula"].contains(&nakshatra) {
+            let expected = RASHIS[panchanga.current_nakshatra_pada as usize - 1];
+            assert_eq!(
+                panchanga.current_navamsa_rashi, expected,
+                "{} pada {} at {}",
+                nakshatra, panchanga.current_nakshatra_pada, instant
+            );
+        }
+        instant += chrono::Duration::hours(6);
+    }
+}
@@ -0,0 +1,101 @@
+//! Checks `GET /panchang/range.ndjson` by reading the whole stream of a month and
+//! counting one Panchang line per day, that a range over `MAX_RANGE_DAYS` is refused
+//! before anything is streamed, and that a day over the calculation budget ends the
+//! stream with the error envelope.
+
+#![cfg(feature = "server")]
+
+use actix_web::http::{StatusCode, header};
+use actix_web::{App, test, web};
+use panchang_rs::config::Config;
+use panchang_rs::server::{self, AppState};
+use serde_json::Value;
+use std::time::Duration;
+
+/// Streams a range of days in New Delhi from a server without a database
+async fn stream(start_date: &str, end_date: &str) -> (StatusCode, Option<String>, String) {
+    let config = Config::load(None, |_| None).unwrap();
+    stream_from(AppState::new(&config, None), start_date, end_date).await
+}
+
+/// Streams a range of days in New Delhi from a server with `state`
+async fn stream_from(
+    state: AppState,
+    start_date: &str,
+    end_date: &str,
+) -> (StatusCode, Option<String>, String) {
+    let config = Config::load(None, |_| None).unwrap();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(state))
+            .app_data(server::json_config(config.max_body_bytes))
+            .configure(server::configure),
+    )
+    .await;
+    let uri = format!(
+        "/panchang/range.ndjson?start_date={}&end_date={}&time=06:00&zone=%2B05:30&latitude=28.6139&longitude=77.2090",
+        start_date, end_date
+    );
+    let response = test::call_service(&app, test::TestRequest::get().uri(&uri).to_request()).await;
+
+    let status = response.status();
+    let content_type = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .map(|value| value.to_str().unwrap().to_string());
+    let body = test::read_body(response).await;
+    (
+        status,
+        content_type,
+        String::from_utf8(body.to_vec()).unwrap(),
+    )
+}
+
+#[actix_web::test]
+async fn streams_one_line_per_day() {
+    let (status, content_type, body) = stream("01/08/2023", "31/08/2023").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type.as_deref(), Some("application/x-ndjson"));
+    assert!(body.ends_with('\n'));
+    let days: Vec<Value> = body
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(days.len(), 31);
+    for (day, line) in days.iter().enumerate() {
+        assert_eq!(line["date"], format!("{:02}/08/2023", day + 1));
+        assert!(line["tithi"].is_string(), "{}: Tithi", line["date"]);
+    }
+}
+
+#[actix_web::test]
+async fn range_over_the_cap_is_refused_before_streaming() {
+    let (status, content_type, body) = stream("01/01/2023", "31/12/2024").await;
+
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(content_type.as_deref(), Some("application/json"));
+    let body: Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(body["status"], "error");
+    assert_eq!(body["code"], "INVALID_DATE_RANGE");
+}
+
+#[actix_web::test]
+async fn day_over_the_budget_ends_the_stream_with_the_error() {
+    let config = Config::load(None, |_| None).unwrap();
+    let mut state = AppState::new(&config, None);
+    state.calculation_timeout = Duration::ZERO;
+    let (status, content_type, body) = stream_from(state, "01/08/2023", "31/08/2023").await;
+
+    // The range itself is valid, so the stream has started when the first day times out
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type.as_deref(), Some("application/x-ndjson"));
+    let lines: Vec<Value> = body
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 1, "{}", body);
+    assert_eq!(lines[0]["status"], "error");
+    assert_eq!(lines[0]["statusCode"], 504);
+    assert_eq!(lines[0]["code"], "CALCULATION_TIMEOUT");
+}
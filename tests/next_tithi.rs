@@ -0,0 +1,75 @@
+//! Checks the search for the next date a Tithi prevails at sunrise in New Delhi,
+//! including a Tithi that is kshaya, falling between two sunrises, in the first month.
+
+use panchang_rs::panchang::{NextTithiRequest, calculate_next_tithi};
+use serde_json::{Value, json};
+
+mod common;
+
+/// Searches for `tithi` from `from_date` in New Delhi, as JSON or the error's code
+fn next(from_date: &str, tithi: &str, paksha: Option<&str>) -> Result<Value, String> {
+    let request: NextTithiRequest = serde_json::from_value(json!({
+        "from_date": from_date,
+        "tithi": tithi,
+        "paksha": paksha,
+        "time": "06:00",
+        "timezone": "Asia/Kolkata",
+        "latitude": 28.6139,
+        "longitude": 77.2090,
+    }))
+    .unwrap();
+    common::block_on(calculate_next_tithi(&request))
+        .map(|occurrence| serde_json::to_value(occurrence).unwrap())
+        .map_err(|err| err.code().to_string())
+}
+
+#[test]
+fn next_poornima_from_mid_month() {
+    // Shravana Poornima ran from 10:59 on 30 August 2023 to 07:06 on the 31st, so it
+    // prevailed at the sunrise of the 31st
+    let poornima = next("20/08/2023", "Poornima", None).unwrap();
+    assert_eq!(poornima["date"], "31/08/2023");
+    assert_eq!(poornima["paksha"], "Shukla");
+    assert_eq!(poornima["sunrise"], "05:59");
+    assert_eq!(poornima["tithi_start"], "2023-08-30T10:59:26+05:30");
+    assert_eq!(poornima["tithi_end"], "2023-08-31T07:06:10+05:30");
+}
+
+#[test]
+fn tithi_at_the_first_sunrise_is_found_that_day() {
+    let poornima = next("31/08/2023", "Poornima", None).unwrap();
+    assert_eq!(poornima["date"], "31/08/2023");
+}
+
+#[test]
+fn kshaya_tithi_is_found_the_next_month() {
+    // Shukla Chaturdashi began after sunrise on 27 October 2023 and ended before the
+    // next, so the search carries on to November's, which held the 26th's sunrise
+    let chaturdashi = next("20/10/2023", "Chaturdashi", Some("Shukla")).unwrap();
+    assert_eq!(chaturdashi["date"], "26/11/2023");
+    assert_eq!(chaturdashi["tithi_start"], "2023-11-25T17:23:18+05:30");
+    assert_eq!(chaturdashi["tithi_end"], "2023-11-26T15:54:32+05:30");
+}
+
+#[test]
+fn either_paksha_matches_without_one() {
+    // Krishna Chaturdashi of 12 November comes before November's Shukla one
+    let chaturdashi = next("20/10/2023", "Chaturdashi", None).unwrap();
+    assert_eq!(chaturdashi["paksha"], "Krishna");
+}
+
+#[test]
+fn rejects_unknown_tithis_and_pakshas() {
+    assert_eq!(
+        next("20/08/2023", "Navami Tritiya", None).unwrap_err(),
+        "INVALID_TITHI"
+    );
+    assert_eq!(
+        next("20/08/2023", "Ekadashi", Some("Dark")).unwrap_err(),
+        "INVALID_TITHI"
+    );
+    assert_eq!(
+        next("20/08/2023", "Amavasya", Some("Shukla")).unwrap_err(),
+        "INVALID_TITHI"
+    );
+}
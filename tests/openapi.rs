@@ -0,0 +1,35 @@
+//! Checks that builds with the `openapi` feature serve the OpenAPI contract, and
+//! that it describes the Panchang endpoints.
+
+#![cfg(feature = "openapi")]
+
+use actix_web::http::StatusCode;
+use actix_web::{App, test, web};
+use panchang_rs::config::Config;
+use panchang_rs::server::{self, AppState};
+use serde_json::Value;
+
+#[actix_web::test]
+async fn contract_lists_the_panchang_path() {
+    let config = Config::load(None, |_| None).unwrap();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::new(&config, None)))
+            .configure(server::configure),
+    )
+    .await;
+    let response = test::call_service(
+        &app,
+        test::TestRequest::get()
+            .uri("/api-docs/openapi.json")
+            .to_request(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let contract: Value = test::read_body_json(response).await;
+
+    let panchang = &contract["paths"]["/panchang"];
+    assert!(panchang["get"].is_object(), "{}", panchang);
+    assert!(panchang["post"].is_object(), "{}", panchang);
+    assert!(contract["paths"]["/panchang/range"]["post"].is_object());
+}
@@ -0,0 +1,47 @@
+//! Checks the Paksha and the Masa it belongs to on either side of a Poornima and an
+//! Amavasya, in the Amanta and the Purnimanta reckonings.
+//!
+//! The instants are in April and May 2024 in IST, at least three hours from the
+//! full moon of 24 April (05:19) and the new moon of 8 May (08:52). Amanta months end
+//! at Amavasya, so the Krishna Paksha after the Chaitra Poornima is still Chaitra; in
+//! the Purnimanta reckoning it already opens Vaishakha.
+
+use serde_json::{Value, json};
+
+mod common;
+
+/// Calculates the Panchang for an instant in New Delhi, as JSON
+fn panchang(date: &str, time: &str, month_system: &str) -> Value {
+    common::panchang_json(&common::delhi_with(
+        date,
+        time,
+        json!({ "month_system": month_system }),
+    ))
+}
+
+/// Asserts the Paksha, its index and the Masa in each reckoning at an instant
+fn assert_paksha(date: &str, time: &str, name: &str, index: u64, amanta: &str, purnimanta: &str) {
+    for (month_system, masa) in [("amanta", amanta), ("purnimanta", purnimanta)] {
+        let response = panchang(date, time, month_system);
+        let label = format!("{} {} {}", date, time, month_system);
+        assert_eq!(response["paksha"]["name"], name, "{}: Paksha", label);
+        assert_eq!(
+            response["paksha"]["index"], index,
+            "{}: index in Paksha",
+            label
+        );
+        assert_eq!(response["masa"], masa, "{}: Masa", label);
+    }
+}
+
+#[test]
+fn poornima_ends_the_purnimanta_month() {
+    assert_paksha("23/04/2024", "12:00", "Shukla", 15, "Chaitra", "Chaitra");
+    assert_paksha("24/04/2024", "12:00", "Krishna", 1, "Chaitra", "Vaishakha");
+}
+
+#[test]
+fn amavasya_ends_the_amanta_month() {
+    assert_paksha("08/05/2024", "05:30", "Krishna", 15, "Chaitra", "Vaishakha");
+    assert_paksha("08/05/2024", "12:00", "Shukla", 1, "Vaishakha", "Vaishakha");
+}
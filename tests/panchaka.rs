@@ -0,0 +1,55 @@
+//! Checks Panchaka, from the second half of Dhanishta through Revati, both by its
+//! lookup and in the Panchang of dates of August 2023 in New Delhi.
+
+use panchang_rs::panchang::panchaka;
+use serde_json::Value;
+
+mod common;
+
+/// Indexes of the Nakshatras at the edges of Panchaka
+const ASHWINI: usize = 0;
+const SRAVANA: usize = 21;
+const DHANISHTA: usize = 22;
+const REVATI: usize = 26;
+
+/// Weekday indexes, from Sunday
+const SUNDAY: usize = 0;
+const SATURDAY: usize = 6;
+
+/// The Panchang at noon IST in New Delhi on `date`
+fn panchang(date: &str) -> Value {
+    common::panchang_json(&common::delhi(date, "12:00"))
+}
+
+#[test]
+fn revati_is_panchaka_of_the_weekday() {
+    assert_eq!(panchaka(REVATI, 4, SUNDAY), Some("Roga Panchaka"));
+    assert_eq!(panchaka(REVATI, 1, SATURDAY), Some("Mrityu Panchaka"));
+    assert_eq!(panchaka(REVATI, 1, 3), Some("Panchaka"));
+}
+
+#[test]
+fn ashwini_is_not_panchaka() {
+    for weekday in 0..7 {
+        assert_eq!(panchaka(ASHWINI, 1, weekday), None);
+    }
+}
+
+#[test]
+fn panchaka_begins_halfway_through_dhanishta() {
+    assert_eq!(panchaka(SRAVANA, 4, SUNDAY), None);
+    assert_eq!(panchaka(DHANISHTA, 2, SUNDAY), None);
+    assert_eq!(panchaka(DHANISHTA, 3, SUNDAY), Some("Roga Panchaka"));
+}
+
+#[test]
+fn response_names_the_panchaka() {
+    // The Moon was in Revati at noon on Sunday 6 August and in Ashwini on Monday 7th
+    let revati = panchang("06/08/2023");
+    assert_eq!(revati["nakshatra"], "Revathi");
+    assert_eq!(revati["panchaka"], "Roga Panchaka");
+
+    let ashwini = panchang("07/08/2023");
+    assert_eq!(ashwini["nakshatra"], "Ashwini");
+    assert_eq!(ashwini["panchaka"], Value::Null);
+}
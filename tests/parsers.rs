@@ -0,0 +1,129 @@
+//! Regression tests for inputs found by the `parsers` fuzz target in `fuzz/`, and
+//! the edge cases around them. Every input must come back as an error, not a panic.
+//! Month lengths, leap years and signed, fractional offsets are checked as well.
+
+use chrono::NaiveDate;
+use panchang_rs::{PanchangError, parse_clock_time, parse_date, parse_tz_offset};
+
+#[test]
+fn rejects_years_without_a_calendar_date() {
+    // Found by the fuzzer: accepted, then panicked converting to a NaiveDate
+    assert!(matches!(
+        parse_date("8/8/999999"),
+        Err(PanchangError::DateOutOfRange(_))
+    ));
+    assert!(matches!(
+        parse_date("01/01/0"),
+        Err(PanchangError::DateOutOfRange(_))
+    ));
+    assert!(matches!(
+        parse_date("01/01/99999999999"),
+        Err(PanchangError::InvalidDateFormat(_))
+    ));
+
+    let (day, month, year) = parse_date("31/12/9999").unwrap();
+    assert!(NaiveDate::from_ymd_opt(year, month as u32, day as u32).is_some());
+}
+
+#[test]
+fn rejects_malformed_fields() {
+    for date in [
+        "",
+        "//",
+        "15/08",
+        "15/08/2023/1",
+        "+15/08/2023",
+        "15/-8/2023",
+        "١٥/٠٨/٢٠٢٣",
+        "15/08/2023x",
+    ] {
+        assert!(parse_date(date).is_err(), "{:?}", date);
+    }
+    for time in [
+        "",
+        ":",
+        "12",
+        "12:",
+        "+1:30",
+        "١٢:٣٠",
+        "99999999999:00",
+        "12:30:00",
+    ] {
+        assert!(parse_clock_time(time).is_err(), "{:?}", time);
+    }
+    for zone in [
+        "",
+        "+",
+        "-:",
+        "+-5",
+        "+05:",
+        "٠٥:٣٠",
+        "+99999999999",
+        "+14:01",
+        "-12:30",
+    ] {
+        assert!(parse_tz_offset(zone).is_err(), "{:?}", zone);
+    }
+}
+
+#[test]
+fn ignores_surrounding_whitespace() {
+    assert_eq!(parse_date(" 15/08/2023\n").unwrap(), (15, 8, 2023));
+    assert_eq!(parse_clock_time("\t12:30 ").unwrap(), (12.0, 30));
+    assert_eq!(parse_tz_offset(" +05:30 ").unwrap(), 5.5);
+}
+
+#[test]
+fn accepts_each_month_up_to_its_length() {
+    let lengths = [
+        (1, 31),
+        (2, 28),
+        (3, 31),
+        (4, 30),
+        (5, 31),
+        (6, 30),
+        (7, 31),
+        (8, 31),
+        (9, 30),
+        (10, 31),
+        (11, 30),
+        (12, 31),
+    ];
+    for (month, length) in lengths {
+        let last = format!("{}/{}/2023", length, month);
+        assert_eq!(parse_date(&last).unwrap(), (length, month, 2023), "{last}");
+        let past = format!("{}/{}/2023", length + 1, month);
+        assert!(
+            matches!(parse_date(&past), Err(PanchangError::DateOutOfRange(_))),
+            "{past}"
+        );
+    }
+}
+
+#[test]
+fn century_years_are_leap_only_every_400() {
+    for (date, leap) in [
+        ("29/02/1900", false),
+        ("29/02/2000", true),
+        ("29/02/2024", true),
+        ("29/02/2100", false),
+    ] {
+        assert_eq!(parse_date(date).is_ok(), leap, "{date}");
+    }
+}
+
+#[test]
+fn reads_fractional_and_signed_offsets() {
+    for (zone, hours) in [
+        ("-05:30", -5.5),
+        ("+05:45", 5.75),
+        ("-00:30", -0.5),
+        ("+00:30", 0.5),
+        ("Z", 0.0),
+        ("z", 0.0),
+        ("+14:00", 14.0),
+        ("-12", -12.0),
+    ] {
+        assert_eq!(parse_tz_offset(zone).unwrap(), hours, "{zone}");
+    }
+}
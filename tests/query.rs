@@ -0,0 +1,113 @@
+//! Checks that `GET /panchang` answers a query string with the envelope `POST
+//! /panchang` gives for the same request, apart from its timestamp and request ID.
+
+#![cfg(feature = "server")]
+
+use actix_web::http::StatusCode;
+use actix_web::{App, test, web};
+use panchang_rs::config::Config;
+use panchang_rs::server::{self, AppState};
+use serde_json::{Value, json};
+
+/// Removes the keys of an envelope that differ between any two responses
+fn without_identity(mut envelope: Value) -> Value {
+    let envelope_object = envelope.as_object_mut().unwrap();
+    assert!(envelope_object.remove("timestamp").is_some());
+    assert!(envelope_object.remove("requestId").is_some());
+    envelope
+}
+
+#[actix_web::test]
+async fn get_matches_post() {
+    let config = Config::load(None, |_| None).unwrap();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::new(&config, None)))
+            .app_data(server::json_config(config.max_body_bytes))
+            .configure(server::configure),
+    )
+    .await;
+
+    let get = test::TestRequest::get()
+        .uri("/panchang?date=15/08/2023&time=12:00&zone=%2B05:30&latitude=28.6139&longitude=77.2090&ayanamsa=raman")
+        .to_request();
+    let post = test::TestRequest::post()
+        .uri("/panchang")
+        .set_json(json!({
+            "date": "15/08/2023",
+            "time": "12:00",
+            "zone": "+05:30",
+            "latitude": 28.6139,
+            "longitude": 77.2090,
+            "ayanamsa": "raman",
+        }))
+        .to_request();
+
+    let get = test::call_service(&app, get).await;
+    assert_eq!(get.status(), StatusCode::OK);
+    let get: Value = test::read_body_json(get).await;
+    let post = test::call_service(&app, post).await;
+    assert_eq!(post.status(), StatusCode::OK);
+    let post: Value = test::read_body_json(post).await;
+
+    assert_eq!(get["data"]["tithi"], "Chaturdashi");
+    assert_eq!(without_identity(get), without_identity(post));
+}
+
+#[actix_web::test]
+async fn options_in_the_query_match_the_body() {
+    let config = Config::load(None, |_| None).unwrap();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::new(&config, None)))
+            .app_data(server::json_config(config.max_body_bytes))
+            .configure(server::configure),
+    )
+    .await;
+
+    // Flags, counts and the calendar reach a flattened struct as strings
+    let get = test::TestRequest::get()
+        .uri("/panchang?date=15/08/2023&time=12:00&zone=%2B05:30&latitude=28.6139&longitude=77.2090&calendar=julian&gregorian_cutover=14/09/1752&nakshatra_system=28&at_sunrise=true&verbose=true&longitudes=true")
+        .to_request();
+    let post = test::TestRequest::post()
+        .uri("/panchang")
+        .set_json(json!({
+            "date": "15/08/2023",
+            "time": "12:00",
+            "zone": "+05:30",
+            "latitude": 28.6139,
+            "longitude": 77.2090,
+            "calendar": "julian",
+            "gregorian_cutover": "14/09/1752",
+            "nakshatra_system": 28,
+            "at_sunrise": true,
+            "verbose": true,
+            "longitudes": true,
+        }))
+        .to_request();
+
+    let get = test::call_service(&app, get).await;
+    assert_eq!(get.status(), StatusCode::OK);
+    let get: Value = test::read_body_json(get).await;
+    let post = test::call_service(&app, post).await;
+    assert_eq!(post.status(), StatusCode::OK);
+    let post: Value = test::read_body_json(post).await;
+
+    assert!(get["data"]["meta"]["hour"].as_f64().unwrap() < 6.0);
+    assert!(get["data"]["sun_longitude"].is_number());
+    assert_eq!(without_identity(get), without_identity(post));
+
+    for query in [
+        "verbose=maybe",
+        "nakshatra_system=29",
+        "nakshatra_system=many",
+    ] {
+        let uri = format!(
+            "/panchang?date=15/08/2023&time=12:00&zone=%2B05:30&latitude=28.6139&longitude=77.2090&{}",
+            query
+        );
+        let response =
+            test::call_service(&app, test::TestRequest::get().uri(&uri).to_request()).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{}", query);
+    }
+}
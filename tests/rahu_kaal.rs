@@ -0,0 +1,53 @@
+//! Checks `inauspicious_periods` against the published weekday table of Rahu Kaal,
+//! Yamaganda and Gulika Kaal for a day from 06:00 to 18:00, and against the bands
+//! that the NOAA sunrise and sunset give in New Delhi.
+
+use panchang_rs::panchang::{inauspicious_periods, sunrise_sunset};
+
+/// Rahu Kaal, Yamaganda and Gulika Kaal from Sunday, as start hours of their 1½-hour bands
+const TABLE: [(f64, f64, f64); 7] = [
+    (16.5, 12.0, 15.0),
+    (7.5, 10.5, 13.5),
+    (15.0, 9.0, 12.0),
+    (12.0, 7.5, 10.5),
+    (13.5, 6.0, 9.0),
+    (10.5, 15.0, 7.5),
+    (9.0, 13.5, 6.0),
+];
+
+#[test]
+fn bands_of_a_six_to_six_day() {
+    for (weekday, (rahu_kaal, yamaganda, gulika)) in TABLE.into_iter().enumerate() {
+        let periods = inauspicious_periods(6.0, 18.0, weekday);
+        assert_eq!(
+            periods.rahu_kaal,
+            (rahu_kaal, rahu_kaal + 1.5),
+            "weekday {weekday}"
+        );
+        assert_eq!(
+            periods.yamaganda,
+            (yamaganda, yamaganda + 1.5),
+            "weekday {weekday}"
+        );
+        assert_eq!(periods.gulika, (gulika, gulika + 1.5), "weekday {weekday}");
+    }
+}
+
+#[test]
+fn sunday_rahu_kaal_ends_at_sunset() {
+    let periods = inauspicious_periods(6.25, 18.75, 0);
+    assert_eq!(periods.rahu_kaal, (17.1875, 18.75));
+}
+
+#[test]
+fn delhi_on_independence_day_2023() {
+    // NOAA gives sunrise at 05:50 and sunset at 19:01, so the Tuesday's Rahu Kaal,
+    // the seventh eighth of the daytime, runs from 15:43 to 17:22, to within a minute
+    let (sunrise, sunset) = sunrise_sunset(15, 8, 2023, 28.6139, 77.2090, 5.5).unwrap();
+    let periods = inauspicious_periods(sunrise, sunset, 2);
+
+    let minute = 1.0 / 60.0;
+    let (start, end) = periods.rahu_kaal;
+    assert!((start - (15.0 + 43.0 / 60.0)).abs() <= minute, "{start}");
+    assert!((end - (17.0 + 22.0 / 60.0)).abs() <= minute, "{end}");
+}
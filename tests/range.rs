@@ -0,0 +1,66 @@
+//! Checks `/panchang/range` over a week: one day per date in order, with the Tithi
+//! at the same time each day advancing by about one a day.
+
+#![cfg(feature = "server")]
+
+use actix_web::http::StatusCode;
+use actix_web::{App, test, web};
+use panchang_rs::config::Config;
+use panchang_rs::server::{self, AppState};
+use serde_json::{Value, json};
+
+/// Number (1-30) of the Tithi of a day, from its position in its Paksha
+fn tithi_number(day: &Value) -> u64 {
+    let paksha = &day["paksha"];
+    let index = paksha["index"].as_u64().unwrap();
+    match paksha["name"].as_str().unwrap() {
+        "Krishna" => index + 15,
+        _ => index,
+    }
+}
+
+#[actix_web::test]
+async fn week_of_tithis_advances() {
+    let config = Config::load(None, |_| None).unwrap();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::new(&config, None)))
+            .app_data(server::json_config(config.max_body_bytes))
+            .configure(server::configure),
+    )
+    .await;
+    let request = test::TestRequest::post()
+        .uri("/panchang/range")
+        .set_json(json!({
+            "start_date": "12/08/2023",
+            "end_date": "18/08/2023",
+            "time": "06:00",
+            "zone": "+05:30",
+            "latitude": 28.6139,
+            "longitude": 77.2090,
+        }))
+        .to_request();
+    let response = test::call_service(&app, request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: Value = test::read_body_json(response).await;
+
+    let days = body["data"].as_array().unwrap();
+    assert_eq!(days.len(), 7);
+    for (day, date) in days.iter().zip(12..) {
+        assert_eq!(day["date"], format!("{}/08/2023", date));
+    }
+
+    // A Tithi lasts between about 20 and 27 hours, so a day later it has moved on
+    // by one, or rarely by none or two; across the new moon of the 16th it wraps
+    let numbers: Vec<u64> = days.iter().map(tithi_number).collect();
+    for pair in numbers.windows(2) {
+        let step = (pair[1] + 30 - pair[0]) % 30;
+        assert!((0..=2).contains(&step), "{numbers:?}");
+    }
+    let advance: u64 = numbers
+        .windows(2)
+        .map(|pair| (pair[1] + 30 - pair[0]) % 30)
+        .sum();
+    assert!((5..=7).contains(&advance), "{numbers:?}");
+    assert!(numbers.contains(&30), "{numbers:?}");
+}
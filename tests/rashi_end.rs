@@ -0,0 +1,33 @@
+//! Checks when the Moon leaves its Rashi, across the wrap from Meena back to Mesha.
+//!
+//! The Moon entered Mesha on 12 March 2024 at 20:29 IST in the Lahiri Ayanamsa, so
+//! it is in Meena at 20:28 and in Mesha at 20:31 that evening.
+
+use serde_json::Value;
+
+mod common;
+
+/// Calculates the Panchang for an instant in New Delhi, as JSON
+fn panchang(date: &str, time: &str) -> Value {
+    common::panchang_json(&common::delhi(date, time))
+}
+
+#[test]
+fn meena_ends_in_mesha() {
+    let response = panchang("12/03/2024", "12:00");
+    assert_eq!(response["rashi"], "Meena");
+    assert_eq!(response["rashi_end"], "2024-03-12T20:29:24+05:30");
+    assert_eq!(response["next_rashi"], "Mesha");
+}
+
+#[test]
+fn rashi_changes_at_the_end() {
+    let before = panchang("12/03/2024", "20:28");
+    assert_eq!(before["rashi"], "Meena");
+    assert_eq!(before["rashi_end"], "2024-03-12T20:29:24+05:30");
+
+    let after = panchang("12/03/2024", "20:31");
+    assert_eq!(after["rashi"], "Mesha");
+    assert_eq!(after["next_rashi"], "Vrishabha");
+    assert_eq!(after["rashi_end"], "2024-03-14T22:39:43+05:30");
+}
@@ -0,0 +1,70 @@
+//! Checks that a client exceeding `RATE_LIMIT_PER_MIN` is answered with a 429 in the
+//! API envelope and a `Retry-After` header, while other clients and the health
+//! probes are still served.
+
+#![cfg(feature = "server")]
+
+use actix_web::http::{StatusCode, header};
+use actix_web::middleware::from_fn;
+use actix_web::{App, test, web};
+use panchang_rs::config::Config;
+use panchang_rs::middleware::{RateLimiter, rate_limit, request_id};
+use panchang_rs::server::{self, AppState};
+use serde_json::Value;
+
+const PANCHANG: &str =
+    "/panchang?date=15/08/2023&time=12:00&zone=%2B05:30&latitude=28.6139&longitude=77.2090";
+
+/// A GET of `uri` from the client at `ip`
+fn from(ip: &str, uri: &str) -> test::TestRequest {
+    test::TestRequest::get()
+        .uri(uri)
+        .peer_addr(format!("{}:40000", ip).parse().unwrap())
+}
+
+#[actix_web::test]
+async fn client_over_the_limit_gets_429() {
+    let config = Config::load(None, |_| None).unwrap();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::new(&config, None)))
+            .app_data(web::Data::new(RateLimiter::new(2)))
+            .wrap(from_fn(rate_limit))
+            .wrap(from_fn(request_id))
+            .configure(server::configure),
+    )
+    .await;
+
+    for _ in 0..2 {
+        let response = test::call_service(&app, from("203.0.113.7", PANCHANG).to_request()).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    let limited = test::call_service(&app, from("203.0.113.7", PANCHANG).to_request()).await;
+    assert_eq!(limited.status(), StatusCode::TOO_MANY_REQUESTS);
+    // Two a minute refill a token every 30 seconds
+    let retry_after: u64 = limited
+        .headers()
+        .get(header::RETRY_AFTER)
+        .expect("429 has a Retry-After")
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(
+        (1..=30).contains(&retry_after),
+        "Retry-After: {}",
+        retry_after
+    );
+    let body: Value = test::read_body_json(limited).await;
+    assert_eq!(body["status"], "error");
+    assert_eq!(body["statusCode"], 429);
+    assert_eq!(body["code"], "RATE_LIMITED");
+    assert!(body["requestId"].is_string());
+
+    // Other clients have their own bucket, and the probes are never limited
+    let other = test::call_service(&app, from("203.0.113.8", PANCHANG).to_request()).await;
+    assert_eq!(other.status(), StatusCode::OK);
+    let probe = test::call_service(&app, from("203.0.113.7", "/healthz").to_request()).await;
+    assert_eq!(probe.status(), StatusCode::OK);
+}
@@ -0,0 +1,101 @@
+//! Checks `calculate_panchanga` against a reference dataset of 35 festival instants
+//! from 2021 to 2025, at New Delhi.
+//!
+//! The expected elements in `tests/data/reference.json` are transcribed from the
+//! New Delhi pages of Drik Panchang, and each row's `source` quotes the published
+//! times it rests on: the start and end of the Tithi, and where a row states them,
+//! of the Rohini Nakshatra or the Bhadra (Visti) Karana. A row states only the limbs
+//! its source gives, so the Yoga and Rashi are rarely checked here.
+//!
+//! `tests/data/cross_check_reference.py` optionally recomputes the stated limbs with
+//! the Sun and Moon of Meeus' Astronomical Algorithms, to catch a mistyped row; it
+//! does not produce the expected values.
+//!
+//! # Tolerance
+//!
+//! Every instant lies at least an hour inside the published times of what its row
+//! states, far more than the minutes the crate's simplified theory may shift a
+//! boundary by, so each stated limb must match exactly.
+
+use panchang_rs::panchang::panchanga_diff;
+use panchang_rs::{Ayanamsa, calculate_panchanga, parse_clock_time, parse_date, parse_tz_offset};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct Reference {
+    date: String,
+    time: String,
+    zone: String,
+    /// Position (1-30) of the Tithi in the lunar month
+    tithi_number: u8,
+    tithi: String,
+    #[serde(default)]
+    nakshatra: Option<String>,
+    #[serde(default)]
+    yoga: Option<String>,
+    #[serde(default)]
+    karana: Option<String>,
+    #[serde(default)]
+    rashi: Option<String>,
+    /// What the instant was picked for
+    note: String,
+    /// Published almanac and the times it gives for the stated limbs
+    source: String,
+}
+
+#[test]
+fn matches_reference_dataset() {
+    let references: Vec<Reference> = serde_json::from_str(include_str!("data/reference.json"))
+        .expect("reference dataset is valid JSON");
+    assert!(references.len() >= 30);
+
+    for reference in &references {
+        let label = format!(
+            "{} {} {} ({}; {})",
+            reference.date, reference.time, reference.zone, reference.note, reference.source
+        );
+
+        let (day, month, year) = parse_date(&reference.date).unwrap();
+        let (hour, _) = parse_clock_time(&reference.time).unwrap();
+        let zone = parse_tz_offset(&reference.zone).unwrap();
+        let panchanga = calculate_panchanga(day, month, year, hour, zone, Ayanamsa::Lahiri);
+
+        assert_eq!(
+            panchanga.current_tithi_number, reference.tithi_number,
+            "{}: Tithi {}, expected {} ({})",
+            label, panchanga.current_tithi, reference.tithi_number, reference.tithi
+        );
+
+        // The stated limbs, the others left as calculated
+        let mut expected = panchanga.clone();
+        expected.current_tithi = reference.tithi.clone();
+        let stated = [
+            (&mut expected.current_nakshatra, &reference.nakshatra),
+            (&mut expected.current_yoga, &reference.yoga),
+            (&mut expected.current_karana, &reference.karana),
+            (&mut expected.current_chandra_rashi, &reference.rashi),
+        ];
+        for (limb, value) in stated {
+            if let Some(value) = value {
+                *limb = value.clone();
+            }
+        }
+        let differences: Vec<String> = panchanga_diff(&panchanga, &expected)
+            .into_iter()
+            .map(|field| {
+                format!(
+                    "{} differs: {} vs {}",
+                    field,
+                    panchanga.field(field).unwrap(),
+                    expected.field(field).unwrap()
+                )
+            })
+            .collect();
+        assert!(
+            differences.is_empty(),
+            "{}: {}",
+            label,
+            differences.join(", ")
+        );
+    }
+}
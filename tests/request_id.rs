@@ -0,0 +1,99 @@
+//! Checks that every response carries a request id, generated unless the client
+//! supplied one in `X-Request-Id`.
+
+#![cfg(feature = "server")]
+
+use actix_web::body::MessageBody;
+use actix_web::dev::ServiceResponse;
+use actix_web::middleware::from_fn;
+use actix_web::{App, test};
+use panchang_rs::middleware::{REQUEST_ID_HEADER, request_id};
+use panchang_rs::server;
+use serde_json::Value;
+use uuid::Uuid;
+
+/// Reads the id from the response header, checking the envelope reports the same one
+async fn id_of(response: ServiceResponse<impl MessageBody>) -> String {
+    let header = response
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .expect("response has a request id")
+        .to_str()
+        .unwrap()
+        .to_string();
+    let body: Value = test::read_body_json(response).await;
+    assert_eq!(body["requestId"], header.as_str());
+    header
+}
+
+#[actix_web::test]
+async fn requests_get_distinct_ids() {
+    let app = test::init_service(
+        App::new()
+            .wrap(from_fn(request_id))
+            .configure(server::configure),
+    )
+    .await;
+
+    let first = id_of(
+        test::call_service(&app, test::TestRequest::get().uri("/healthz").to_request()).await,
+    )
+    .await;
+    let second = id_of(
+        test::call_service(&app, test::TestRequest::get().uri("/healthz").to_request()).await,
+    )
+    .await;
+
+    assert_ne!(first, second);
+    assert!(Uuid::parse_str(&first).is_ok());
+    assert!(Uuid::parse_str(&second).is_ok());
+}
+
+#[actix_web::test]
+async fn supplied_id_is_honoured() {
+    let app = test::init_service(
+        App::new()
+            .wrap(from_fn(request_id))
+            .configure(server::configure),
+    )
+    .await;
+
+    let request = test::TestRequest::get()
+        .uri("/healthz")
+        .insert_header((REQUEST_ID_HEADER, "trace-123"))
+        .to_request();
+    assert_eq!(
+        id_of(test::call_service(&app, request).await).await,
+        "trace-123"
+    );
+
+    // Errors carry the supplied id too
+    let request = test::TestRequest::get()
+        .uri("/no-such-path")
+        .insert_header((REQUEST_ID_HEADER, "trace-404"))
+        .to_request();
+    assert_eq!(
+        id_of(test::call_service(&app, request).await).await,
+        "trace-404"
+    );
+}
+
+#[actix_web::test]
+async fn unusable_ids_are_replaced() {
+    let app = test::init_service(
+        App::new()
+            .wrap(from_fn(request_id))
+            .configure(server::configure),
+    )
+    .await;
+
+    let too_long = "a".repeat(129);
+    for supplied in ["", "   ", too_long.as_str()] {
+        let request = test::TestRequest::get()
+            .uri("/healthz")
+            .insert_header((REQUEST_ID_HEADER, supplied))
+            .to_request();
+        let id = id_of(test::call_service(&app, request).await).await;
+        assert!(Uuid::parse_str(&id).is_ok(), "`{}` was kept", supplied);
+    }
+}
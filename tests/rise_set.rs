@@ -0,0 +1,156 @@
+//! Checks sunrise and sunset against the NOAA Solar Calculator, and moonrise and
+//! moonset against the Moon's monthly cycle.
+//!
+//! The NOAA times, computed with its published spreadsheet equations, are for the
+//! Sun's upper limb at the refracted horizon, the −0.833° altitude of its centre. The
+//! geometric horizon of the Sun's centre would put every sunrise several minutes later,
+//! beyond the tolerance of two minutes.
+
+use panchang_rs::panchang::{moonrise_moonset, sunrise_sunset};
+
+/// Largest difference from a reference time, in hours
+const TOLERANCE: f64 = 2.0 / 60.0;
+
+/// An observer's date, latitude, longitude and zone, and the NOAA sunrise and
+/// sunset in local HH:MM
+struct Case {
+    place: &'static str,
+    date: (i32, i32, i32),
+    latitude: f64,
+    longitude: f64,
+    zone: f64,
+    sunrise: &'static str,
+    sunset: &'static str,
+}
+
+const CASES: [Case; 6] = [
+    Case {
+        place: "New Delhi",
+        date: (15, 8, 2023),
+        latitude: 28.6139,
+        longitude: 77.2090,
+        zone: 5.5,
+        sunrise: "05:50",
+        sunset: "19:01",
+    },
+    Case {
+        place: "Chennai",
+        date: (15, 1, 2024),
+        latitude: 13.0827,
+        longitude: 80.2707,
+        zone: 5.5,
+        sunrise: "06:35",
+        sunset: "18:01",
+    },
+    Case {
+        place: "Greenwich",
+        date: (21, 6, 2024),
+        latitude: 51.4779,
+        longitude: 0.0,
+        zone: 1.0,
+        sunrise: "04:43",
+        sunset: "21:21",
+    },
+    Case {
+        place: "the equator",
+        date: (20, 3, 2024),
+        latitude: 0.0,
+        longitude: 0.0,
+        zone: 0.0,
+        sunrise: "06:04",
+        sunset: "18:11",
+    },
+    Case {
+        place: "New York",
+        date: (1, 1, 2024),
+        latitude: 40.7128,
+        longitude: -74.0060,
+        zone: -5.0,
+        sunrise: "07:20",
+        sunset: "16:39",
+    },
+    Case {
+        place: "Sydney",
+        date: (21, 12, 2024),
+        latitude: -33.8688,
+        longitude: 151.2093,
+        zone: 11.0,
+        sunrise: "05:41",
+        sunset: "20:06",
+    },
+];
+
+/// Reads an HH:MM time as decimal hours
+fn hours(time: &str) -> f64 {
+    let (hours, minutes) = time.split_once(':').unwrap();
+    hours.parse::<f64>().unwrap() + minutes.parse::<f64>().unwrap() / 60.0
+}
+
+/// Moonrise and moonset in New Delhi on a day of May 2024
+fn delhi_moon(day: i32) -> (Option<f64>, Option<f64>) {
+    moonrise_moonset(day, 5, 2024, 28.6139, 77.2090, 5.5)
+}
+
+#[test]
+fn sunrise_and_sunset_match_noaa() {
+    for case in &CASES {
+        let (day, month, year) = case.date;
+        let (sunrise, sunset) =
+            sunrise_sunset(day, month, year, case.latitude, case.longitude, case.zone).unwrap();
+        for (name, actual, expected) in [
+            ("sunrise", sunrise, case.sunrise),
+            ("sunset", sunset, case.sunset),
+        ] {
+            assert!(
+                (actual - hours(expected)).abs() <= TOLERANCE,
+                "{} on {}/{}/{}: {} at {:.3} h, expected {}",
+                case.place,
+                day,
+                month,
+                year,
+                name,
+                actual,
+                expected
+            );
+        }
+    }
+}
+
+#[test]
+fn moon_rises_with_the_sun_at_new_moon_and_opposite_it_at_full_moon() {
+    // New moon on 8 May 2024 and full moon on 23 May, both in daytime in India
+    let (sunrise, _) = sunrise_sunset(8, 5, 2024, 28.6139, 77.2090, 5.5).unwrap();
+    let (moonrise, _) = delhi_moon(8);
+    assert!(
+        (moonrise.unwrap() - sunrise).abs() < 1.0,
+        "new moon rises with the Sun"
+    );
+
+    let (_, sunset) = sunrise_sunset(23, 5, 2024, 28.6139, 77.2090, 5.5).unwrap();
+    let (moonrise, _) = delhi_moon(23);
+    assert!(
+        (moonrise.unwrap() - sunset).abs() < 1.0,
+        "full moon rises at sunset"
+    );
+}
+
+#[test]
+fn moon_rises_later_each_day_and_skips_a_day_a_month() {
+    let days: Vec<_> = (1..=31).map(delhi_moon).collect();
+
+    // Rising about 50 minutes later each day, the Moon misses one day's moonrise and
+    // another's moonset
+    assert_eq!(days.iter().filter(|(rise, _)| rise.is_none()).count(), 1);
+    assert_eq!(days.iter().filter(|(_, set)| set.is_none()).count(), 1);
+    for (day, pair) in days.windows(2).enumerate() {
+        if let (Some(today), Some(tomorrow)) = (pair[0].0, pair[1].0) {
+            let delay = tomorrow - today;
+            assert!(
+                (0.5..1.25).contains(&delay),
+                "moonrise of {} May is {:.2} h after the day before",
+                day + 2,
+                delay
+            );
+        }
+    }
+}
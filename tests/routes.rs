@@ -0,0 +1,44 @@
+//! Checks that requests no route serves are answered in the API envelope.
+
+#![cfg(feature = "server")]
+
+use actix_web::http::{StatusCode, header};
+use actix_web::{App, test};
+use panchang_rs::server;
+use serde_json::Value;
+
+#[actix_web::test]
+async fn unknown_path_is_enveloped_404() {
+    let app = test::init_service(App::new().configure(server::configure)).await;
+    let response = test::call_service(
+        &app,
+        test::TestRequest::get().uri("/no-such-path").to_request(),
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let body: Value = test::read_body_json(response).await;
+    assert_eq!(body["status"], "error");
+    assert_eq!(body["statusCode"], 404);
+    assert_eq!(body["message"], "Not Found");
+    assert_eq!(body["code"], "NOT_FOUND");
+    assert!(!body["requestId"].as_str().unwrap().is_empty());
+}
+
+#[actix_web::test]
+async fn wrong_method_is_enveloped_405() {
+    let app = test::init_service(App::new().configure(server::configure)).await;
+    let response = test::call_service(
+        &app,
+        test::TestRequest::delete().uri("/panchang").to_request(),
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(response.headers().get(header::ALLOW).unwrap(), "GET, POST");
+    let body: Value = test::read_body_json(response).await;
+    assert_eq!(body["status"], "error");
+    assert_eq!(body["statusCode"], 405);
+    assert_eq!(body["code"], "METHOD_NOT_ALLOWED");
+    assert!(!body["requestId"].as_str().unwrap().is_empty());
+}
@@ -0,0 +1,106 @@
+//! Checks that `samples` calculates the limbs at evenly spaced or named times of the
+//! date, so that the first and last samples bracket a Tithi change during the day.
+
+#![cfg(feature = "server")]
+
+use actix_web::http::StatusCode;
+use actix_web::{App, test, web};
+use panchang_rs::config::Config;
+use panchang_rs::server::{self, AppState};
+use serde_json::{Value, json};
+
+/// Sends a `/panchang` request for 14 August 2023 in New Delhi with `samples`
+async fn panchang(samples: Value) -> (StatusCode, Value) {
+    let config = Config::load(None, |_| None).unwrap();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::new(&config, None)))
+            .app_data(server::json_config(config.max_body_bytes))
+            .configure(server::configure),
+    )
+    .await;
+    let request = test::TestRequest::post()
+        .uri("/panchang")
+        .set_json(json!({
+            "date": "14/08/2023",
+            "time": "12:00",
+            "zone": "+05:30",
+            "latitude": 28.6139,
+            "longitude": 77.2090,
+            "samples": samples,
+        }))
+        .to_request();
+    let response = test::call_service(&app, request).await;
+    let status = response.status();
+    (status, test::read_body_json(response).await)
+}
+
+/// Times of the samples of a response
+fn times(response: &Value) -> Vec<&str> {
+    response["data"]["samples"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|sample| sample["time"].as_str().unwrap())
+        .collect()
+}
+
+#[actix_web::test]
+async fn four_samples_bracket_a_tithi_change() {
+    let (status, response) = panchang(json!(4)).await;
+    assert_eq!(status, StatusCode::OK, "{}", response);
+    assert_eq!(
+        times(&response),
+        [
+            "2023-08-14T00:00:00+05:30",
+            "2023-08-14T06:00:00+05:30",
+            "2023-08-14T12:00:00+05:30",
+            "2023-08-14T18:00:00+05:30",
+        ]
+    );
+
+    // Chaturdashi begins at 10:26, between the second and the third samples
+    let data = &response["data"];
+    let samples = data["samples"].as_array().unwrap();
+    assert!(data["tithi_start"].as_str().unwrap() > times(&response)[1]);
+    assert!(data["tithi_start"].as_str().unwrap() < times(&response)[2]);
+    assert_eq!(samples[0]["tithi"], "Thrayodashi");
+    assert_eq!(samples[1]["tithi"], "Thrayodashi");
+    assert_eq!(samples[2]["tithi"], "Chaturdashi");
+    assert_eq!(samples[3]["tithi"], data["tithi"]);
+
+    // The Nakshatra moves on from Punarvasu to Pushya during the day too
+    assert_eq!(samples[0]["nakshatra"], "Punarvasu");
+    assert_eq!(samples[3]["nakshatra"], "Pushya");
+}
+
+#[actix_web::test]
+async fn named_times_follow_the_sun() {
+    let (status, response) = panchang(json!(["sunrise", "noon", "sunset", "21:30"])).await;
+    assert_eq!(status, StatusCode::OK, "{}", response);
+
+    // The samples' instants, to the second, round to the minutes of the day reported
+    let times = times(&response);
+    let minutes = |time: &str| {
+        let parts: Vec<f64> = time.split(':').map(|part| part.parse().unwrap()).collect();
+        parts[0] * 60.0 + parts[1] + parts.get(2).map_or(0.0, |seconds| seconds / 60.0)
+    };
+    let clock = |time: &str| minutes(&time[11..19]).round();
+    let data = &response["data"];
+    assert_eq!(clock(times[0]), minutes(data["sunrise"].as_str().unwrap()));
+    assert!(clock(times[0]) < clock(times[1]) && clock(times[1]) < clock(times[2]));
+    assert_eq!(clock(times[2]), minutes(data["sunset"].as_str().unwrap()));
+    assert_eq!(times[3], "2023-08-14T21:30:00+05:30");
+
+    // The same times as a comma-separated string
+    let (_, listed) = panchang(json!("sunrise,noon,sunset,21:30")).await;
+    assert_eq!(listed["data"]["samples"], response["data"]["samples"]);
+}
+
+#[actix_web::test]
+async fn too_many_or_unknown_samples_are_rejected() {
+    for samples in [json!(0), json!(49), json!("sunrise,midnight")] {
+        let (status, response) = panchang(samples.clone()).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}: {}", samples, response);
+    }
+}
@@ -0,0 +1,25 @@
+//! Checks the Samvatsara against the almanac years around Ugadi (Chaitra Shukla
+//! Pratipada): Shubhakrit from 2 April 2022, Shobhakrit from 22 March 2023, Krodhi
+//! from 9 April 2024 and Vishvavasu from 30 March 2025.
+
+use panchang_rs::{Ayanamsa, calculate_panchanga};
+
+/// The Samvatsara at noon in New Delhi
+fn samvatsara(day: i32, month: i32, year: i32) -> String {
+    calculate_panchanga(day, month, year, 12.0, 5.5, Ayanamsa::Lahiri).current_samvatsara
+}
+
+#[test]
+fn samvatsara_changes_at_ugadi() {
+    assert_eq!(samvatsara(15, 8, 2022), "Shubhakrit");
+
+    // Phalguna of early 2023 still belongs to Shubhakrit
+    assert_eq!(samvatsara(1, 3, 2023), "Shubhakrit");
+    assert_eq!(samvatsara(15, 8, 2023), "Shobhakrit");
+
+    // So does Magha of early 2024 to Shobhakrit
+    assert_eq!(samvatsara(15, 2, 2024), "Shobhakrit");
+    assert_eq!(samvatsara(15, 5, 2024), "Krodhi");
+    assert_eq!(samvatsara(1, 12, 2024), "Krodhi");
+    assert_eq!(samvatsara(15, 4, 2025), "Vishvavasu");
+}
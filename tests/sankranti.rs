@@ -0,0 +1,40 @@
+//! Checks the Makara Sankranti of 2024, which Drik Panchang times at 02:54 IST on
+//! 15 January for New Delhi with the Lahiri Ayanamsa.
+//!
+//! The crate's Sun is the geometric mean-equinox longitude, without the 20″ of
+//! aberration, and its Lahiri Ayanamsa is a mean formula, so its Sun crosses 270°
+//! sidereal about twenty minutes before the almanac's. The search itself must find
+//! that crossing to within a minute.
+
+use chrono::{Duration, TimeZone, Utc};
+use panchang_rs::Ayanamsa;
+use panchang_rs::panchang::{calculate_panchanga_at, sankranti_on};
+
+#[test]
+fn makara_sankranti_2024() {
+    let (rashi, ingress) =
+        sankranti_on(15, 1, 2024, 5.5, Ayanamsa::Lahiri).expect("a Sankranti on the 15th");
+    assert_eq!(rashi, "Makara");
+
+    // The Sun is still in Dhanu a minute before the instant found, and in Makara a minute after
+    let surya_rashi = |offset: i64| {
+        calculate_panchanga_at(ingress + Duration::minutes(offset), Ayanamsa::Lahiri)
+            .current_surya_rashi
+    };
+    assert_eq!(surya_rashi(-1), "Dhanu");
+    assert_eq!(surya_rashi(1), "Makara");
+
+    // 02:54 IST is 21:24 UTC the day before
+    let published = Utc.with_ymd_and_hms(2024, 1, 14, 21, 24, 0).unwrap();
+    let early = published - ingress;
+    assert!(
+        early >= Duration::zero() && early < Duration::minutes(30),
+        "{} against {}",
+        ingress,
+        published
+    );
+
+    // Not on the days either side
+    assert!(sankranti_on(14, 1, 2024, 5.5, Ayanamsa::Lahiri).is_none());
+    assert!(sankranti_on(16, 1, 2024, 5.5, Ayanamsa::Lahiri).is_none());
+}
@@ -0,0 +1,83 @@
+//! Checks that `script` writes the element names in Devanagari, and that a script
+//! the API does not know is rejected with a 400 in the API envelope.
+
+#![cfg(feature = "server")]
+
+use actix_web::http::StatusCode;
+use actix_web::{App, test, web};
+use panchang_rs::config::Config;
+use panchang_rs::panchang::{NameKind, Script, name_in_script};
+use panchang_rs::server::{self, AppState};
+use serde_json::{Value, json};
+
+/// Sends a `/panchang` request for 15 August 2023 in New Delhi with `script`
+async fn panchang(script: &str) -> (StatusCode, Value) {
+    let config = Config::load(None, |_| None).unwrap();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::new(&config, None)))
+            .app_data(server::json_config(config.max_body_bytes))
+            .configure(server::configure),
+    )
+    .await;
+    let request = test::TestRequest::post()
+        .uri("/panchang")
+        .set_json(json!({
+            "date": "15/08/2023",
+            "time": "12:00",
+            "zone": "+05:30",
+            "latitude": 28.6139,
+            "longitude": 77.2090,
+            "script": script,
+        }))
+        .to_request();
+    let response = test::call_service(&app, request).await;
+    let status = response.status();
+    (status, test::read_body_json(response).await)
+}
+
+#[actix_web::test]
+async fn tithi_in_devanagari() {
+    assert_eq!(
+        name_in_script(NameKind::Tithi, "Chathurthi", Script::Devanagari),
+        "चतुर्थी"
+    );
+    assert_eq!(
+        name_in_script(NameKind::Tithi, "Chathurthi", Script::Iast),
+        "Caturthī"
+    );
+
+    // A name that is not a Tithi is left as it is
+    assert_eq!(
+        name_in_script(NameKind::Tithi, "Rohini", Script::Devanagari),
+        "Rohini"
+    );
+}
+
+#[actix_web::test]
+async fn response_names_are_written_in_the_script() {
+    let (status, roman) = panchang("roman").await;
+    assert_eq!(status, StatusCode::OK, "{}", roman);
+    let (status, devanagari) = panchang("devanagari").await;
+    assert_eq!(status, StatusCode::OK, "{}", devanagari);
+
+    let tithi = roman["data"]["tithi"].as_str().unwrap();
+    assert_eq!(
+        devanagari["data"]["tithi"],
+        name_in_script(NameKind::Tithi, tithi, Script::Devanagari)
+    );
+    assert_ne!(devanagari["data"]["tithi"], tithi);
+}
+
+#[actix_web::test]
+async fn unknown_script_is_rejected() {
+    let (status, body) = panchang("klingon").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["status"], "error");
+    assert_eq!(body["code"], "INVALID_BODY");
+    assert!(
+        body["message"].as_str().unwrap().contains("klingon"),
+        "{}",
+        body
+    );
+}
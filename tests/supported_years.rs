@@ -0,0 +1,63 @@
+//! Checks the supported years at their edges and just outside them.
+//!
+//! Dates outside the range a deployment supports are rejected, while dates outside
+//! the 1900-2100 accuracy range that it still accepts carry an `accuracy_warning`.
+
+use std::ops::RangeInclusive;
+
+use panchang_rs::PanchangError;
+use panchang_rs::panchang::{PanchangResponse, calculate_panchang};
+use serde_json::Value;
+
+mod common;
+
+/// Calculates the Panchang at a local date and time in New Delhi, for a deployment
+/// supporting `years`
+fn panchang(
+    date: &str,
+    time: &str,
+    years: RangeInclusive<i32>,
+) -> Result<PanchangResponse, PanchangError> {
+    let request = common::delhi(date, time).with_supported_years(years);
+    common::block_on(calculate_panchang(&request))
+}
+
+fn accuracy_warning(response: PanchangResponse) -> Value {
+    serde_json::to_value(response).unwrap()["accuracy_warning"].clone()
+}
+
+#[test]
+fn accepts_edges_of_default_range() {
+    for (date, time) in [("01/01/1900", "00:00"), ("31/12/2100", "23:59")] {
+        let response = panchang(date, time, 1900..=2100).unwrap();
+        assert_eq!(accuracy_warning(response), Value::Null, "{}", date);
+    }
+}
+
+#[test]
+fn rejects_dates_just_outside_default_range() {
+    for date in ["31/12/1899", "01/01/2101"] {
+        let error = panchang(date, "12:00", 1900..=2100).unwrap_err();
+        assert!(
+            matches!(error, PanchangError::DateOutOfRange(_)),
+            "{}: {}",
+            date,
+            error
+        );
+        assert_eq!(error.code(), "DATE_OUT_OF_RANGE");
+    }
+}
+
+#[test]
+fn warns_outside_accuracy_range() {
+    for date in ["31/12/1899", "01/01/2101"] {
+        let response = panchang(date, "12:00", 1800..=2200).unwrap();
+        let warning = accuracy_warning(response);
+        assert!(
+            warning.as_str().unwrap().contains("1900-2100"),
+            "{}: {}",
+            date,
+            warning
+        );
+    }
+}
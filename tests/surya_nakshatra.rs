@@ -0,0 +1,47 @@
+//! Checks the Sun's Nakshatra and pada on either side of its entries into Krittika
+//! and Ardra in 2024.
+//!
+//! With the Lahiri Ayanamsa the Sun entered Krittika on 11 May at about 07:45 IST and
+//! Ardra, which opens the monsoon in the agricultural calendars, on 22 June at about
+//! 00:50 IST. Each instant is half a day or more from those entries.
+
+use panchang_rs::{Ayanamsa, calculate_panchanga};
+
+mod common;
+
+/// Asserts the Sun's Nakshatra and pada at a local noon in IST
+fn assert_surya_nakshatra(day: i32, month: i32, nakshatra: &str, pada: u8) {
+    let panchanga = calculate_panchanga(day, month, 2024, 12.0, 5.5, Ayanamsa::Lahiri);
+    assert_eq!(
+        (
+            panchanga.current_surya_nakshatra.as_str(),
+            panchanga.current_surya_nakshatra_pada
+        ),
+        (nakshatra, pada),
+        "{}/{}/2024",
+        day,
+        month
+    );
+}
+
+#[test]
+fn sun_enters_krittika() {
+    assert_surya_nakshatra(10, 5, "Bharani", 4);
+    assert_surya_nakshatra(11, 5, "Krittika", 1);
+    // Krittika's last three padas are in Vrishabha, which the Sun enters on 14 May
+    assert_surya_nakshatra(15, 5, "Krittika", 2);
+}
+
+#[test]
+fn sun_enters_ardra() {
+    assert_surya_nakshatra(21, 6, "Mrigashira", 4);
+    assert_surya_nakshatra(22, 6, "Ardhra", 1);
+}
+
+#[test]
+fn response_reports_the_suns_nakshatra() {
+    let response = common::panchang_json(&common::delhi("12/05/2024", "12:00"));
+    assert_eq!(response["surya_nakshatra"], "Krittika");
+    assert_eq!(response["surya_nakshatra_pada"], 1);
+    assert_eq!(response["surya_rashi"], "Mesha");
+}
@@ -0,0 +1,90 @@
+//! Checks that a calculation running past `CALCULATION_TIMEOUT_MS` is answered with
+//! a 504 in the API envelope, by giving a year's range a budget of a millisecond, and
+//! that one that panics is reported as an internal error instead of failing its worker.
+
+#![cfg(feature = "server")]
+
+use actix_web::http::StatusCode;
+use actix_web::{App, ResponseError, test, web};
+use panchang_rs::PanchangError;
+use panchang_rs::config::Config;
+use panchang_rs::server::{self, AppState, within_budget};
+use serde_json::{Value, json};
+
+/// Configures a server without a database, with calculations limited to `timeout_ms`
+fn config(timeout_ms: &str) -> Config {
+    Config::load(None, |name| {
+        (name == "CALCULATION_TIMEOUT_MS").then(|| timeout_ms.to_string())
+    })
+    .unwrap()
+}
+
+/// A request for the 366 days of 2024
+fn year_range() -> test::TestRequest {
+    test::TestRequest::post()
+        .uri("/panchang/range")
+        .set_json(json!({
+            "start_date": "01/01/2024",
+            "end_date": "31/12/2024",
+            "time": "06:00",
+            "zone": "+05:30",
+            "latitude": 28.6139,
+            "longitude": 77.2090,
+        }))
+}
+
+#[actix_web::test]
+async fn slow_calculation_is_enveloped_504() {
+    let config = config("1");
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::new(&config, None)))
+            .app_data(server::json_config(config.max_body_bytes))
+            .configure(server::configure),
+    )
+    .await;
+    let response = test::call_service(&app, year_range().to_request()).await;
+
+    assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+    let body: Value = test::read_body_json(response).await;
+    assert_eq!(body["status"], "error");
+    assert_eq!(body["statusCode"], 504);
+    assert_eq!(body["code"], "CALCULATION_TIMEOUT");
+    assert_eq!(body["message"], "Calculation timed out after 1 ms");
+}
+
+#[actix_web::test]
+async fn calculation_within_the_budget_succeeds() {
+    let config = config("60000");
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::new(&config, None)))
+            .app_data(server::json_config(config.max_body_bytes))
+            .configure(server::configure),
+    )
+    .await;
+    let response = test::call_service(
+        &app,
+        test::TestRequest::get()
+            .uri("/panchang?date=15/08/2023&time=12:00&zone=%2B05:30&latitude=28.6139&longitude=77.2090")
+            .to_request(),
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[actix_web::test]
+async fn panicking_calculation_is_an_internal_error() {
+    let state = AppState::new(&config("60000"), None);
+    let result: Result<(), PanchangError> =
+        within_budget(&state, async { panic!("calculation bug") }).await;
+
+    let error = result.unwrap_err();
+    assert!(matches!(error, PanchangError::CalculationFailed(_)));
+    assert_eq!(error.code(), "INTERNAL_ERROR");
+    assert_eq!(error.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+
+    // The runtime keeps serving calculations afterwards
+    assert_eq!(within_budget(&state, async { Ok(1) }).await.unwrap(), 1);
+}
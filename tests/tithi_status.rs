@@ -0,0 +1,51 @@
+//! Checks the Tithi status against dates of 2023 in New Delhi on which a Tithi is
+//! repeated at two sunrises (vriddhi) or falls between two (kshaya).
+
+use serde_json::{Value, json};
+
+mod common;
+
+/// The Panchang at noon IST in New Delhi on `date`
+fn panchang(date: &str) -> Value {
+    common::panchang_json(&common::delhi(date, "12:00"))
+}
+
+#[test]
+fn ekadashi_spanning_two_sunrises_is_vriddhi() {
+    // Parama Ekadashi ran from 05:06 on 11 August to 06:31 on the 12th, and was
+    // observed on the second of its sunrises
+    let panchang = panchang("11/08/2023");
+    assert_eq!(panchang["tithi_status"], "vriddhi");
+    assert_eq!(
+        panchang["sunrise_tithis"],
+        json!({ "at_sunrise": "Ekadashi", "at_next_sunrise": "Ekadashi", "skipped": null })
+    );
+}
+
+#[test]
+fn chaturdashi_between_two_sunrises_is_kshaya() {
+    // Chaturdashi began after sunrise on 27 October and ended before the next, when
+    // Sharad Purnima had begun
+    let panchang = panchang("27/10/2023");
+    assert_eq!(panchang["tithi_status"], "kshaya");
+    assert_eq!(
+        panchang["sunrise_tithis"],
+        json!({
+            "at_sunrise": "Thrayodashi",
+            "at_next_sunrise": "Poornima",
+            "skipped": "Chaturdashi",
+        })
+    );
+
+    // It is the Tithi at noon, bounded within the day
+    assert_eq!(panchang["tithi"], "Chaturdashi");
+    assert!(panchang["tithi_start"].as_str().unwrap() > "2023-10-27T06:30");
+    assert!(panchang["tithi_end"].as_str().unwrap() < "2023-10-28T06:30");
+}
+
+#[test]
+fn consecutive_tithis_are_normal() {
+    let panchang = panchang("15/08/2023");
+    assert_eq!(panchang["tithi_status"], "normal");
+    assert_eq!(panchang["sunrise_tithis"]["skipped"], Value::Null);
+}
@@ -0,0 +1,44 @@
+//! Checks that the demo page is served at `/` when built with the `ui` feature, and
+//! that API-only builds answer `/` with the API's 404.
+
+#![cfg(feature = "server")]
+
+use actix_web::http::StatusCode;
+use actix_web::{App, test, web};
+use panchang_rs::config::Config;
+use panchang_rs::server::{self, AppState};
+
+/// Sends `GET /` to a server without a database
+async fn index() -> actix_web::dev::ServiceResponse {
+    let config = Config::load(None, |_| None).unwrap();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::new(&config, None)))
+            .configure(server::configure),
+    )
+    .await;
+    test::call_service(&app, test::TestRequest::get().uri("/").to_request()).await
+}
+
+#[cfg(feature = "ui")]
+#[actix_web::test]
+async fn index_serves_the_demo_page() {
+    let response = index().await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let content_type = response
+        .headers()
+        .get(actix_web::http::header::CONTENT_TYPE)
+        .unwrap();
+    assert!(content_type.to_str().unwrap().starts_with("text/html"));
+
+    // The page is whole, asking the API on its own origin
+    let page = String::from_utf8(test::read_body(response).await.to_vec()).unwrap();
+    assert!(page.contains("fetch(\"/panchang\""));
+    assert!(!page.contains("<script src="));
+}
+
+#[cfg(not(feature = "ui"))]
+#[actix_web::test]
+async fn index_is_not_found_without_the_ui() {
+    assert_eq!(index().await.status(), StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,102 @@
+//! Checks that `ValidatedRequest` rejects each invalid input with its error, and that
+//! a valid request comes out parsed and calculates as `calculate_panchang` does.
+
+use chrono::{FixedOffset, NaiveDate};
+use panchang_rs::PanchangError;
+use panchang_rs::panchang::{
+    PanchangRequest, ValidatedRequest, calculate_panchang, calculate_panchang_validated,
+};
+use serde_json::{Value, json};
+
+mod common;
+
+/// Noon IST in New Delhi on 15 August 2023, with `changes` applied
+fn request(changes: Value) -> PanchangRequest {
+    common::delhi_with("15/08/2023", "12:30", changes)
+}
+
+/// Validates `request(changes)`
+fn validate(changes: Value) -> Result<ValidatedRequest, PanchangError> {
+    ValidatedRequest::try_from(&request(changes))
+}
+
+#[test]
+fn rejects_each_invalid_input() {
+    let cases = [
+        (json!({ "date": "31/02/2023" }), "DATE_OUT_OF_RANGE"),
+        (json!({ "date": "2023-08-15" }), "INVALID_DATE"),
+        (json!({ "time": "25:00" }), "INVALID_TIME"),
+        (json!({ "zone": "+25:00" }), "INVALID_TIMEZONE"),
+        (json!({ "timezone": "Asia/Nowhere" }), "INVALID_TIMEZONE"),
+        (json!({ "zone": null }), "INVALID_TIMEZONE"),
+        (json!({ "time": null }), "INVALID_INSTANT"),
+        (json!({ "epoch": 1692081000 }), "INVALID_INSTANT"),
+        (json!({ "latitude": 91.0 }), "INVALID_LOCATION"),
+        (json!({ "longitude": -181.0 }), "INVALID_LOCATION"),
+        (
+            json!({ "janma_nakshatra": "Nowhere" }),
+            "INVALID_BIRTH_DETAILS",
+        ),
+        (json!({ "janma_rashi": "Nowhere" }), "INVALID_BIRTH_DETAILS"),
+    ];
+    for (changes, code) in cases {
+        let error = validate(changes.clone()).unwrap_err();
+        assert_eq!(error.code(), code, "{}: {}", changes, error);
+    }
+}
+
+#[test]
+fn rejects_years_outside_the_supported_range() {
+    let request = request(json!({ "date": "15/08/1850" })).with_supported_years(1900..=2100);
+    let error = ValidatedRequest::try_from(&request).unwrap_err();
+    assert!(
+        matches!(error, PanchangError::DateOutOfRange(_)),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn valid_request_is_parsed() {
+    let validated = validate(json!({})).unwrap();
+    assert_eq!(
+        validated.date(),
+        NaiveDate::from_ymd_opt(2023, 8, 15).unwrap()
+    );
+    assert_eq!(validated.hour(), 12.5);
+    assert_eq!(
+        validated.offset(),
+        FixedOffset::east_opt(5 * 3600 + 1800).unwrap()
+    );
+    assert_eq!(
+        (
+            validated.location().latitude,
+            validated.location().longitude
+        ),
+        (28.6139, 77.2090)
+    );
+
+    // A named timezone gives the offset in effect at the date, here summer time
+    let validated = validate(json!({ "zone": null, "timezone": "America/New_York" })).unwrap();
+    assert_eq!(validated.offset(), FixedOffset::west_opt(4 * 3600).unwrap());
+
+    // 09:30 on 15 August 2023 in UTC, which is 15:00 in IST
+    let validated = validate(json!({ "date": null, "time": null, "epoch": 1692091800 })).unwrap();
+    assert_eq!(
+        validated.date(),
+        NaiveDate::from_ymd_opt(2023, 8, 15).unwrap()
+    );
+    assert_eq!(validated.hour(), 15.0);
+}
+
+#[test]
+fn validated_request_calculates_as_the_request() {
+    let request = request(json!({ "janma_nakshatra": "Rohini", "verbose": true }));
+    let expected = common::block_on(calculate_panchang(&request)).unwrap();
+
+    let validated = ValidatedRequest::try_from(&request).unwrap();
+    assert_eq!(
+        serde_json::to_value(calculate_panchang_validated(&validated)).unwrap(),
+        serde_json::to_value(expected).unwrap()
+    );
+}
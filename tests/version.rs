@@ -0,0 +1,241 @@
+//! Checks the response schema versions: version 1, asked for with `?v=1` or a vendor
+//! `Accept` media type, keeps exactly the six original limbs, while version 2, the
+//! default, carries the newer fields such as the sunrise and the Masa. The next-Tithi
+//! and compare endpoints negotiate the version and the label language alike.
+
+#![cfg(feature = "server")]
+
+use actix_web::http::{StatusCode, header};
+use actix_web::{App, test, web};
+use panchang_rs::config::Config;
+use panchang_rs::server::{self, AppState};
+use serde_json::{Value, json};
+
+/// Query string of noon IST on 15/08/2023 in New Delhi
+const QUERY: &str = "date=15/08/2023&time=12:00&zone=%2B05:30&latitude=28.6139&longitude=77.2090";
+
+/// The limbs of a version 1 Panchang
+const V1_FIELDS: [&str; 6] = ["karana", "nakshatra", "paksha", "rashi", "tithi", "yoga"];
+
+/// Sends a request to a server without a database, returning its status and body
+async fn call(request: test::TestRequest) -> (StatusCode, Value) {
+    let config = Config::load(None, |_| None).unwrap();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState::new(&config, None)))
+            .app_data(server::json_config(config.max_body_bytes))
+            .configure(server::configure),
+    )
+    .await;
+    let response = test::call_service(&app, request.to_request()).await;
+    (response.status(), test::read_body_json(response).await)
+}
+
+/// Keys of a JSON object, which serde_json keeps sorted
+fn keys(value: &Value) -> Vec<&str> {
+    value
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(String::as_str)
+        .collect()
+}
+
+#[actix_web::test]
+async fn v1_query_keeps_the_six_limbs() {
+    let uri = format!("/panchang?{}&v=1", QUERY);
+    let (status, body) = call(test::TestRequest::get().uri(&uri)).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["apiVersion"], 1);
+    assert_eq!(keys(&body["data"]), V1_FIELDS);
+    assert_eq!(body["data"]["tithi"], "Chaturdashi");
+    assert_eq!(body["data"]["paksha"], "Krishna");
+}
+
+#[actix_web::test]
+async fn v1_accept_header_keeps_the_six_limbs() {
+    let request = test::TestRequest::post()
+        .uri("/panchang")
+        .insert_header((header::ACCEPT, "application/vnd.panchang.v1+json"))
+        .set_json(json!({
+            "date": "15/08/2023",
+            "time": "12:00",
+            "zone": "+05:30",
+            "latitude": 28.6139,
+            "longitude": 77.2090,
+        }));
+    let (status, body) = call(request).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["apiVersion"], 1);
+    assert_eq!(keys(&body["data"]), V1_FIELDS);
+}
+
+#[actix_web::test]
+async fn v2_adds_the_newer_fields() {
+    for uri in [
+        format!("/panchang?{}", QUERY),
+        format!("/panchang?{}&v=2", QUERY),
+    ] {
+        let (status, body) = call(test::TestRequest::get().uri(&uri)).await;
+
+        assert_eq!(status, StatusCode::OK, "{}", uri);
+        assert_eq!(body["apiVersion"], 2, "{}", uri);
+        let data = &body["data"];
+        assert!(data["sunrise"].is_string(), "{}: sunrise", uri);
+        assert_eq!(data["masa"], "Shravana", "{}", uri);
+        assert_eq!(data["paksha"]["name"], "Krishna", "{}", uri);
+    }
+}
+
+#[actix_web::test]
+async fn v1_range_keeps_each_days_date() {
+    let request = test::TestRequest::post()
+        .uri("/panchang/range?v=1")
+        .set_json(json!({
+            "start_date": "01/08/2023",
+            "end_date": "03/08/2023",
+            "time": "06:00",
+            "zone": "+05:30",
+            "latitude": 28.6139,
+            "longitude": 77.2090,
+        }));
+    let (status, body) = call(request).await;
+
+    assert_eq!(status, StatusCode::OK);
+    let days = body["data"].as_array().unwrap();
+    assert_eq!(days.len(), 3);
+    for day in days {
+        assert_eq!(
+            keys(day),
+            [
+                "date",
+                "karana",
+                "nakshatra",
+                "paksha",
+                "rashi",
+                "tithi",
+                "yoga"
+            ]
+        );
+    }
+}
+
+#[actix_web::test]
+async fn unknown_version_is_not_acceptable() {
+    let uri = format!("/panchang?{}&v=3", QUERY);
+    let (status, body) = call(test::TestRequest::get().uri(&uri)).await;
+
+    assert_eq!(status, StatusCode::NOT_ACCEPTABLE);
+    assert_eq!(body["code"], "UNSUPPORTED_API_VERSION");
+
+    let request = test::TestRequest::get()
+        .uri(&format!("/panchang?{}", QUERY))
+        .insert_header((header::ACCEPT, "application/vnd.panchang.v9+json"));
+    let (status, _) = call(request).await;
+    assert_eq!(status, StatusCode::NOT_ACCEPTABLE);
+}
+
+/// Body of a next-Tithi search from 20/08/2023 in New Delhi
+fn next_tithi() -> Value {
+    json!({
+        "from_date": "20/08/2023",
+        "tithi": "Ekadashi",
+        "time": "06:00",
+        "zone": "+05:30",
+        "latitude": 28.6139,
+        "longitude": 77.2090,
+    })
+}
+
+/// Body of a comparison of noon IST on 15/08/2023 in New Delhi
+fn compare() -> Value {
+    json!({
+        "date": "15/08/2023",
+        "time": "12:00",
+        "zone": "+05:30",
+        "latitude": 28.6139,
+        "longitude": 77.2090,
+        "ayanamsas": ["lahiri", "raman"],
+    })
+}
+
+#[actix_web::test]
+async fn next_tithi_and_compare_negotiate_the_version() {
+    for (uri, body) in [
+        ("/panchang/next-tithi", next_tithi()),
+        ("/panchang/compare", compare()),
+    ] {
+        let (status, v2) = call(test::TestRequest::post().uri(uri).set_json(body.clone())).await;
+        assert_eq!(status, StatusCode::OK, "{}", v2);
+        assert_eq!(v2["apiVersion"], 2);
+        assert!(v2["data"]["defaulted"].is_array(), "{}", v2);
+
+        let request = test::TestRequest::post()
+            .uri(uri)
+            .insert_header((header::ACCEPT, "application/vnd.panchang.v1+json"))
+            .set_json(body.clone());
+        let (status, v1) = call(request).await;
+        assert_eq!(status, StatusCode::OK, "{}", v1);
+        assert_eq!(v1["apiVersion"], 1);
+        assert!(v1["data"].get("defaulted").is_none(), "{}", v1);
+
+        let (status, refused) = call(
+            test::TestRequest::post()
+                .uri(&format!("{}?v=3", uri))
+                .set_json(body),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_ACCEPTABLE, "{}", refused);
+    }
+}
+
+#[actix_web::test]
+async fn v1_comparison_keeps_the_six_limbs_of_each_system() {
+    let (_, body) = call(
+        test::TestRequest::post()
+            .uri("/panchang/compare?v=1")
+            .set_json(compare()),
+    )
+    .await;
+
+    for system in body["data"]["systems"].as_array().unwrap() {
+        let mut expected = vec!["ayanamsa", "ayanamsa_degrees"];
+        expected.extend(V1_FIELDS);
+        expected.sort();
+        assert_eq!(keys(system), expected);
+    }
+    assert!(
+        body["data"]["differences"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|difference| difference["limb"] != "lagna")
+    );
+}
+
+#[actix_web::test]
+async fn next_tithi_and_compare_are_labelled() {
+    let (_, next_tithi) = call(
+        test::TestRequest::post()
+            .uri("/panchang/next-tithi?lang=hi")
+            .set_json(next_tithi()),
+    )
+    .await;
+    let labels = &next_tithi["data"]["labels"];
+    assert_eq!(
+        keys(labels),
+        ["paksha", "sunrise", "tithi", "tithi_end", "tithi_start"]
+    );
+    assert_eq!(labels["tithi"], "तिथि");
+
+    let request = test::TestRequest::post()
+        .uri("/panchang/compare")
+        .insert_header((header::ACCEPT_LANGUAGE, "ta"))
+        .set_json(compare());
+    let (_, compare) = call(request).await;
+    for system in compare["data"]["systems"].as_array().unwrap() {
+        assert!(system["labels"]["nakshatra"].is_string(), "{}", system);
+    }
+}
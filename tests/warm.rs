@@ -0,0 +1,85 @@
+//! Checks that the warm-cache job stores the sunrise Panchang in `panchang_cache`,
+//! under the key of any client request for the same sunrise Panchang.
+//!
+//! Needs a PostgreSQL database, configured through the same `DB_HOST`, `DB_PORT`,
+//! `DB_USER`, `DB_PASSWORD` and `DB_NAME` variables as the server, so it is ignored
+//! by default; run it with `cargo test --test warm -- --ignored`.
+
+#![cfg(feature = "server")]
+
+use chrono::{TimeZone, Utc};
+use panchang_rs::Ayanamsa;
+use panchang_rs::cache::cache_key;
+use panchang_rs::config::Config;
+use panchang_rs::panchang::{Location, PanchangRequest, ValidatedRequest};
+use panchang_rs::warm::warm_cache;
+use serde_json::json;
+use sqlx::PgPool;
+
+mod common;
+
+/// Whether a response is cached for the request
+async fn is_cached(pool: &PgPool, request: &PanchangRequest) -> bool {
+    sqlx::query("SELECT 1 FROM panchang_cache WHERE cache_key = $1")
+        .bind(cache_key(&ValidatedRequest::try_from(request).unwrap()))
+        .fetch_optional(pool)
+        .await
+        .expect("cache table is readable")
+        .is_some()
+}
+
+#[actix_web::test]
+#[ignore = "needs a PostgreSQL database, configured through DB_HOST"]
+async fn warm_job_populates_cache() {
+    let pool = common::connect(&Config::from_env().expect("valid configuration")).await;
+
+    let bengaluru = Location {
+        latitude: 12.9716,
+        longitude: 77.5946,
+        timezone: Some(String::from("Asia/Kolkata")),
+    };
+    let today = PanchangRequest::at_sunrise("15/08/2023", &bengaluru)
+        .map_options(|options| options.with_default_ayanamsa(Ayanamsa::Lahiri));
+    let day_after = PanchangRequest::at_sunrise("17/08/2023", &bengaluru)
+        .map_options(|options| options.with_default_ayanamsa(Ayanamsa::Lahiri));
+    for request in [&today, &day_after] {
+        sqlx::query("DELETE FROM panchang_cache WHERE cache_key = $1")
+            .bind(cache_key(&ValidatedRequest::try_from(request).unwrap()))
+            .execute(&pool)
+            .await
+            .expect("cache table is writable");
+    }
+
+    // 20:00 UTC is already the next day in IST
+    let now = Utc.with_ymd_and_hms(2023, 8, 14, 20, 0, 0).unwrap();
+    let warmed = warm_cache(
+        &pool,
+        std::slice::from_ref(&bengaluru),
+        Ayanamsa::Lahiri,
+        now,
+        3,
+    )
+    .await;
+
+    assert_eq!(warmed, 3);
+    assert!(is_cached(&pool, &today).await);
+    assert!(is_cached(&pool, &day_after).await);
+
+    // As a client would spell the request, with another time and a raw offset
+    let client: PanchangRequest = serde_json::from_value(json!({
+        "date": "15/8/2023",
+        "time": "09:30",
+        "zone": "+05:30",
+        "latitude": 12.9716,
+        "longitude": 77.5946,
+        "at_sunrise": true,
+    }))
+    .unwrap();
+    assert!(
+        is_cached(
+            &pool,
+            &client.map_options(|options| options.with_default_ayanamsa(Ayanamsa::Lahiri))
+        )
+        .await
+    );
+}
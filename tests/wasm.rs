@@ -0,0 +1,28 @@
+//! Runs the JavaScript bindings in a headless browser:
+//!
+//! ```text
+//! wasm-pack test --headless --firefox -- --no-default-features --features wasm
+//! ```
+
+#![cfg(all(target_arch = "wasm32", feature = "wasm"))]
+
+use panchang_rs::wasm::calculate_panchanga;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn calculates_known_tithi() {
+    // Noon IST in New Delhi
+    let value = calculate_panchanga("15/08/2023", "12:00", "+05:30", 28.6139, 77.2090).unwrap();
+    let response: serde_json::Value = serde_wasm_bindgen::from_value(value).unwrap();
+
+    assert_eq!(response["tithi"], "Chaturdashi");
+    assert_eq!(response["paksha"]["name"], "Krishna");
+}
+
+#[wasm_bindgen_test]
+fn rejects_invalid_date() {
+    assert!(calculate_panchanga("31/04/2023", "12:00", "+05:30", 28.6139, 77.2090).is_err());
+    assert!(calculate_panchanga("15/08/2023", "25:00", "+05:30", 28.6139, 77.2090).is_err());
+}
@@ -0,0 +1,42 @@
+//! Checks `yoga_bounds` against the Yoga changes Drik Panchang publishes for New
+//! Delhi: Bramha gave way to Indra at 08:47 IST on 22 January 2024, and Ayushman to
+//! Saubhagya at 16:25 IST on Diwali, 12 November 2023.
+//!
+//! The crate's simplified theory can shift a boundary by a few minutes, so each
+//! instant must match to within five.
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use panchang_rs::panchang::{Ayanamsa, calculate_panchanga_at, yoga_bounds};
+
+/// The Yoga in effect at an instant, in Lahiri
+fn yoga_at(instant: DateTime<Utc>) -> String {
+    calculate_panchanga_at(instant, Ayanamsa::Lahiri).current_yoga
+}
+
+/// Asserts that `actual` is within five minutes of `expected`
+fn assert_near(actual: DateTime<Utc>, expected: DateTime<Utc>) {
+    let off = (actual - expected).abs();
+    assert!(off <= Duration::minutes(5), "{actual} against {expected}");
+}
+
+#[test]
+fn indra_begins_on_the_morning_of_22_january_2024() {
+    let (start, end) = yoga_bounds(22, 1, 2024, 12.0, 5.5, Ayanamsa::Lahiri).unwrap();
+
+    // 08:47 IST
+    assert_near(start, Utc.with_ymd_and_hms(2024, 1, 22, 3, 17, 0).unwrap());
+    assert_eq!(yoga_at(start - Duration::minutes(1)), "Bramha");
+    assert_eq!(yoga_at(start + Duration::minutes(1)), "Indra");
+    assert_eq!(yoga_at(end - Duration::minutes(1)), "Indra");
+}
+
+#[test]
+fn ayushman_ends_on_the_afternoon_of_diwali_2023() {
+    let (start, end) = yoga_bounds(12, 11, 2023, 12.0, 5.5, Ayanamsa::Lahiri).unwrap();
+
+    // 16:25 IST
+    assert_near(end, Utc.with_ymd_and_hms(2023, 11, 12, 10, 55, 0).unwrap());
+    assert_eq!(yoga_at(start + Duration::minutes(1)), "Ayushman");
+    assert_eq!(yoga_at(end - Duration::minutes(1)), "Ayushman");
+    assert_eq!(yoga_at(end + Duration::minutes(1)), "Saubhagya");
+}